@@ -0,0 +1,116 @@
+//! # Cache Backends
+//!
+//! Storage the volatility cache shares its values and refresh work through, behind a common
+//! [`CacheBackend`] trait. The in-memory backend serves a single instance; with the `redis`
+//! feature and `REDIS_URL` set, replicas behind a load balancer share one Redis, so a token
+//! refreshed by one of them isn't fetched again by the others.
+
+use crate::background::volatility_cache::TokenSnapshot;
+use crate::config::AppConfig;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::warn;
+
+#[cfg(feature = "redis")]
+pub mod redis;
+
+/// Error returned by a backend call, carrying the underlying failure message.
+pub type BackendError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Shared store of computed token values and of claims on their refreshes.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Latest values shared for a token, if any have been stored and not expired.
+    async fn get(&self, token_address: &str) -> Result<Option<TokenSnapshot>, BackendError>;
+
+    /// Share a token's values for `ttl`, replacing previous ones.
+    async fn put(&self, token: &TokenSnapshot, ttl: Duration) -> Result<(), BackendError>;
+
+    /// Drop a token's shared values.
+    async fn remove(&self, token_address: &str) -> Result<(), BackendError>;
+
+    /// Claim a token's refresh for at most `ttl`, returning `false` when another instance
+    /// holds a claim on it.
+    async fn claim_refresh(&self, token_address: &str, ttl: Duration) -> Result<bool, BackendError>;
+
+    /// Release a claim taken by [`CacheBackend::claim_refresh`] once the refresh is done.
+    async fn release_refresh(&self, token_address: &str) -> Result<(), BackendError>;
+}
+
+/// Backend for `config`: Redis when `REDIS_URL` is set and the `redis` feature enabled,
+/// otherwise in-memory.
+pub fn from_config(config: &AppConfig) -> Arc<dyn CacheBackend> {
+    #[cfg(feature = "redis")]
+    if let Some(url) = config.redis_url.as_deref() {
+        match redis::RedisBackend::new(url) {
+            Ok(backend) => return Arc::new(backend),
+            Err(e) => warn!(error = %e, "Invalid REDIS_URL, sharing nothing between instances"),
+        }
+    }
+    #[cfg(not(feature = "redis"))]
+    if config.redis_url.is_some() {
+        warn!("REDIS_URL is set but the redis feature is disabled, sharing nothing between instances");
+    }
+
+    Arc::new(InMemoryBackend::default())
+}
+
+/// Backend kept in the process, for single-instance deployments.
+#[derive(Default)]
+pub struct InMemoryBackend {
+    /// Map of token address to its values and their expiry
+    tokens: RwLock<HashMap<String, (TokenSnapshot, Instant)>>,
+    /// Map of token address to the expiry of the claim on its refresh
+    claims: Mutex<HashMap<String, Instant>>,
+}
+
+#[async_trait]
+impl CacheBackend for InMemoryBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(&self, token_address: &str) -> Result<Option<TokenSnapshot>, BackendError> {
+        let tokens = self.tokens.read().await;
+        Ok(tokens
+            .get(token_address)
+            .filter(|(_, expires)| *expires > Instant::now())
+            .map(|(token, _)| token.clone()))
+    }
+
+    async fn put(&self, token: &TokenSnapshot, ttl: Duration) -> Result<(), BackendError> {
+        let mut tokens = self.tokens.write().await;
+        let now = Instant::now();
+        tokens.retain(|_, (_, expires)| *expires > now);
+        tokens.insert(token.token_address.clone(), (token.clone(), now + ttl));
+        Ok(())
+    }
+
+    async fn remove(&self, token_address: &str) -> Result<(), BackendError> {
+        self.tokens.write().await.remove(token_address);
+        self.claims.lock().await.remove(token_address);
+        Ok(())
+    }
+
+    async fn claim_refresh(&self, token_address: &str, ttl: Duration) -> Result<bool, BackendError> {
+        let mut claims = self.claims.lock().await;
+        let now = Instant::now();
+        if claims.get(token_address).is_some_and(|expires| *expires > now) {
+            return Ok(false);
+        }
+        claims.retain(|_, expires| *expires > now);
+        claims.insert(token_address.to_string(), now + ttl);
+        Ok(true)
+    }
+
+    async fn release_refresh(&self, token_address: &str) -> Result<(), BackendError> {
+        self.claims.lock().await.remove(token_address);
+        Ok(())
+    }
+}
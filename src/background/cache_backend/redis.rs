@@ -0,0 +1,97 @@
+//! Redis backend, shared by every instance pointed at the same server.
+
+use super::{BackendError, CacheBackend};
+use crate::background::volatility_cache::TokenSnapshot;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+/// Prefix of every key the backend writes, keeping it clear of other users of the server.
+const KEY_PREFIX: &str = "hist-volatility";
+
+/// Backend storing token values as JSON strings and claims as expiring keys.
+pub struct RedisBackend {
+    client: redis::Client,
+    /// Connection opened by the first call and reconnected as needed
+    connection: OnceCell<ConnectionManager>,
+}
+
+impl RedisBackend {
+    /// Backend for the server at `url`, connecting on first use.
+    ///
+    /// # Errors
+    /// - Returns the Redis error when `url` is not a valid Redis URL.
+    pub fn new(url: &str) -> Result<Self, redis::RedisError> {
+        Ok(Self {
+            client: redis::Client::open(url)?,
+            connection: OnceCell::new(),
+        })
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, BackendError> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await?;
+        Ok(connection.clone())
+    }
+}
+
+fn token_key(token_address: &str) -> String {
+    format!("{}:token:{}", KEY_PREFIX, token_address)
+}
+
+fn claim_key(token_address: &str) -> String {
+    format!("{}:claim:{}", KEY_PREFIX, token_address)
+}
+
+#[async_trait]
+impl CacheBackend for RedisBackend {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(&self, token_address: &str) -> Result<Option<TokenSnapshot>, BackendError> {
+        let mut connection = self.connection().await?;
+        let value: Option<String> = connection.get(token_key(token_address)).await?;
+        Ok(value.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    async fn put(&self, token: &TokenSnapshot, ttl: Duration) -> Result<(), BackendError> {
+        let mut connection = self.connection().await?;
+        let json = serde_json::to_string(token)?;
+        let _: () = connection
+            .pset_ex(token_key(&token.token_address), json, ttl.as_millis() as u64)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove(&self, token_address: &str) -> Result<(), BackendError> {
+        let mut connection = self.connection().await?;
+        let _: () = connection
+            .del(&[token_key(token_address), claim_key(token_address)])
+            .await?;
+        Ok(())
+    }
+
+    async fn claim_refresh(&self, token_address: &str, ttl: Duration) -> Result<bool, BackendError> {
+        let mut connection = self.connection().await?;
+        let claimed: Option<String> = redis::cmd("SET")
+            .arg(claim_key(token_address))
+            .arg(1)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut connection)
+            .await?;
+        Ok(claimed.is_some())
+    }
+
+    async fn release_refresh(&self, token_address: &str) -> Result<(), BackendError> {
+        let mut connection = self.connection().await?;
+        let _: () = connection.del(claim_key(token_address)).await?;
+        Ok(())
+    }
+}
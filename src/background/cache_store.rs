@@ -0,0 +1,93 @@
+//! On-disk store of the volatility cache, so a restart resumes from the last refresh cycle
+//! instead of refetching every token.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::warn;
+
+/// sled database holding one JSON record per cached token, keyed by token address.
+pub struct CacheStore {
+    db: sled::Db,
+}
+
+impl CacheStore {
+    /// Open the database at `path`, creating it if needed.
+    ///
+    /// # Errors
+    /// - Returns the sled error when the database can't be opened, e.g. because another
+    ///   process holds it.
+    pub fn open(path: &Path) -> Result<Self, sled::Error> {
+        // Every write is flushed by `save`, so no background flusher holds the database open
+        // once the store is dropped
+        let db = sled::Config::new().path(path).flush_every_ms(None).open()?;
+        Ok(Self { db })
+    }
+
+    /// Replace the stored records with `records`, and flush them to disk.
+    ///
+    /// # Errors
+    /// - Returns a message when a record can't be encoded or the database can't be written.
+    pub async fn save<T: Serialize>(
+        &self,
+        records: impl IntoIterator<Item = (String, T)>,
+    ) -> Result<(), String> {
+        let mut batch = sled::Batch::default();
+        let mut kept = HashSet::new();
+        for (key, record) in records {
+            let value = serde_json::to_vec(&record).map_err(|e| e.to_string())?;
+            batch.insert(key.as_bytes(), value);
+            kept.insert(key.into_bytes());
+        }
+        for key in self.db.iter().keys() {
+            let key = key.map_err(|e| e.to_string())?;
+            if !kept.contains(key.as_ref()) {
+                batch.remove(key);
+            }
+        }
+        self.db.apply_batch(batch).map_err(|e| e.to_string())?;
+        self.db.flush_async().await.map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Write `records` over the stored ones with the same keys and delete the `removed`
+    /// keys, keeping every other record, and flush them to disk.
+    ///
+    /// # Errors
+    /// - Returns a message when a record can't be encoded or the database can't be written.
+    pub async fn update<T: Serialize>(
+        &self,
+        records: impl IntoIterator<Item = (String, T)>,
+        removed: impl IntoIterator<Item = String>,
+    ) -> Result<(), String> {
+        let mut batch = sled::Batch::default();
+        for (key, record) in records {
+            let value = serde_json::to_vec(&record).map_err(|e| e.to_string())?;
+            batch.insert(key.as_bytes(), value);
+        }
+        for key in removed {
+            batch.remove(key.as_bytes());
+        }
+        self.db.apply_batch(batch).map_err(|e| e.to_string())?;
+        self.db.flush_async().await.map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Every stored record, skipping those that can't be read or decoded as `T`.
+    pub fn load<T: DeserializeOwned>(&self) -> Vec<T> {
+        self.db
+            .iter()
+            .filter_map(|entry| {
+                let (key, value) = entry
+                    .map_err(|e| warn!(error = %e, "Failed to read a stored cache record"))
+                    .ok()?;
+                serde_json::from_slice(&value)
+                    .map_err(|e| {
+                        warn!(key = %String::from_utf8_lossy(&key), error = %e, "Skipping undecodable cache record")
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+}
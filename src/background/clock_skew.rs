@@ -0,0 +1,95 @@
+//! Detection of host clock skew against the price provider's server time.
+//!
+//! Trailing windows and staleness checks are computed from the host clock, so a clock that
+//! is far off silently shifts every window. The provider's `Date` header is a cheap second
+//! opinion.
+
+use crate::background::health_monitor::HealthMonitor;
+use crate::state::AppState;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How often the skew is re-measured after the startup check.
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Response header carrying the skew, in seconds, while it is beyond the threshold.
+pub const CLOCK_SKEW_HEADER: &str = "x-clock-skew-seconds";
+
+/// Measures host clock minus server clock, in seconds, from the `Date` header of a request
+/// to `url`. Any response status will do; the local time is taken halfway through the
+/// round trip.
+pub async fn measure_skew(client: &reqwest::Client, url: &str) -> Result<i64, String> {
+    let sent_at = Utc::now();
+    let response = client.head(url).send().await.map_err(|e| e.to_string())?;
+    let received_at = Utc::now();
+
+    let server_time = response
+        .headers()
+        .get(header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .ok_or_else(|| format!("{} returned no valid Date header", url))?;
+
+    let local_time = sent_at + (received_at - sent_at) / 2;
+    Ok((local_time - server_time.with_timezone(&Utc)).num_seconds())
+}
+
+/// Measures the skew against `url` once and records it on `monitor`, warning when it is
+/// beyond `threshold_secs`.
+pub async fn check_clock_skew(
+    client: &reqwest::Client,
+    url: &str,
+    monitor: &HealthMonitor,
+    threshold_secs: u64,
+) {
+    match measure_skew(client, url).await {
+        Ok(skew_seconds) => {
+            let exceeded = skew_seconds.unsigned_abs() > threshold_secs;
+            if exceeded {
+                warn!(
+                    skew_seconds = %skew_seconds,
+                    threshold_secs = %threshold_secs,
+                    "Host clock is skewed against the price provider; date windows may be off"
+                );
+            } else {
+                info!(skew_seconds = %skew_seconds, "Checked host clock against the price provider");
+            }
+            monitor.record_clock_skew(skew_seconds, exceeded).await;
+        }
+        Err(e) => warn!(error = %e, "Could not check host clock skew"),
+    }
+}
+
+/// Checks the clock skew against `url` now and every [`CHECK_INTERVAL`] after.
+pub fn start_clock_skew_check(url: String, monitor: HealthMonitor, threshold_secs: u64) {
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        loop {
+            check_clock_skew(&client, &url, &monitor, threshold_secs).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Middleware adding `X-Clock-Skew-Seconds` to responses while the last measured skew is
+/// beyond the threshold, flagging that their date windows may be shifted.
+pub async fn annotate_clock_skew(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    if let Some(skew_seconds) = state.monitor.exceeded_clock_skew().await {
+        response
+            .headers_mut()
+            .insert(CLOCK_SKEW_HEADER, HeaderValue::from(skew_seconds));
+    }
+    response
+}
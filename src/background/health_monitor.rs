@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::utils::timestamp_format;
+
+/// Number of recent upstream calls the error rate is computed over
+const UPSTREAM_WINDOW: usize = 100;
+/// Number of recent background cycles the success rate is computed over
+const CYCLE_WINDOW: usize = 60;
+/// Number of incident markers kept for the status page
+const MAX_INCIDENTS: usize = 20;
+
+/// A notable failure worth surfacing on the status page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Incident {
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub at: DateTime<Utc>,
+    pub component: String,
+    pub message: String,
+}
+
+/// Outcome of one background refresh cycle.
+#[derive(Debug, Clone, Copy)]
+struct CycleOutcome {
+    finished_at: DateTime<Utc>,
+    succeeded: bool,
+}
+
+/// Latest comparison of the host clock against the provider's.
+#[derive(Debug, Clone, Copy)]
+struct ClockCheck {
+    skew_seconds: i64,
+    exceeded: bool,
+}
+
+#[derive(Debug, Default)]
+struct MonitorState {
+    upstream_calls: VecDeque<bool>,
+    cycles: VecDeque<CycleOutcome>,
+    incidents: VecDeque<Incident>,
+    clock: Option<ClockCheck>,
+}
+
+/// Records upstream call and background cycle outcomes over sliding windows,
+/// so the status endpoint can summarize subsystem health over time
+#[derive(Clone)]
+pub struct HealthMonitor {
+    started_at: DateTime<Utc>,
+    state: Arc<RwLock<MonitorState>>,
+}
+
+/// Point-in-time summary of the recorded outcomes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthSnapshot {
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: i64,
+    pub upstream_requests: usize,
+    pub upstream_errors: usize,
+    pub upstream_error_rate: f64,
+    pub background_cycles: usize,
+    pub background_cycles_succeeded: usize,
+    pub background_success_rate: f64,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_cycle_at: Option<DateTime<Utc>>,
+    pub last_cycle_succeeded: Option<bool>,
+    /// Host clock minus the provider's clock at the last check; positive when the host is
+    /// ahead. `None` until a check succeeded.
+    pub clock_skew_seconds: Option<i64>,
+    /// Whether the last measured skew was beyond `CLOCK_SKEW_THRESHOLD_SECS`.
+    pub clock_skewed: bool,
+    pub last_incidents: Vec<Incident>,
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthMonitor {
+    /// Create a monitor whose uptime starts now
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            state: Arc::new(RwLock::new(MonitorState::default())),
+        }
+    }
+
+    /// Record the outcome of a call to the price provider
+    pub async fn record_upstream(&self, result: Result<(), String>) {
+        let mut state = self.state.write().await;
+        push_bounded(&mut state.upstream_calls, result.is_ok(), UPSTREAM_WINDOW);
+        if let Err(message) = result {
+            let incident = Incident {
+                at: Utc::now(),
+                component: "upstream".to_string(),
+                message,
+            };
+            push_bounded(&mut state.incidents, incident, MAX_INCIDENTS);
+        }
+    }
+
+    /// Record the outcome of a background refresh cycle
+    pub async fn record_cycle(&self, failed_tokens: usize, total_tokens: usize) {
+        let mut state = self.state.write().await;
+        let outcome = CycleOutcome {
+            finished_at: Utc::now(),
+            succeeded: failed_tokens == 0,
+        };
+        push_bounded(&mut state.cycles, outcome, CYCLE_WINDOW);
+        if failed_tokens > 0 {
+            let incident = Incident {
+                at: outcome.finished_at,
+                component: "background".to_string(),
+                message: format!("{} of {} token refreshes failed", failed_tokens, total_tokens),
+            };
+            push_bounded(&mut state.incidents, incident, MAX_INCIDENTS);
+        }
+    }
+
+    /// Record an incident of the background task that isn't tied to a refresh cycle
+    pub async fn record_background_incident(&self, message: String) {
+        let mut state = self.state.write().await;
+        let incident = Incident {
+            at: Utc::now(),
+            component: "background".to_string(),
+            message,
+        };
+        push_bounded(&mut state.incidents, incident, MAX_INCIDENTS);
+    }
+
+    /// Record a measured clock skew, with an incident when it is beyond the threshold
+    pub async fn record_clock_skew(&self, skew_seconds: i64, exceeded: bool) {
+        let mut state = self.state.write().await;
+        state.clock = Some(ClockCheck {
+            skew_seconds,
+            exceeded,
+        });
+        if exceeded {
+            let incident = Incident {
+                at: Utc::now(),
+                component: "clock".to_string(),
+                message: format!("Host clock is {}s off the provider's", skew_seconds),
+            };
+            push_bounded(&mut state.incidents, incident, MAX_INCIDENTS);
+        }
+    }
+
+    /// Last measured clock skew in seconds, if it was beyond the threshold
+    pub async fn exceeded_clock_skew(&self) -> Option<i64> {
+        let state = self.state.read().await;
+        state
+            .clock
+            .filter(|check| check.exceeded)
+            .map(|check| check.skew_seconds)
+    }
+
+    /// Summarize the recorded outcomes
+    pub async fn snapshot(&self) -> HealthSnapshot {
+        let state = self.state.read().await;
+        let upstream_errors = state.upstream_calls.iter().filter(|ok| !**ok).count();
+        let cycles_succeeded = state.cycles.iter().filter(|cycle| cycle.succeeded).count();
+        let last_cycle = state.cycles.back();
+
+        HealthSnapshot {
+            started_at: self.started_at,
+            uptime_seconds: (Utc::now() - self.started_at).num_seconds(),
+            upstream_requests: state.upstream_calls.len(),
+            upstream_errors,
+            upstream_error_rate: ratio(upstream_errors, state.upstream_calls.len()),
+            background_cycles: state.cycles.len(),
+            background_cycles_succeeded: cycles_succeeded,
+            background_success_rate: if state.cycles.is_empty() {
+                1.0
+            } else {
+                ratio(cycles_succeeded, state.cycles.len())
+            },
+            last_cycle_at: last_cycle.map(|cycle| cycle.finished_at),
+            last_cycle_succeeded: last_cycle.map(|cycle| cycle.succeeded),
+            clock_skew_seconds: state.clock.map(|check| check.skew_seconds),
+            clock_skewed: state.clock.is_some_and(|check| check.exceeded),
+            last_incidents: state.incidents.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn push_bounded<T>(queue: &mut VecDeque<T>, value: T, capacity: usize) {
+    if queue.len() == capacity {
+        queue.pop_front();
+    }
+    queue.push_back(value);
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_snapshot_rates_and_incidents() {
+        let monitor = HealthMonitor::new();
+        monitor.record_upstream(Ok(())).await;
+        monitor.record_upstream(Err("timeout".to_string())).await;
+        monitor.record_cycle(0, 2).await;
+        monitor.record_cycle(1, 2).await;
+
+        let snapshot = monitor.snapshot().await;
+        assert_eq!(snapshot.upstream_requests, 2);
+        assert_eq!(snapshot.upstream_error_rate, 0.5);
+        assert_eq!(snapshot.background_success_rate, 0.5);
+        assert_eq!(snapshot.last_cycle_succeeded, Some(false));
+        // Most recent incident first
+        assert_eq!(snapshot.last_incidents.len(), 2);
+        assert_eq!(snapshot.last_incidents[0].component, "background");
+    }
+}
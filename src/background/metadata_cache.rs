@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::utils::chain::Chain;
+
+/// How long fetched token metadata is reused; names and logos change rarely, if ever
+pub const METADATA_TTL_HOURS: i64 = 24;
+
+/// Descriptive fields of a token, as reported by the provider
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub logo_uri: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedMetadata {
+    metadata: TokenMetadata,
+    fetched_at: DateTime<Utc>,
+}
+
+/// Cache of token metadata by chain and address, so labelling results doesn't cost a
+/// Birdeye call per view
+#[derive(Clone)]
+pub struct MetadataCache {
+    entries: Arc<RwLock<HashMap<(Chain, String), CachedMetadata>>>,
+    ttl: ChronoDuration,
+}
+
+impl Default for MetadataCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetadataCache {
+    /// Create an empty cache whose entries expire after [`METADATA_TTL_HOURS`]
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            ttl: ChronoDuration::hours(METADATA_TTL_HOURS),
+        }
+    }
+
+    /// Cached metadata of a token, if fetched within the TTL
+    pub async fn get(&self, chain: Chain, token_address: &str) -> Option<TokenMetadata> {
+        let entries = self.entries.read().await;
+        entries
+            .get(&(chain, token_address.to_string()))
+            .filter(|entry| Utc::now() - entry.fetched_at < self.ttl)
+            .map(|entry| entry.metadata.clone())
+    }
+
+    /// Store freshly fetched metadata of a token
+    pub async fn insert(&self, chain: Chain, token_address: String, metadata: TokenMetadata) {
+        self.entries.write().await.insert(
+            (chain, token_address),
+            CachedMetadata {
+                metadata,
+                fetched_at: Utc::now(),
+            },
+        );
+    }
+}
@@ -1 +1,10 @@
-pub mod volatility_cache; 
\ No newline at end of file
+pub mod volatility_cache;
+pub mod price_cache;
+pub mod health_monitor;
+pub mod clock_skew;
+pub mod metadata_cache;
+pub mod token_registry;
+pub mod cache_backend;
+pub mod volatility_history;
+#[cfg(feature = "persistence")]
+pub mod cache_store;
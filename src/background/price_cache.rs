@@ -0,0 +1,461 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use crate::background::health_monitor::HealthMonitor;
+use crate::math::{Candle, Interval};
+use crate::provider::{PriceProvider, ProviderError};
+use crate::utils::address_type::AddressType;
+use crate::utils::chain::Chain;
+
+/// Identifies a raw price series fetched from the provider.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeriesKey {
+    pub chain: Chain,
+    pub address_type: AddressType,
+    pub token_address: String,
+    pub interval: Interval,
+    /// Whether the series holds full OHLCV candles rather than closes only.
+    pub ohlc: bool,
+}
+
+impl SeriesKey {
+    /// Key of the same series with full OHLCV candles, whose closes can answer this one.
+    fn with_ohlc(&self) -> Self {
+        Self {
+            ohlc: true,
+            ..self.clone()
+        }
+    }
+}
+
+/// A fetched series together with the time range it covers.
+#[derive(Debug, Clone)]
+struct CachedSeries {
+    candles: Vec<Candle>,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+    fetched_at: DateTime<Utc>,
+    /// Providers the candles were fetched from.
+    providers: BTreeSet<&'static str>,
+}
+
+/// How much of a requested range the price cache can answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Coverage {
+    /// A fresh entry covers the whole range.
+    Full(Vec<Candle>),
+    /// A fresh entry overlaps the range; only the `gaps` still need fetching.
+    Partial {
+        candles: Vec<Candle>,
+        gaps: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+    },
+    /// Nothing usable is cached; the whole range must be fetched.
+    Miss,
+}
+
+/// Candles read through the price cache, and the providers that served them.
+#[derive(Debug, Clone)]
+pub struct FetchedSeries {
+    pub candles: Vec<Candle>,
+    pub providers: Vec<&'static str>,
+}
+
+/// Short-lived cache of raw price series, so repeated queries over the same
+/// token and range don't each cost a Birdeye call
+///
+/// Shared by request handlers and the volatility cache's background refresh, so every window
+/// and estimator over a token reuses the same fetched candles. Closes-only lookups are
+/// answered from OHLCV entries too.
+#[derive(Clone)]
+pub struct PriceCache {
+    entries: Arc<RwLock<HashMap<SeriesKey, CachedSeries>>>,
+    ttl: ChronoDuration,
+    max_entries: usize,
+    /// Entries dropped for having expired or to keep the cache under `max_entries`.
+    pruned: Arc<AtomicU64>,
+}
+
+impl PriceCache {
+    /// Create a new price cache whose entries expire after `ttl_secs` seconds, holding at
+    /// most `max_entries` series
+    pub fn new(ttl_secs: u64, max_entries: usize) -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            ttl: ChronoDuration::seconds(ttl_secs as i64),
+            max_entries: max_entries.max(1),
+            pruned: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Get the cached candles in `[from_date, to_date]`, if a fresh entry covers the whole range
+    pub async fn get(
+        &self,
+        key: &SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> Option<Vec<Candle>> {
+        match self.resolve(key, from_date, to_date).await {
+            Coverage::Full(candles) => Some(candles),
+            _ => None,
+        }
+    }
+
+    /// Decide whether `[from_date, to_date]` is fully, partially or not at all covered by a fresh entry
+    pub async fn resolve(
+        &self,
+        key: &SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> Coverage {
+        let entries = self.entries.read().await;
+        let Some((_, entry)) = self.fresh_entry(&entries, key, from_date, to_date) else {
+            return Coverage::Miss;
+        };
+
+        let candles = entry
+            .candles
+            .iter()
+            .filter(|candle| (from_date.timestamp()..=to_date.timestamp()).contains(&candle.unix_time))
+            .copied()
+            .collect();
+
+        let mut gaps = Vec::new();
+        if from_date < entry.from_date {
+            gaps.push((from_date, entry.from_date));
+        }
+        if entry.to_date < to_date {
+            gaps.push((entry.to_date, to_date));
+        }
+
+        if gaps.is_empty() {
+            Coverage::Full(candles)
+        } else {
+            Coverage::Partial { candles, gaps }
+        }
+    }
+
+    /// The fresh entry for `key` overlapping `[from_date, to_date]` and the key it is stored
+    /// under, falling back to the OHLCV entry of the same series for closes-only keys
+    fn fresh_entry<'a>(
+        &self,
+        entries: &'a HashMap<SeriesKey, CachedSeries>,
+        key: &SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> Option<(&'a SeriesKey, &'a CachedSeries)> {
+        let usable = |(_, entry): &(&SeriesKey, &CachedSeries)| {
+            Utc::now() - entry.fetched_at < self.ttl
+                && entry.from_date <= to_date
+                && from_date <= entry.to_date
+        };
+        let own = entries.get_key_value(key).filter(usable);
+        if own.is_some() || key.ohlc {
+            return own;
+        }
+        entries.get_key_value(&key.with_ohlc()).filter(usable)
+    }
+
+    /// Providers the fresh entry for `key` was fetched from, empty without one
+    pub async fn providers(&self, key: &SeriesKey) -> Vec<&'static str> {
+        let entries = self.entries.read().await;
+        self.fresh_entry(&entries, key, DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)
+            .map(|(_, entry)| entry.providers.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// When the fresh entry for `key` was first fetched, `None` without one
+    pub async fn fetched_at(&self, key: &SeriesKey) -> Option<DateTime<Utc>> {
+        let entries = self.entries.read().await;
+        self.fresh_entry(&entries, key, DateTime::<Utc>::MIN_UTC, DateTime::<Utc>::MAX_UTC)
+            .map(|(_, entry)| entry.fetched_at)
+    }
+
+    /// Get the candles in `[from_date, to_date]`, fetching from `provider` only the parts
+    /// no fresh entry covers and caching them
+    ///
+    /// Every provider call is recorded on `monitor`. Fails with the first provider error.
+    pub async fn fetch_through(
+        &self,
+        provider: &dyn PriceProvider,
+        monitor: &HealthMonitor,
+        key: &SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+    ) -> Result<FetchedSeries, ProviderError> {
+        // Extend the OHLCV entry answering a closes-only key rather than start a second entry
+        let key = &{
+            let entries = self.entries.read().await;
+            self.fresh_entry(&entries, key, from_date, to_date)
+                .map_or_else(|| key.clone(), |(serving, _)| serving.clone())
+        };
+        let (mut candles, gaps) = match self.resolve(key, from_date, to_date).await {
+            Coverage::Full(candles) => {
+                let providers = self.providers(key).await;
+                return Ok(FetchedSeries { candles, providers });
+            }
+            Coverage::Partial { candles, gaps } => (candles, gaps),
+            Coverage::Miss => (Vec::new(), vec![(from_date, to_date)]),
+        };
+        let mut providers: BTreeSet<&'static str> = self.providers(key).await.into_iter().collect();
+
+        for (gap_from, gap_to) in gaps {
+            let result = provider
+                .fetch_history(&key.token_address, key.interval, gap_from, gap_to, key.ohlc)
+                .await;
+            monitor
+                .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+                .await;
+            let fetched = result?;
+
+            providers.insert(fetched.provider);
+            self.extend(key.clone(), gap_from, gap_to, fetched.candles.clone(), fetched.provider)
+                .await;
+            candles.extend(fetched.candles);
+        }
+
+        // Gap boundaries are inclusive on both sides, so edge candles may appear twice
+        candles.sort_by_key(|candle| candle.unix_time);
+        candles.dedup_by_key(|candle| candle.unix_time);
+
+        Ok(FetchedSeries {
+            candles,
+            providers: providers.into_iter().collect(),
+        })
+    }
+
+    /// Drop the entry for `key`, so the next request for it goes to the provider
+    pub async fn invalidate(&self, key: &SeriesKey) {
+        self.entries.write().await.remove(key);
+    }
+
+    /// Drop every expired entry, returning how many were dropped
+    pub async fn prune_expired(&self) -> usize {
+        let mut entries = self.entries.write().await;
+        let pruned = self.drop_expired(&mut entries);
+        self.pruned.fetch_add(pruned as u64, Ordering::Relaxed);
+        pruned
+    }
+
+    /// Number of series held, expired ones included until they are pruned
+    pub async fn entry_count(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Total entries dropped since startup, for having expired or to make room
+    pub fn pruned(&self) -> u64 {
+        self.pruned.load(Ordering::Relaxed)
+    }
+
+    /// Store a series freshly fetched from `provider` covering `[from_date, to_date]`
+    pub async fn insert(
+        &self,
+        key: SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        candles: Vec<Candle>,
+        provider: &'static str,
+    ) {
+        let mut entries = self.entries.write().await;
+        self.make_room(&mut entries, &key);
+        entries.insert(
+            key,
+            CachedSeries {
+                candles,
+                from_date,
+                to_date,
+                fetched_at: Utc::now(),
+                providers: BTreeSet::from([provider]),
+            },
+        );
+    }
+
+    /// Merge a freshly fetched gap into a fresh overlapping entry, or replace the entry otherwise.
+    ///
+    /// A merged entry keeps its original fetch time so it never outlives its oldest candles.
+    pub async fn extend(
+        &self,
+        key: SeriesKey,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        candles: Vec<Candle>,
+        provider: &'static str,
+    ) {
+        let mut entries = self.entries.write().await;
+        let now = Utc::now();
+        match entries.get_mut(&key) {
+            Some(entry)
+                if now - entry.fetched_at < self.ttl
+                    && entry.from_date <= to_date
+                    && from_date <= entry.to_date =>
+            {
+                entry.candles.extend(candles);
+                entry.candles.sort_by_key(|candle| candle.unix_time);
+                entry.candles.dedup_by_key(|candle| candle.unix_time);
+                entry.from_date = entry.from_date.min(from_date);
+                entry.to_date = entry.to_date.max(to_date);
+                entry.providers.insert(provider);
+            }
+            _ => {
+                self.make_room(&mut entries, &key);
+                entries.insert(
+                    key,
+                    CachedSeries {
+                        candles,
+                        from_date,
+                        to_date,
+                        fetched_at: now,
+                        providers: BTreeSet::from([provider]),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Before `key` is added, drop the expired entries, then the oldest fetched ones while
+    /// the cache is still full, so series requested once don't pile up
+    fn make_room(&self, entries: &mut HashMap<SeriesKey, CachedSeries>, key: &SeriesKey) {
+        if entries.contains_key(key) {
+            return;
+        }
+        let mut pruned = self.drop_expired(entries);
+        while entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.fetched_at)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => entries.remove(&oldest),
+                None => break,
+            };
+            pruned += 1;
+        }
+        self.pruned.fetch_add(pruned as u64, Ordering::Relaxed);
+    }
+
+    fn drop_expired(&self, entries: &mut HashMap<SeriesKey, CachedSeries>) -> usize {
+        let before = entries.len();
+        let now = Utc::now();
+        entries.retain(|_, entry| now - entry.fetched_at < self.ttl);
+        before - entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn key() -> SeriesKey {
+        SeriesKey {
+            chain: Chain::Solana,
+            address_type: AddressType::Token,
+            token_address: "So11111111111111111111111111111111111111112".to_string(),
+            interval: Interval::OneDay,
+            ohlc: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_returns_sub_range_of_covering_entry() {
+        let cache = PriceCache::new(60, 100);
+        let day = |d| Utc.with_ymd_and_hms(2025, 1, d, 0, 0, 0).unwrap();
+        let candles = (1..=5)
+            .map(|d| Candle::from_price(day(d).timestamp(), d as f64))
+            .collect();
+        cache.insert(key(), day(1), day(5), candles, "birdeye").await;
+
+        let hit = cache.get(&key(), day(2), day(4)).await.expect("Should be covered");
+        assert_eq!(hit.len(), 3);
+        assert!(cache.get(&key(), day(2), day(6)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_reports_only_missing_gaps() {
+        let cache = PriceCache::new(60, 100);
+        let day = |d| Utc.with_ymd_and_hms(2025, 1, d, 0, 0, 0).unwrap();
+        let candles = (3..=5)
+            .map(|d| Candle::from_price(day(d).timestamp(), d as f64))
+            .collect();
+        cache.insert(key(), day(3), day(5), candles, "birdeye").await;
+
+        match cache.resolve(&key(), day(1), day(7)).await {
+            Coverage::Partial { candles, gaps } => {
+                assert_eq!(candles.len(), 3);
+                assert_eq!(gaps, vec![(day(1), day(3)), (day(5), day(7))]);
+            }
+            other => panic!("Expected partial coverage, got {other:?}"),
+        }
+        assert_eq!(cache.resolve(&key(), day(8), day(9)).await, Coverage::Miss);
+
+        let gap = vec![Candle::from_price(day(5).timestamp(), 5.0), Candle::from_price(day(6).timestamp(), 6.0)];
+        cache.extend(key(), day(5), day(6), gap, "coingecko").await;
+        let merged = cache.get(&key(), day(3), day(6)).await.expect("Should be covered after extend");
+        assert_eq!(merged.len(), 4);
+        assert_eq!(cache.providers(&key()).await, vec!["birdeye", "coingecko"]);
+    }
+
+    #[tokio::test]
+    async fn test_closes_only_lookups_are_answered_from_ohlc_entries() {
+        let cache = PriceCache::new(60, 100);
+        let day = |d| Utc.with_ymd_and_hms(2025, 1, d, 0, 0, 0).unwrap();
+        let ohlc_key = SeriesKey { ohlc: true, ..key() };
+        let candles = (1..=5)
+            .map(|d| Candle::from_price(day(d).timestamp(), d as f64))
+            .collect();
+        cache.insert(ohlc_key.clone(), day(1), day(5), candles, "birdeye").await;
+
+        assert_eq!(cache.get(&key(), day(2), day(4)).await.map(|hit| hit.len()), Some(3));
+        assert_eq!(cache.providers(&key()).await, vec!["birdeye"]);
+
+        // Closes can't stand in for OHLCV candles
+        let closes_only = PriceCache::new(60, 100);
+        closes_only.insert(key(), day(1), day(5), vec![], "birdeye").await;
+        assert!(closes_only.get(&ohlc_key, day(2), day(4)).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_ignores_expired_entries() {
+        let cache = PriceCache::new(0, 100);
+        let now = Utc::now();
+        cache.insert(key(), now, now, vec![], "birdeye").await;
+        assert!(cache.get(&key(), now, now).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_oldest_entries_make_room_for_new_ones() {
+        let token_key = |token_address: &str| SeriesKey {
+            token_address: token_address.to_string(),
+            ..key()
+        };
+        let now = Utc::now();
+
+        let expiring = PriceCache::new(0, 100);
+        for token_address in ["a", "b", "c"] {
+            expiring.insert(token_key(token_address), now, now, vec![], "birdeye").await;
+        }
+        assert_eq!(expiring.entries.read().await.len(), 1);
+
+        let bounded = PriceCache::new(60, 2);
+        for token_address in ["a", "b", "c"] {
+            bounded.insert(token_key(token_address), now, now, vec![], "birdeye").await;
+        }
+        let entries = bounded.entries.read().await;
+        assert_eq!(entries.len(), 2);
+        assert!(!entries.contains_key(&token_key("a")));
+        assert_eq!(bounded.pruned(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired_drops_and_counts_expired_entries() {
+        let cache = PriceCache::new(0, 100);
+        let now = Utc::now();
+        cache.insert(key(), now, now, vec![], "birdeye").await;
+        assert_eq!(cache.entry_count().await, 1);
+
+        assert_eq!(cache.prune_expired().await, 1);
+        assert_eq!(cache.entry_count().await, 0);
+        assert_eq!(cache.pruned(), 1);
+    }
+}
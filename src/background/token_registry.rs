@@ -0,0 +1,145 @@
+//! Registry of per-token refresh settings for the volatility cache's scheduler.
+
+use crate::background::volatility_cache::CACHED_WINDOW_DAYS;
+use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// How the background task refreshes one token, overriding the demand-driven defaults.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRefreshConfig {
+    /// Seconds between refreshes, whether or not the token is hot. Beyond
+    /// `VOLATILITY_CACHE_MAX_AGE_SECS`, requests in between recompute the value.
+    #[serde(default)]
+    pub refresh_interval_secs: Option<u64>,
+    /// Trailing windows cached for the token, each one of the cached window lengths; the
+    /// longest is its headline value. All of them when unset.
+    #[serde(default)]
+    pub window_days: Option<Vec<u32>>,
+}
+
+/// Refresh settings by token mint, as configured in `TOKEN_REFRESH_CONFIG`.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    tokens: HashMap<String, TokenRefreshConfig>,
+}
+
+impl TokenRegistry {
+    /// Registry parsed from `json`, an object of mint to refresh settings, for a cache evicting
+    /// values after `evict_after_secs` without a refresh.
+    ///
+    /// # Errors
+    /// - Returns a message for malformed JSON, a refresh interval that is zero or longer than
+    ///   `evict_after_secs` (the value would be evicted between refreshes), or windows that are
+    ///   empty or not among the cached window lengths.
+    pub fn parse(json: &str, evict_after_secs: u64) -> Result<Self, String> {
+        let tokens: HashMap<String, TokenRefreshConfig> = serde_json::from_str(json)
+            .map_err(|e| format!("TOKEN_REFRESH_CONFIG is not a valid token map: {}", e))?;
+
+        for (mint, settings) in &tokens {
+            if settings.refresh_interval_secs == Some(0) {
+                return Err(format!(
+                    "TOKEN_REFRESH_CONFIG has a zero refresh interval for {}.",
+                    mint
+                ));
+            }
+            if let Some(interval) = settings.refresh_interval_secs.filter(|&secs| secs > evict_after_secs) {
+                return Err(format!(
+                    "TOKEN_REFRESH_CONFIG refresh interval for {} ({}s) exceeds VOLATILITY_CACHE_EVICT_AFTER_SECS ({}s).",
+                    mint, interval, evict_after_secs
+                ));
+            }
+            if let Some(windows) = &settings.window_days {
+                if windows.is_empty() || windows.iter().any(|days| !CACHED_WINDOW_DAYS.contains(days)) {
+                    return Err(format!(
+                        "TOKEN_REFRESH_CONFIG windows for {} must be among {:?}.",
+                        mint, CACHED_WINDOW_DAYS
+                    ));
+                }
+            }
+        }
+        Ok(Self { tokens })
+    }
+
+    /// Registry for `config`, empty when `TOKEN_REFRESH_CONFIG` is unset or invalid
+    /// (`AppConfig::from_env` rejects that case up front).
+    pub fn from_config(config: &AppConfig) -> Self {
+        match config
+            .token_refresh_config
+            .as_deref()
+            .map(|json| Self::parse(json, config.volatility_cache_evict_after_secs))
+        {
+            Some(Ok(registry)) => registry,
+            Some(Err(e)) => {
+                warn!(error = %e, "Ignoring configured token refresh settings");
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Configured refresh interval of a token, if it has one.
+    pub fn refresh_interval_secs(&self, mint: &str) -> Option<u64> {
+        self.tokens.get(mint).and_then(|settings| settings.refresh_interval_secs)
+    }
+
+    /// Whether the window of `days` is cached for a token.
+    pub fn caches_window(&self, mint: &str, days: u32) -> bool {
+        match self.tokens.get(mint).and_then(|settings| settings.window_days.as_ref()) {
+            Some(windows) => windows.contains(&days),
+            None => true,
+        }
+    }
+
+    /// Length in days of a token's headline window: its longest cached window.
+    pub fn headline_window_days(&self, mint: &str) -> u32 {
+        CACHED_WINDOW_DAYS
+            .into_iter()
+            .filter(|&days| self.caches_window(mint, days))
+            .max()
+            .unwrap_or(CACHED_WINDOW_DAYS[CACHED_WINDOW_DAYS.len() - 1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_tokens_override_defaults() {
+        let registry = TokenRegistry::parse(
+            r#"{
+                "So11111111111111111111111111111111111111112": { "refreshIntervalSecs": 60, "windowDays": [30, 90] },
+                "longTail": { "refreshIntervalSecs": 3600, "windowDays": [7, 30] }
+            }"#,
+            86_400,
+        )
+        .expect("should parse");
+
+        assert_eq!(registry.refresh_interval_secs("longTail"), Some(3600));
+        assert_eq!(registry.headline_window_days("longTail"), 30);
+        assert!(!registry.caches_window("So11111111111111111111111111111111111111112", 7));
+        assert_eq!(registry.headline_window_days("unconfigured"), 90);
+        assert_eq!(registry.refresh_interval_secs("unconfigured"), None);
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        assert!(TokenRegistry::parse(r#"{ "mint": { "windowDays": [14] } }"#, 86_400).is_err());
+        assert!(TokenRegistry::parse(r#"{ "mint": { "windowDays": [] } }"#, 86_400).is_err());
+        assert!(TokenRegistry::parse(r#"{ "mint": { "refreshIntervalSecs": 0 } }"#, 86_400).is_err());
+        assert!(TokenRegistry::parse("not json", 86_400).is_err());
+    }
+
+    #[test]
+    fn test_refresh_intervals_beyond_eviction_are_rejected() {
+        let json = r#"{ "mint": { "refreshIntervalSecs": 7200 } }"#;
+        assert!(TokenRegistry::parse(json, 7200).is_ok());
+        assert_eq!(
+            TokenRegistry::parse(json, 3600).unwrap_err(),
+            "TOKEN_REFRESH_CONFIG refresh interval for mint (7200s) exceeds VOLATILITY_CACHE_EVICT_AFTER_SECS (3600s)."
+        );
+    }
+}
@@ -1,193 +1,2051 @@
-use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::hash_map::{DefaultHasher, RandomState};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
+#[cfg(feature = "persistence")]
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
-use tracing::{info, warn, error};
+use cron::Schedule;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn, error};
+#[cfg(feature = "persistence")]
+use crate::background::cache_store::CacheStore;
+use crate::background::cache_backend::{self, CacheBackend};
+use crate::background::health_monitor::HealthMonitor;
+use crate::background::price_cache::{FetchedSeries, PriceCache, SeriesKey};
+use crate::background::token_registry::TokenRegistry;
+use crate::background::volatility_history::{self, HistoryStore, VolatilityPoint};
 use crate::config::AppConfig;
-use crate::routes::historical_volatility::{BirdeyeHistoricalPriceResponse, calculate_volatility};
+use crate::math::rolling::trailing_volatility;
+use crate::math::{Candle, EstimatorKind, Interval, VolatilityEstimator};
+use crate::provider::{self, PriceProvider, ProviderKind, ProviderSeries};
+use crate::utils::address_type::{self, AddressType};
+use crate::utils::chain::{self, Chain};
+use crate::utils::timestamp_format;
+
+/// Time between the starts of two background refresh cycles
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Wait before respawning a panicked refresh task, doubled after each panic up to
+/// [`MAX_RESTART_BACKOFF`]
+const MIN_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest wait before respawning a panicked refresh task
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// How long a claim on a token's refresh outlives an instance that stopped mid-refresh
+const REFRESH_CLAIM_TTL: Duration = Duration::from_secs(30);
+
+/// Length of the rolling window cached values are computed over, unless the token's
+/// `TOKEN_REFRESH_CONFIG` entry leaves it out
+pub const CACHE_WINDOW_DAYS: i64 = 90;
+
+/// Trailing windows, in days, cached alongside the headline value for every tracked token.
+///
+/// All are computed from the same fetched series; the longest is the headline value itself.
+pub const CACHED_WINDOW_DAYS: [u32; 3] = [7, 30, CACHE_WINDOW_DAYS as u32];
+
+/// A token's cached volatility and where it came from
+#[derive(Debug, Clone, Copy)]
+struct CachedVolatility {
+    volatility: f64,
+    /// Volatility over each of [`CACHED_WINDOW_DAYS`]; `None` where the series is too short
+    windows: [Option<f64>; CACHED_WINDOW_DAYS.len()],
+    last_updated: DateTime<Utc>,
+    /// When `volatility` or `windows` last took a different value
+    changed_at: DateTime<Utc>,
+    /// Provider that served the series the volatility was computed from
+    provider: &'static str,
+    /// Candles in the series the volatility was computed from
+    data_points: usize,
+    /// Last time a request looked the token up, deciding which entry to evict when full
+    last_requested: DateTime<Utc>,
+    /// Request count decaying with time constant `HOT_TOKEN_WINDOW_SECS`, as of `last_requested`
+    demand: f64,
+}
+
+/// Volatility recomputed from a fresh provider fetch, as the cache would store it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecomputedVolatility {
+    pub volatility: f64,
+    /// Volatility over each of [`CACHED_WINDOW_DAYS`]; `None` where the series is too short
+    pub windows: [Option<f64>; CACHED_WINDOW_DAYS.len()],
+    /// Provider that served the fresh series
+    pub provider: &'static str,
+    /// Candles in the fresh series
+    pub data_points: usize,
+}
+
+/// Usage and refresh counters, kept since startup
+#[derive(Debug, Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    refreshes: AtomicU64,
+    refresh_errors: AtomicU64,
+    stale_evictions: AtomicU64,
+    capacity_evictions: AtomicU64,
+    idle_evictions: AtomicU64,
+    task_restarts: AtomicU64,
+    shared_hits: AtomicU64,
+    /// End time and duration of the last background refresh cycle
+    last_refresh: RwLock<Option<(DateTime<Utc>, Duration)>>,
+}
+
+/// Point-in-time statistics of the volatility cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    /// Tracked tokens with a cached value.
+    pub entries: usize,
+    /// Volatility requests served from the cache since startup.
+    pub hits: u64,
+    /// Volatility requests for untracked tokens since startup, each adding the token.
+    pub misses: u64,
+    /// `hits / (hits + misses)`; `0` before any request.
+    pub hit_rate: f64,
+    /// Mean age of the cached values in seconds; `None` when the cache is empty.
+    pub average_staleness_secs: Option<f64>,
+    /// Age of the oldest cached value in seconds; `None` when the cache is empty.
+    pub max_staleness_secs: Option<f64>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_refresh_at: Option<DateTime<Utc>>,
+    /// Duration of the last background refresh cycle, in milliseconds.
+    pub last_refresh_duration_ms: Option<u64>,
+    /// Token refreshes attempted by background cycles since startup.
+    pub refreshes: u64,
+    /// Background token refreshes that failed since startup.
+    pub refresh_errors: u64,
+    /// Cached values older than `VOLATILITY_CACHE_MAX_AGE_SECS`, no longer served.
+    pub stale_entries: usize,
+    /// Tokens evicted after failing to refresh for `VOLATILITY_CACHE_EVICT_AFTER_SECS`.
+    pub stale_evictions: u64,
+    /// Least recently requested tokens evicted to stay within `VOLATILITY_CACHE_MAX_TOKENS`.
+    pub capacity_evictions: u64,
+    /// Tokens requested often enough to be refreshed every cycle.
+    pub hot_entries: usize,
+    /// Tokens evicted after going unrequested for `TOKEN_RETENTION_SECS`.
+    pub idle_evictions: u64,
+    /// Times the background refresh task panicked and was respawned.
+    pub task_restarts: u64,
+    /// Token values taken from another instance through the cache backend instead of fetched.
+    pub shared_hits: u64,
+    /// Price series held by the price cache, expired ones included until pruned.
+    pub price_cache_entries: usize,
+    /// Price series dropped since startup, expired or beyond `PRICE_CACHE_MAX_ENTRIES`.
+    pub price_cache_pruned: u64,
+}
+
+/// The series a token's cached values were computed from, extended by each refresh
+#[derive(Debug, Clone)]
+struct StoredSeries {
+    candles: Vec<Candle>,
+    /// [`content_hash`] of `candles`
+    hash: u64,
+}
+
+/// A token's cached values and the series they were computed from, as saved by the
+/// persistence store and shared through the cache backend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSnapshot {
+    pub token_address: String,
+    pub volatility: f64,
+    /// Volatility over each of [`CACHED_WINDOW_DAYS`]; `None` where the series is too short
+    pub windows: [Option<f64>; CACHED_WINDOW_DAYS.len()],
+    pub last_updated: DateTime<Utc>,
+    pub changed_at: DateTime<Utc>,
+    pub provider: String,
+    pub data_points: usize,
+    pub last_requested: DateTime<Utc>,
+    pub demand: f64,
+    /// Whether the token is kept however long it goes unrequested
+    pub pinned: bool,
+    /// Stored series, empty when the token has none
+    pub candles: Vec<Candle>,
+}
+
+/// Stored series hash and pin of a token, as last saved to the persistence store
+#[cfg(feature = "persistence")]
+type SavedState = (Option<u64>, bool);
+
+/// Shared map of token address to its cached volatility
+type TokenCache = Arc<RwLock<HashMap<String, CachedVolatility>>>;
+
+/// A token fetch started by [`VolatilityCache::add_token`] or
+/// [`VolatilityCache::force_refresh_token`], awaited by every concurrent caller
+type InFlightFetch = Shared<BoxFuture<'static, Result<(), String>>>;
 
 /// Cache for storing volatility data for different tokens
 #[derive(Clone)]
 pub struct VolatilityCache {
-    /// Map of token address to (volatility, last_updated)
-    cache: Arc<RwLock<HashMap<String, (f64, DateTime<Utc>)>>>,
+    /// Map of token address to its cached volatility
+    cache: TokenCache,
     /// Configuration for API requests
     config: Arc<AppConfig>,
+    /// Records refresh cycle and upstream outcomes for the status endpoint
+    monitor: HealthMonitor,
+    /// Map of token address to the last series its values were computed from
+    series: Arc<RwLock<HashMap<String, StoredSeries>>>,
+    /// Source of the price series volatility is computed from
+    provider: Arc<dyn PriceProvider>,
+    /// Raw price series the refreshes read through, shared with the request handlers
+    price_cache: PriceCache,
+    /// Hit, miss and refresh counters reported by [`VolatilityCache::stats`]
+    counters: Arc<Counters>,
+    /// Map of token address to its fetch in progress, so concurrent misses share one
+    in_flight: Arc<Mutex<HashMap<(String, bool), InFlightFetch>>>,
+    /// Tokens kept however long they go unrequested, see [`VolatilityCache::pin_token`]
+    pinned: Arc<RwLock<HashSet<String>>>,
+    /// Per-token refresh intervals and windows from `TOKEN_REFRESH_CONFIG`
+    registry: Arc<TokenRegistry>,
+    /// Database changed tokens are saved to after every cycle, when `VOLATILITY_CACHE_DB_PATH`
+    /// is set
+    #[cfg(feature = "persistence")]
+    store: Option<Arc<CacheStore>>,
+    /// Map of token address to its state as last saved to `store`, so unchanged tokens
+    /// aren't written again
+    #[cfg(feature = "persistence")]
+    saved: Arc<Mutex<HashMap<String, SavedState>>>,
+    /// Values and refresh claims shared with other instances, see [`CacheBackend`]
+    backend: Arc<dyn CacheBackend>,
+    /// Every value computed by a refresh, see [`HistoryStore`]
+    history: Arc<dyn HistoryStore>,
+    /// Current time the refresh windows end at, fixed by tests
+    clock: fn() -> DateTime<Utc>,
 }
 
 impl VolatilityCache {
-    /// Create a new volatility cache
+    /// Create a new volatility cache backed by the configured providers
     pub fn new(config: AppConfig) -> Self {
+        let provider = provider::from_config(&config);
+        Self::with_provider(config, provider)
+    }
+
+    /// Create a new volatility cache that fetches prices from `provider`
+    pub fn with_provider(config: AppConfig, provider: Arc<dyn PriceProvider>) -> Self {
+        let price_cache = PriceCache::new(config.price_cache_ttl_secs, config.price_cache_max_entries);
+        let registry = Arc::new(TokenRegistry::from_config(&config));
+        #[cfg(feature = "persistence")]
+        let store = open_store(&config);
+        let backend = cache_backend::from_config(&config);
+        let history = volatility_history::from_config(&config);
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
             config: Arc::new(config),
+            monitor: HealthMonitor::new(),
+            series: Arc::new(RwLock::new(HashMap::new())),
+            provider,
+            price_cache,
+            counters: Arc::new(Counters::default()),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            pinned: Arc::new(RwLock::new(HashSet::new())),
+            registry,
+            #[cfg(feature = "persistence")]
+            store,
+            #[cfg(feature = "persistence")]
+            saved: Arc::new(Mutex::new(HashMap::new())),
+            backend,
+            history,
+            clock: Utc::now,
         }
     }
 
+    /// Share values and refresh work through `backend` instead of the configured one
+    pub fn with_backend(mut self, backend: Arc<dyn CacheBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Record computed values to `history` instead of the configured store
+    pub fn with_history(mut self, history: Arc<dyn HistoryStore>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// History of the values computed by refreshes, shared with the request handlers
+    pub fn history(&self) -> &Arc<dyn HistoryStore> {
+        &self.history
+    }
+
+    /// Health monitor shared with the request handlers
+    pub fn monitor(&self) -> &HealthMonitor {
+        &self.monitor
+    }
+
+    /// Price provider shared with the request handlers
+    pub fn provider(&self) -> &Arc<dyn PriceProvider> {
+        &self.provider
+    }
+
+    /// Price series cache shared with the request handlers
+    pub fn price_cache(&self) -> &PriceCache {
+        &self.price_cache
+    }
+
+    /// Whether cached values can answer a query for this estimator and interval on the
+    /// current request's chain.
+    ///
+    /// The cache maintains daily-candle values of Solana tokens (not pairs) for the configured
+    /// estimator only.
+    pub fn covers(&self, estimator: EstimatorKind, interval: Interval) -> bool {
+        estimator == self.config.volatility_estimator
+            && interval == Interval::OneDay
+            && chain::current() == Chain::Solana
+            && address_type::current() == AddressType::Token
+    }
+
+    /// Length in days of the window a token's headline value is computed over
+    pub fn window_days(&self, token_address: &str) -> u32 {
+        self.registry.headline_window_days(token_address)
+    }
+
+    /// Whether a value refreshed at `last_updated` is recent enough to serve
+    fn is_fresh(&self, last_updated: DateTime<Utc>) -> bool {
+        Utc::now() - last_updated <= ChronoDuration::seconds(self.config.volatility_cache_max_age_secs as i64)
+    }
+
+    /// An entry's decaying request count as of `now`
+    fn demand_at(&self, entry: &CachedVolatility, now: DateTime<Utc>) -> f64 {
+        let elapsed = (now - entry.last_requested).num_milliseconds().max(0) as f64 / 1000.0;
+        entry.demand * (-elapsed / self.config.hot_token_window_secs as f64).exp()
+    }
+
+    /// Whether an entry is requested often enough to be refreshed every cycle
+    fn is_hot(&self, entry: &CachedVolatility, now: DateTime<Utc>) -> bool {
+        self.demand_at(entry, now) >= self.config.hot_token_min_requests
+    }
+
+    /// Whether a tracked token is requested often enough to be refreshed every cycle
+    pub async fn is_hot_token(&self, token_address: &str) -> bool {
+        let cache = self.cache.read().await;
+        cache
+            .get(token_address)
+            .is_some_and(|entry| self.is_hot(entry, Utc::now()))
+    }
+
     /// Get the current volatility for a token
+    ///
+    /// `None` when the cached value is older than `VOLATILITY_CACHE_MAX_AGE_SECS`, so callers
+    /// recompute it rather than serve a value the background task failed to refresh.
     pub async fn get_volatility(&self, token_address: &str) -> Option<f64> {
         let cache = self.cache.read().await;
-        cache.get(token_address).map(|(volatility, _)| *volatility)
+        cache
+            .get(token_address)
+            .filter(|entry| self.is_fresh(entry.last_updated))
+            .map(|entry| entry.volatility)
+    }
+
+    /// Get a token's cached volatility whatever its age, for diagnostics
+    pub async fn peek_volatility(&self, token_address: &str) -> Option<f64> {
+        let cache = self.cache.read().await;
+        cache.get(token_address).map(|entry| entry.volatility)
     }
 
-    /// Start the background task that updates volatility data every 60 seconds
-    pub async fn start_background_task(&self) {
-        let cache = Arc::clone(&self.cache);
-        let config = Arc::clone(&self.config);
+    /// Get the current volatility for a token to serve a request, counting the lookup as a
+    /// cache hit or miss in [`VolatilityCache::stats`]
+    ///
+    /// Also marks the token as requested, keeping it from being evicted when the cache is full.
+    pub async fn lookup(&self, token_address: &str) -> Option<f64> {
+        let volatility = {
+            let mut cache = self.cache.write().await;
+            cache.get_mut(token_address).and_then(|entry| {
+                let now = Utc::now();
+                entry.demand = self.demand_at(entry, now) + 1.0;
+                entry.last_requested = now;
+                self.is_fresh(entry.last_updated).then_some(entry.volatility)
+            })
+        };
+        let counter = if volatility.is_some() {
+            &self.counters.hits
+        } else {
+            &self.counters.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        volatility
+    }
+
+    /// Get a token's cached volatility over a trailing window of `days`
+    ///
+    /// `None` when the token is not cached or its values are stale, `days` is not one of
+    /// [`CACHED_WINDOW_DAYS`], or the cached series was too short to cover the window.
+    pub async fn get_window_volatility(&self, token_address: &str, days: u32) -> Option<f64> {
+        let index = CACHED_WINDOW_DAYS.iter().position(|&window| window == days)?;
+        let cache = self.cache.read().await;
+        cache
+            .get(token_address)
+            .filter(|entry| self.is_fresh(entry.last_updated))
+            .and_then(|entry| entry.windows[index])
+    }
+
+    /// Get the time a token's cached volatility was last updated
+    pub async fn last_updated(&self, token_address: &str) -> Option<DateTime<Utc>> {
+        let cache = self.cache.read().await;
+        cache.get(token_address).map(|entry| entry.last_updated)
+    }
+
+    /// Get the number of candles a token's cached volatility was computed from
+    pub async fn data_points(&self, token_address: &str) -> Option<usize> {
+        let cache = self.cache.read().await;
+        cache.get(token_address).map(|entry| entry.data_points)
+    }
+
+    /// Get the time a token's cached values last changed, as opposed to merely being refreshed
+    pub async fn changed_at(&self, token_address: &str) -> Option<DateTime<Utc>> {
+        let cache = self.cache.read().await;
+        cache.get(token_address).map(|entry| entry.changed_at)
+    }
+
+    /// Addresses of tracked tokens whose cached values changed after `since`, sorted
+    pub async fn changed_since(&self, since: DateTime<Utc>) -> Vec<String> {
+        let cache = self.cache.read().await;
+        let mut tokens: Vec<String> = cache
+            .iter()
+            .filter(|(_, entry)| entry.changed_at > since)
+            .map(|(token_address, _)| token_address.clone())
+            .collect();
+        tokens.sort();
+        tokens
+    }
+
+    /// Get the provider a token's cached volatility was computed from
+    pub async fn provider_of(&self, token_address: &str) -> Option<&'static str> {
+        let cache = self.cache.read().await;
+        cache.get(token_address).map(|entry| entry.provider)
+    }
+
+    /// Addresses of every token the background task keeps refreshed, sorted
+    pub async fn tracked_tokens(&self) -> Vec<String> {
+        let cache = self.cache.read().await;
+        let mut tokens: Vec<String> = cache.keys().cloned().collect();
+        tokens.sort();
+        tokens
+    }
+
+    /// Entry count, hit and miss counts, staleness and background refresh statistics
+    pub async fn stats(&self) -> CacheStats {
+        let now = Utc::now();
+        let (ages, hot_entries): (Vec<f64>, usize) = {
+            let cache = self.cache.read().await;
+            let ages = cache
+                .values()
+                .map(|entry| (now - entry.last_updated).num_milliseconds() as f64 / 1000.0)
+                .collect();
+            (ages, cache.values().filter(|entry| self.is_hot(entry, now)).count())
+        };
+        let hits = self.counters.hits.load(Ordering::Relaxed);
+        let misses = self.counters.misses.load(Ordering::Relaxed);
+        let last_refresh = *self.counters.last_refresh.read().await;
+
+        CacheStats {
+            entries: ages.len(),
+            hits,
+            misses,
+            hit_rate: if hits + misses == 0 {
+                0.0
+            } else {
+                hits as f64 / (hits + misses) as f64
+            },
+            average_staleness_secs: (!ages.is_empty())
+                .then(|| ages.iter().sum::<f64>() / ages.len() as f64),
+            max_staleness_secs: ages.iter().copied().reduce(f64::max),
+            last_refresh_at: last_refresh.map(|(at, _)| at),
+            last_refresh_duration_ms: last_refresh.map(|(_, duration)| duration.as_millis() as u64),
+            refreshes: self.counters.refreshes.load(Ordering::Relaxed),
+            refresh_errors: self.counters.refresh_errors.load(Ordering::Relaxed),
+            stale_entries: ages
+                .iter()
+                .filter(|&&age| age > self.config.volatility_cache_max_age_secs as f64)
+                .count(),
+            stale_evictions: self.counters.stale_evictions.load(Ordering::Relaxed),
+            capacity_evictions: self.counters.capacity_evictions.load(Ordering::Relaxed),
+            hot_entries,
+            idle_evictions: self.counters.idle_evictions.load(Ordering::Relaxed),
+            task_restarts: self.counters.task_restarts.load(Ordering::Relaxed),
+            shared_hits: self.counters.shared_hits.load(Ordering::Relaxed),
+            price_cache_entries: self.price_cache.entry_count().await,
+            price_cache_pruned: self.price_cache.pruned(),
+        }
+    }
+
+    /// Start the background task that updates volatility data every 60 seconds, until
+    /// `shutdown` is cancelled
+    ///
+    /// With `CACHE_REFRESH_CRON` set, cycles run on its schedule instead. The task is
+    /// supervised, see [`VolatilityCache::supervise`]. The returned handle completes once
+    /// the task has stopped after `shutdown`.
+    pub async fn start_background_task(&self, shutdown: CancellationToken) -> JoinHandle<()> {
+        let this = self.clone();
+        let schedule = self
+            .config
+            .cache_refresh_cron
+            .as_deref()
+            .and_then(|expression| Schedule::from_str(expression).ok());
         
-        tokio::spawn(async move {
-            // Run update immediately once
-            Self::update_all_tokens(&cache, &config).await;
-            
-            // Then start the loop that runs every 60 seconds
-            loop {
-                // Sleep for 60 seconds
-                tokio::time::sleep(Duration::from_secs(60)).await;
+        tokio::spawn(async move { this.supervise(schedule, shutdown).await })
+    }
+
+    /// Run the refresh task, respawning it with exponential backoff whenever it panics, so
+    /// a bug in one refresh can't leave the cache silently going stale
+    ///
+    /// Each panic is logged, counted in [`CacheStats::task_restarts`] and reported as a
+    /// background incident on the status endpoint. Cancelling `shutdown` aborts the task,
+    /// dropping the cycle in progress: a refresh cut short leaves its token's values as
+    /// they were.
+    async fn supervise(&self, schedule: Option<Schedule>, shutdown: CancellationToken) {
+        let mut backoff = MIN_RESTART_BACKOFF;
+        loop {
+            let this = self.clone();
+            let schedule = schedule.clone();
+            let started = Instant::now();
+            let mut task = tokio::spawn(async move {
+                // Run update immediately once
+                this.update_all_tokens().await;
                 
-                // Update all cached tokens
-                Self::update_all_tokens(&cache, &config).await;
+                match schedule {
+                    Some(schedule) => this.run_scheduled_cycles(&schedule).await,
+                    None => this.run_fixed_cycles().await,
+                }
+            });
+            let result = tokio::select! {
+                result = &mut task => result,
+                _ = shutdown.cancelled() => {
+                    task.abort();
+                    let _ = task.await;
+                    info!("Background refresh task stopped");
+                    return;
+                }
+            };
+
+            // Besides panicking, the task only ends when it has nothing left to schedule
+            let Err(e) = result else { return };
+            if !e.is_panic() {
+                return;
+            }
+            let message = panic_message(e.into_panic());
+            // A task that ran fine for a while restarts without the accumulated backoff
+            if started.elapsed() > MAX_RESTART_BACKOFF {
+                backoff = MIN_RESTART_BACKOFF;
+            }
+            self.counters.task_restarts.fetch_add(1, Ordering::Relaxed);
+            error!(error = %message, backoff_secs = %backoff.as_secs(), "Background refresh task panicked, restarting");
+            self.monitor
+                .record_background_incident(format!("Refresh task panicked and was restarted: {}", message))
+                .await;
+
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = shutdown.cancelled() => return,
             }
-        });
+            backoff = (backoff * 2).min(MAX_RESTART_BACKOFF);
+        }
     }
 
-    /// Update volatility data for all tokens in the cache
-    async fn update_all_tokens(
-        cache: &Arc<RwLock<HashMap<String, (f64, DateTime<Utc>)>>>,
-        config: &Arc<AppConfig>,
-    ) {
+    /// Start a cycle every 60 seconds, spreading its refreshes over the cycle
+    async fn run_fixed_cycles(&self) {
+        let spread = REFRESH_INTERVAL.mul_f64(self.config.refresh_spread_ratio);
+        let mut cycles = tokio::time::interval_at(tokio::time::Instant::now() + REFRESH_INTERVAL, REFRESH_INTERVAL);
+        cycles.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            cycles.tick().await;
+            
+            // Update the hot tokens, and the cold ones due for a refresh
+            let due = self.due_tokens().await;
+            self.refresh_tokens(due, spread).await;
+        }
+    }
+
+    /// Start a cycle at every time of `schedule`, spreading its refreshes over the time until
+    /// the next one
+    ///
+    /// Times missed while a cycle overran are skipped rather than caught up on.
+    async fn run_scheduled_cycles(&self, schedule: &Schedule) {
+        let mut last_start = Utc::now();
+        loop {
+            let Some((start, until_next)) = next_cycle(schedule, last_start.max(Utc::now())) else {
+                warn!("CACHE_REFRESH_CRON has no upcoming times, stopping background refreshes");
+                return;
+            };
+            tokio::time::sleep((start - Utc::now()).to_std().unwrap_or_default()).await;
+            last_start = start;
+
+            let spread = until_next.to_std().unwrap_or_default().mul_f64(self.config.refresh_spread_ratio);
+            let due = self.due_tokens().await;
+            self.refresh_tokens(due, spread).await;
+        }
+    }
+
+    /// Update volatility data for all tokens in the cache at once
+    async fn update_all_tokens(&self) {
         let token_addresses: Vec<String> = {
-            let cache = cache.read().await;
+            let cache = self.cache.read().await;
             cache.keys().cloned().collect()
         };
+        self.refresh_tokens(token_addresses, Duration::ZERO).await;
+    }
+
+    /// Tokens to refresh this cycle: those whose configured refresh interval has elapsed,
+    /// and among the others, hot ones and cold ones last refreshed at least
+    /// `COLD_REFRESH_INTERVAL_SECS` ago
+    async fn due_tokens(&self) -> Vec<String> {
+        let now = Utc::now();
+        let cold_interval = ChronoDuration::seconds(self.config.cold_refresh_interval_secs as i64);
+        let cache = self.cache.read().await;
+        cache
+            .iter()
+            .filter(|(token_address, entry)| {
+                let age = now - entry.last_updated;
+                match self.registry.refresh_interval_secs(token_address) {
+                    Some(interval) => age >= ChronoDuration::seconds(interval as i64),
+                    None => self.is_hot(entry, now) || age >= cold_interval,
+                }
+            })
+            .map(|(token_address, _)| token_address.clone())
+            .collect()
+    }
+
+    /// Update volatility data for `token_addresses`, spread over `spread`
+    ///
+    /// Each token gets an equal slot of `spread` and starts its refresh at a random point
+    /// within it, so provider calls neither burst at the start of the cycle nor line up
+    /// from one cycle to the next. Up to `REFRESH_CONCURRENCY` refreshes run at once.
+    async fn refresh_tokens(&self, token_addresses: Vec<String>, spread: Duration) {
+        let started = Instant::now();
+
+        let total_tokens = token_addresses.len();
+        let slot = spread.div_f64(total_tokens.max(1) as f64);
+        let failed_tokens = stream::iter(token_addresses.into_iter().enumerate())
+            .map(|(index, token_address)| {
+                // Owned, so the spawned background task's future stays `Send`
+                let this = self.clone();
+                async move {
+                    let offset = slot.mul_f64(index as f64 + jitter(&token_address));
+                    tokio::time::sleep_until((started + offset).into()).await;
+                    let result = this.update_token(&token_address, false, false).await;
+                    if let Err(e) = &result {
+                        error!(token_address = %token_address, error = %e, "Failed to update token volatility");
+                    }
+                    result.is_err()
+                }
+            })
+            .buffer_unordered(self.config.refresh_concurrency)
+            .filter(|failed| std::future::ready(*failed))
+            .count()
+            .await;
+
+        self.monitor.record_cycle(failed_tokens, total_tokens).await;
+        self.counters
+            .refreshes
+            .fetch_add(total_tokens as u64, Ordering::Relaxed);
+        self.counters
+            .refresh_errors
+            .fetch_add(failed_tokens as u64, Ordering::Relaxed);
+        *self.counters.last_refresh.write().await = Some((Utc::now(), started.elapsed()));
+
+        self.evict_failing_tokens().await;
+        self.evict_idle_tokens().await;
+        self.prune_price_cache().await;
+        self.persist_changed().await;
+    }
+
+    /// Drop price series older than `PRICE_CACHE_TTL_SECS`, which would otherwise stay in
+    /// memory until the next insert
+    async fn prune_price_cache(&self) {
+        let pruned = self.price_cache.prune_expired().await;
+        if pruned > 0 {
+            debug!(pruned, "Pruned expired price series");
+        }
+    }
+
+    /// Evict tokens no request has looked up for `TOKEN_RETENTION_SECS`, except pinned ones
+    async fn evict_idle_tokens(&self) {
+        let retention = ChronoDuration::seconds(self.config.token_retention_secs as i64);
+        let now = Utc::now();
+        let evicted: Vec<String> = {
+            let mut cache = self.cache.write().await;
+            let pinned = self.pinned.read().await;
+            let evicted: Vec<String> = cache
+                .iter()
+                .filter(|(token_address, entry)| {
+                    now - entry.last_requested > retention && !pinned.contains(*token_address)
+                })
+                .map(|(token_address, _)| token_address.clone())
+                .collect();
+            for token_address in &evicted {
+                cache.remove(token_address);
+            }
+            evicted
+        };
+        if evicted.is_empty() {
+            return;
+        }
 
-        for token_address in token_addresses {
-            if let Err(e) = Self::update_token(cache, config, &token_address).await {
-                error!(token_address = %token_address, error = %e, "Failed to update token volatility");
+        let mut series = self.series.write().await;
+        for token_address in &evicted {
+            series.remove(token_address);
+            info!(token_address = %token_address, "Evicted token no longer requested");
+        }
+        self.counters
+            .idle_evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
+    }
+
+    /// Evict tokens whose refreshes have been failing for longer than
+    /// `VOLATILITY_CACHE_EVICT_AFTER_SECS`, so the loop stops spending provider calls on them
+    async fn evict_failing_tokens(&self) {
+        let evict_after = ChronoDuration::seconds(self.config.volatility_cache_evict_after_secs as i64);
+        let now = Utc::now();
+        let evicted: Vec<String> = {
+            let mut cache = self.cache.write().await;
+            let evicted: Vec<String> = cache
+                .iter()
+                .filter(|(_, entry)| now - entry.last_updated > evict_after)
+                .map(|(token_address, _)| token_address.clone())
+                .collect();
+            for token_address in &evicted {
+                cache.remove(token_address);
             }
+            evicted
+        };
+        if evicted.is_empty() {
+            return;
         }
+
+        let mut series = self.series.write().await;
+        for token_address in &evicted {
+            series.remove(token_address);
+            warn!(token_address = %token_address, "Evicted token whose refreshes kept failing");
+        }
+        self.counters
+            .stale_evictions
+            .fetch_add(evicted.len() as u64, Ordering::Relaxed);
     }
 
     /// Update volatility data for a specific token
+    ///
+    /// Values another instance shared since this one last refreshed the token are taken
+    /// as they are. Otherwise the refresh is claimed on the cache backend and the token
+    /// refetched, unless another instance holds the claim: background refreshes then leave
+    /// the token to it, while new tokens (`track` set) are fetched anyway. With `force` set,
+    /// shared values are ignored and the token recomputed as by
+    /// [`VolatilityCache::force_refresh_token`].
     async fn update_token(
-        cache: &Arc<RwLock<HashMap<String, (f64, DateTime<Utc>)>>>,
-        config: &Arc<AppConfig>,
+        &self,
         token_address: &str,
+        track: bool,
+        force: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Calculate date range for 90-day rolling window
-        let to_date = Utc::now();
-        let from_date = to_date - ChronoDuration::days(90);
+        if !force && self.adopt_shared(token_address, track).await {
+            return Ok(());
+        }
+        let claim = self.claim_refresh(token_address).await;
+        if claim.is_none() && !track {
+            debug!(token_address = %token_address, "Token being refreshed by another instance, skipping");
+            return Ok(());
+        }
+
+        // Stringified, so the spawned background task's future stays `Send`
+        let result = self
+            .refresh_token(token_address, track, force)
+            .await
+            .map_err(|e| e.to_string());
+        if let Some(claim) = claim {
+            claim.release().await;
+        }
+        if result.is_ok() {
+            self.share(token_address).await;
+        }
+        result.map_err(Into::into)
+    }
 
-        // Fetch historical price data
-        let response = Self::fetch_historical_prices(config, from_date, to_date, token_address).await?;
+    /// Claim a token's refresh on the cache backend, `None` when another instance holds it
+    ///
+    /// A backend that can't be reached counts as claimed, so an outage doesn't stop refreshes.
+    async fn claim_refresh(&self, token_address: &str) -> Option<RefreshClaim> {
+        let claimed = self
+            .backend
+            .claim_refresh(token_address, REFRESH_CLAIM_TTL)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(backend = self.backend.name(), error = %e, "Failed to claim token refresh");
+                true
+            });
+        claimed.then(|| RefreshClaim {
+            backend: self.backend.clone(),
+            token_address: Some(token_address.to_string()),
+        })
+    }
+
+    /// Take a token's values from the cache backend when another instance computed them
+    /// within the last cycle, after this one last refreshed the token; a token new to this
+    /// instance (`track` set) takes any values still fresh enough to serve
+    ///
+    /// Returns whether the shared values were taken.
+    async fn adopt_shared(&self, token_address: &str, track: bool) -> bool {
+        let shared = match self.backend.get(token_address).await {
+            Ok(Some(shared)) => shared,
+            Ok(None) => return false,
+            Err(e) => {
+                warn!(backend = self.backend.name(), error = %e, "Failed to read shared token values");
+                return false;
+            }
+        };
+
+        let now = Utc::now();
+        let mut cache = self.cache.write().await;
+        let usable = match cache.get(token_address) {
+            Some(local) => {
+                shared.last_updated > local.last_updated
+                    && now - shared.last_updated < ChronoDuration::seconds(REFRESH_INTERVAL.as_secs() as i64)
+            }
+            None => track && self.is_fresh(shared.last_updated),
+        };
+        if !usable {
+            return false;
+        }
+        let (last_requested, demand) = cache
+            .get(token_address)
+            .map_or((now, 1.0), |local| (local.last_requested, local.demand));
+        let room = self.make_room(&mut cache, &*self.pinned.read().await, token_address);
+        if matches!(room, Room::Full) {
+            return false;
+        }
+        cache.insert(token_address.to_string(), self.cached_from(&shared, last_requested, demand));
+        drop(cache);
+        let mut series = self.series.write().await;
+        if !shared.candles.is_empty() {
+            series.insert(
+                token_address.to_string(),
+                StoredSeries {
+                    hash: content_hash(&shared.candles),
+                    candles: shared.candles,
+                },
+            );
+        }
+        self.drop_evicted(&mut series, room);
+        self.counters.shared_hits.fetch_add(1, Ordering::Relaxed);
+        debug!(token_address = %token_address, "Took token values shared by another instance");
+        true
+    }
+
+    /// Share a token's cached values through the cache backend, for as long as they could
+    /// be kept without a successful refresh
+    async fn share(&self, token_address: &str) {
+        let token = {
+            let cache = self.cache.read().await;
+            let Some(entry) = cache.get(token_address) else { return };
+            let series = self.series.read().await;
+            let pinned = self.pinned.read().await.contains(token_address);
+            snapshot_of(token_address, entry, series.get(token_address), pinned)
+        };
+        let ttl = Duration::from_secs(self.config.volatility_cache_evict_after_secs);
+        if let Err(e) = self.backend.put(&token, ttl).await {
+            warn!(backend = self.backend.name(), error = %e, "Failed to share token values");
+        }
+    }
+
+    /// Record a token's freshly computed window values to the history store
+    async fn record_history(
+        &self,
+        token_address: &str,
+        windows: &[Option<f64>; CACHED_WINDOW_DAYS.len()],
+        computed_at: DateTime<Utc>,
+    ) {
+        let points: Vec<VolatilityPoint> = CACHED_WINDOW_DAYS
+            .into_iter()
+            .zip(windows)
+            .filter_map(|(window_days, volatility)| {
+                Some(VolatilityPoint {
+                    token_address: token_address.to_string(),
+                    window_days,
+                    volatility: (*volatility)?,
+                    computed_at,
+                })
+            })
+            .collect();
+        if let Err(e) = self.history.record(&points).await {
+            warn!(store = self.history.name(), error = %e, "Failed to record volatility history");
+        }
+    }
+
+    /// Evict the least recently requested unpinned token when a new one would overflow the
+    /// cache, returning the evicted token for [`VolatilityCache::drop_evicted`]
+    fn make_room(
+        &self,
+        cache: &mut HashMap<String, CachedVolatility>,
+        pinned: &HashSet<String>,
+        token_address: &str,
+    ) -> Room {
+        if cache.contains_key(token_address) || cache.len() < self.config.volatility_cache_max_tokens {
+            return Room::Available;
+        }
+        let least_recent = cache
+            .iter()
+            .filter(|(address, _)| !pinned.contains(*address))
+            .min_by_key(|(_, entry)| entry.last_requested)
+            .map(|(address, _)| address.clone());
+        match least_recent {
+            Some(address) => {
+                cache.remove(&address);
+                Room::Evicted(address)
+            }
+            None => Room::Full,
+        }
+    }
+
+    /// Drop the series of a token evicted by [`VolatilityCache::make_room`]
+    fn drop_evicted(&self, series: &mut HashMap<String, StoredSeries>, room: Room) {
+        if let Room::Evicted(evicted) = room {
+            series.remove(&evicted);
+            self.counters.capacity_evictions.fetch_add(1, Ordering::Relaxed);
+            info!(token_address = %evicted, "Evicted least recently requested token to make room");
+        }
+    }
+
+    /// Fetch a token's series and recompute its values
+    ///
+    /// With `track` unset, only tokens still in the cache are updated, so a refresh racing
+    /// with [`VolatilityCache::remove_token`] doesn't bring the token back. With `force` set,
+    /// the whole window is refetched and the values recomputed even when the series looks
+    /// unchanged.
+    async fn refresh_token(
+        &self,
+        token_address: &str,
+        track: bool,
+        force: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = &self.config;
+        // Calculate date range for the token's rolling window
+        let window_days = self.window_days(token_address);
+        let to_date = (self.clock)();
+        let from_date = to_date - ChronoDuration::days(window_days as i64);
+
+        // Fetch the series required by the configured estimator
+        let estimator = config.volatility_estimator.estimator();
+        let key = SeriesKey {
+            chain: Chain::Solana,
+            address_type: AddressType::Token,
+            token_address: token_address.to_string(),
+            interval: Interval::OneDay,
+            ohlc: estimator.requires_ohlc(),
+        };
+        let (candles, provider) = self.fetch_window(&key, from_date, to_date, force).await?;
+
+        // Daily candles rarely change between one-minute cycles: if the provider returned
+        // the same series as last time, the cached value is still current.
+        let series_hash = content_hash(&candles);
+        if !force && self.series.read().await.get(token_address).map(|stored| stored.hash) == Some(series_hash) {
+            let mut cache = self.cache.write().await;
+            if let Some(entry) = cache.get_mut(token_address) {
+                entry.last_updated = Utc::now();
+                entry.provider = provider;
+                debug!(token_address = %token_address, "Price series unchanged, skipping recomputation");
+                return Ok(());
+            }
+        }
+
+        let items_len = candles.len();
         
-        // Process the response
-        if let Some(data) = response.data {
-            let items_len = data.items.len();
-            
-            // Calculate percent change for reference if we have enough data points
-            let percent_change = if items_len >= 2 {
-                let first = data.items.first().unwrap().value;
-                let last = data.items.last().unwrap().value;
-                ((last - first) / first) * 100.0
-            } else {
-                0.0
+        // Calculate percent change for reference if we have enough data points
+        let percent_change = if items_len >= 2 {
+            let first = candles.first().unwrap().close;
+            let last = candles.last().unwrap().close;
+            ((last - first) / first) * 100.0
+        } else {
+            0.0
+        };
+        
+        // Calculate volatility
+        let periods_per_year = Interval::OneDay.periods_per_year(config.annualization_days as f64);
+        let volatility_result = estimator.annualized_volatility(&candles, periods_per_year);
+        
+        if let Some(volatility) = volatility_result {
+            let windows = self.window_volatilities(token_address, &candles, volatility, estimator.as_ref(), periods_per_year);
+
+            // Update the cache, keeping the change time when the values came out the same
+            let now = Utc::now();
+            let mut cache = self.cache.write().await;
+            if !track && !cache.contains_key(token_address) {
+                debug!(token_address = %token_address, "Token removed during refresh, dropping its values");
+                return Ok(());
+            }
+            let changed_at = match cache.get(token_address) {
+                Some(previous) if previous.volatility == volatility && previous.windows == windows => {
+                    previous.changed_at
+                }
+                _ => now,
             };
+            // A new token counts the request that added it
+            let (last_requested, demand) = cache
+                .get(token_address)
+                .map_or((now, 1.0), |previous| (previous.last_requested, previous.demand));
+            let room = self.make_room(&mut cache, &*self.pinned.read().await, token_address);
+            if matches!(room, Room::Full) {
+                warn!(token_address = %token_address, "Cache is full of pinned tokens, not caching");
+                return Err("the volatility cache is full of pinned tokens".into());
+            }
+            cache.insert(
+                token_address.to_string(),
+                CachedVolatility {
+                    volatility,
+                    windows,
+                    last_updated: now,
+                    changed_at,
+                    provider,
+                    data_points: items_len,
+                    last_requested,
+                    demand,
+                },
+            );
+            drop(cache);
+            let mut series = self.series.write().await;
+            series.insert(
+                token_address.to_string(),
+                StoredSeries {
+                    candles,
+                    hash: series_hash,
+                },
+            );
+            self.drop_evicted(&mut series, room);
+            drop(series);
+            self.record_history(token_address, &windows, now).await;
             
-            // Calculate volatility
-            let volatility_result = calculate_volatility(data.items);
+            // Print detailed update with timestamp, token, and volatility value
+            println!("\n[{}] {}-DAY VOLATILITY UPDATE:", Utc::now().format("%Y-%m-%d %H:%M:%S"), window_days);
+            println!("Token: {}", token_address);
+            println!("Period: {} to {}", 
+                     from_date.format("%Y-%m-%d"), 
+                     to_date.format("%Y-%m-%d"));
+            println!("Data points: {}", items_len);
+            println!("Volatility: {:.6}", volatility);
+            println!("{}-day Change: {:.2}%", window_days, percent_change);
+            println!("-----------------------------------");
             
-            if let Some(volatility) = volatility_result {
-                // Update the cache
-                let mut cache = cache.write().await;
-                cache.insert(token_address.to_string(), (volatility, Utc::now()));
-                
-                // Print detailed update with timestamp, token, and volatility value
-                println!("\n[{}] 90-DAY VOLATILITY UPDATE:", Utc::now().format("%Y-%m-%d %H:%M:%S"));
-                println!("Token: {}", token_address);
-                println!("Period: {} to {}", 
-                         from_date.format("%Y-%m-%d"), 
-                         to_date.format("%Y-%m-%d"));
-                println!("Data points: {}", items_len);
-                println!("Volatility: {:.6}", volatility);
-                println!("90-day Change: {:.2}%", percent_change);
-                println!("-----------------------------------");
-                
-                info!(
-                    token_address = %token_address,
-                    volatility = %volatility,
-                    from_date = %from_date.format("%Y-%m-%d"),
-                    to_date = %to_date.format("%Y-%m-%d"),
-                    data_points = %items_len,
-                    "Updated 90-day token volatility"
-                );
-            } else {
-                warn!(
-                    token_address = %token_address,
-                    "Not enough price data to calculate volatility"
-                );
-            }
+            info!(
+                token_address = %token_address,
+                volatility = %volatility,
+                from_date = %from_date.format("%Y-%m-%d"),
+                to_date = %to_date.format("%Y-%m-%d"),
+                data_points = %items_len,
+                window_days = %window_days,
+                "Updated rolling token volatility"
+            );
         } else {
             warn!(
                 token_address = %token_address,
-                "No price data available"
+                "Not enough price data to calculate volatility"
             );
         }
 
         Ok(())
     }
 
-    /// Fetch historical price data from Birdeye API
-    async fn fetch_historical_prices(
-        config: &Arc<AppConfig>,
+    /// Fetch a token's series over `[from_date, to_date]` and the provider that served it
+    ///
+    /// With a stored series reaching into the window, only candles from its last one on are
+    /// fetched and appended, and candles before `from_date` dropped. The last candle is
+    /// fetched again rather than skipped, as it is still forming. Otherwise the whole window
+    /// is read through the price cache, reusing what requests over the token fetched.
+    ///
+    /// With `force` set, neither the stored series nor the price cache is used, and the
+    /// whole window comes from the provider.
+    async fn fetch_window(
+        &self,
+        key: &SeriesKey,
         from_date: DateTime<Utc>,
         to_date: DateTime<Utc>,
-        token_address: &str,
-    ) -> Result<BirdeyeHistoricalPriceResponse, reqwest::Error> {
-        // Convert DateTime objects to Unix timestamps for the API request
-        let from_timestamp = from_date.timestamp();
-        let to_timestamp = to_date.timestamp();
-
-        // Construct the query string with required parameters
-        let query = format!(
-            "address={}&address_type=token&type=1D&time_from={}&time_to={}",
-            token_address, from_timestamp, to_timestamp
-        );
-        let request_url = format!("{}?{}", config.birdeye_base_url, query);
-
-        // Set up HTTP client with required headers
-        let client = reqwest::Client::new();
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            reqwest::header::ACCEPT,
-            reqwest::header::HeaderValue::from_static("application/json"),
-        );
-        headers.insert(
-            "X-API-KEY",
-            reqwest::header::HeaderValue::from_str(&config.birdeye_api_key)
-                .expect("Invalid API key format"),
-        );
-        headers.insert("x-chain", reqwest::header::HeaderValue::from_static("solana"));
-
-        // Make the HTTP request and parse the JSON response
-        let response = client
-            .get(request_url)
-            .headers(headers)
-            .send()
-            .await?
-            .json::<BirdeyeHistoricalPriceResponse>()
-            .await?;
-
-        Ok(response)
+        force: bool,
+    ) -> Result<(Vec<Candle>, &'static str), String> {
+        let window_start = from_date.timestamp();
+        if force {
+            self.price_cache.invalidate(key).await;
+        }
+        let stored = if force {
+            None
+        } else {
+            let series = self.series.read().await;
+            series.get(&key.token_address).and_then(|stored| {
+                let last = stored.candles.last()?.unix_time;
+                let kept: Vec<Candle> = stored
+                    .candles
+                    .iter()
+                    .filter(|candle| (window_start..last).contains(&candle.unix_time))
+                    .copied()
+                    .collect();
+                (last >= window_start).then_some((last, kept))
+            })
+        };
+
+        if let Some((last, mut candles)) = stored {
+            let tail_from = DateTime::from_timestamp(last, 0).unwrap_or(from_date);
+            let result = self
+                .provider
+                .fetch_history(&key.token_address, key.interval, tail_from, to_date, key.ohlc)
+                .await;
+            self.monitor
+                .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+                .await;
+            let ProviderSeries { provider, candles: tail } = result.map_err(|e| e.to_string())?;
+            debug!(token_address = %key.token_address, candles = %tail.len(), "Fetched series tail");
+
+            candles.extend(tail.into_iter().filter(|candle| candle.unix_time >= window_start));
+            candles.sort_by_key(|candle| candle.unix_time);
+            candles.dedup_by_key(|candle| candle.unix_time);
+            // Keep the price cache warm for requests over the same token
+            self.price_cache
+                .insert(key.clone(), from_date, to_date, candles.clone(), provider)
+                .await;
+            return Ok((candles, provider));
+        }
+
+        let FetchedSeries { candles, providers } = self
+            .price_cache
+            .fetch_through(self.provider.as_ref(), &self.monitor, key, from_date, to_date)
+            .await
+            .map_err(|e| e.to_string())?;
+        // A series merged from several providers is attributed to the primary one when it
+        // served part of it
+        let primary = self.provider.name();
+        let provider = match providers.as_slice() {
+            [only] => *only,
+            _ if providers.contains(&primary) => primary,
+            _ => providers.first().copied().unwrap_or(primary),
+        };
+        Ok((candles, provider))
+    }
+
+    /// Recompute a token's values from a fresh provider fetch over the cached window,
+    /// without touching the cache, so they can be checked against what it serves
+    ///
+    /// `Ok(None)` when the fresh series is too short to compute volatility from.
+    pub async fn recompute(&self, token_address: &str) -> Result<Option<RecomputedVolatility>, String> {
+        let to_date = Utc::now();
+        let from_date = to_date - ChronoDuration::days(self.window_days(token_address) as i64);
+
+        let estimator = self.config.volatility_estimator.estimator();
+        let result = self
+            .provider
+            .fetch_history(token_address, Interval::OneDay, from_date, to_date, estimator.requires_ohlc())
+            .await;
+        self.monitor
+            .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+            .await;
+        let ProviderSeries { provider, candles } = result.map_err(|e| e.to_string())?;
+
+        let periods_per_year = Interval::OneDay.periods_per_year(self.config.annualization_days as f64);
+        Ok(estimator
+            .annualized_volatility(&candles, periods_per_year)
+            .map(|volatility| RecomputedVolatility {
+                volatility,
+                windows: self.window_volatilities(token_address, &candles, volatility, estimator.as_ref(), periods_per_year),
+                provider,
+                data_points: candles.len(),
+            }))
     }
 
     /// Add a token to the cache and immediately fetch its volatility
+    ///
+    /// Concurrent calls for the same token share a single provider fetch and its result.
     pub async fn add_token(&self, token_address: String) -> Result<(), Box<dyn std::error::Error>> {
-        let cache = Arc::clone(&self.cache);
-        let config = Arc::clone(&self.config);
-        
-        Self::update_token(&cache, &config, &token_address).await?;
-        
-        Ok(())
+        self.fetch_token(token_address, false).await
+    }
+
+    /// Add a token to the cache, or refresh it, recomputing its values from a whole window
+    /// refetched from the provider
+    ///
+    /// Unlike background refreshes, the stored series, the price cache and values shared by
+    /// other instances are all bypassed, so a corrupt stored series is replaced. Concurrent
+    /// calls for the same token share a single provider fetch and its result.
+    pub async fn force_refresh_token(&self, token_address: String) -> Result<(), Box<dyn std::error::Error>> {
+        self.fetch_token(token_address, true).await
     }
-} 
\ No newline at end of file
+
+    /// Shared fetch behind [`VolatilityCache::add_token`] and
+    /// [`VolatilityCache::force_refresh_token`]
+    async fn fetch_token(&self, token_address: String, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let fetch = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            in_flight
+                .entry((token_address.clone(), force))
+                .or_insert_with(|| {
+                    let this = self.clone();
+                    async move {
+                        let result = this
+                            .update_token(&token_address, true, force)
+                            .await
+                            .map_err(|e| e.to_string());
+                        this.in_flight.lock().unwrap().remove(&(token_address, force));
+                        result
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+        fetch.await.map_err(Into::into)
+    }
+
+    /// Keep a token tracked however long it goes unrequested, until it is removed
+    pub async fn pin_token(&self, token_address: &str) {
+        self.pinned.write().await.insert(token_address.to_string());
+    }
+
+    /// Whether a token is kept however long it goes unrequested
+    pub async fn is_pinned(&self, token_address: &str) -> bool {
+        self.pinned.read().await.contains(token_address)
+    }
+
+    /// Stop tracking a token, dropping its cached values
+    ///
+    /// Returns whether the token was tracked.
+    pub async fn remove_token(&self, token_address: &str) -> bool {
+        let removed = self.cache.write().await.remove(token_address).is_some();
+        self.series.write().await.remove(token_address);
+        self.pinned.write().await.remove(token_address);
+        if let Err(e) = self.backend.remove(token_address).await {
+            warn!(backend = self.backend.name(), error = %e, "Failed to remove shared token values");
+        }
+        removed
+    }
+
+    /// Save every cached token, with its stored series, to the database at
+    /// `VOLATILITY_CACHE_DB_PATH`, replacing what it held
+    ///
+    /// Does nothing without the `persistence` feature or a configured database.
+    pub async fn persist(&self) {
+        #[cfg(feature = "persistence")]
+        if let Some(store) = &self.store {
+            let tokens = self.snapshot().await;
+            let saved = tokens.iter().map(|token| (token.token_address.clone(), saved_state(token))).collect();
+            let records = tokens
+                .into_iter()
+                .map(|token| (token.token_address.clone(), token));
+            match store.save(records).await {
+                Ok(()) => *self.saved.lock().unwrap() = saved,
+                Err(e) => error!(error = %e, "Failed to persist the volatility cache"),
+            }
+        }
+    }
+
+    /// Save the tokens whose stored series or pin changed since they were last saved, and
+    /// delete those no longer cached, so a cycle that changed nothing writes nothing
+    ///
+    /// Does nothing without the `persistence` feature or a configured database.
+    async fn persist_changed(&self) {
+        #[cfg(feature = "persistence")]
+        if let Some(store) = &self.store {
+            let (changed, removed) = {
+                let cache = self.cache.read().await;
+                let series = self.series.read().await;
+                let pinned = self.pinned.read().await;
+                let mut saved = self.saved.lock().unwrap();
+                let mut changed = Vec::new();
+                for (token_address, entry) in cache.iter() {
+                    let stored = series.get(token_address);
+                    let is_pinned = pinned.contains(token_address);
+                    let state = (stored.map(|stored| stored.hash), is_pinned);
+                    if saved.get(token_address) != Some(&state) {
+                        saved.insert(token_address.clone(), state);
+                        changed.push((token_address.clone(), snapshot_of(token_address, entry, stored, is_pinned)));
+                    }
+                }
+                let removed: Vec<String> = saved
+                    .keys()
+                    .filter(|token_address| !cache.contains_key(*token_address))
+                    .cloned()
+                    .collect();
+                for token_address in &removed {
+                    saved.remove(token_address);
+                }
+                (changed, removed)
+            };
+            if changed.is_empty() && removed.is_empty() {
+                return;
+            }
+            debug!(changed = %changed.len(), removed = %removed.len(), "Persisting changed tokens");
+            if let Err(e) = store.update(changed, removed).await {
+                error!(error = %e, "Failed to persist the volatility cache");
+                // Unknown what was written, so write every token again next cycle
+                self.saved.lock().unwrap().clear();
+            }
+        }
+    }
+
+    /// Load the tokens saved by [`VolatilityCache::persist`] before the last shutdown
+    ///
+    /// Entries keep their refresh times, so those older than `VOLATILITY_CACHE_MAX_AGE_SECS`
+    /// are stale and recomputed rather than served until the background task refreshes them,
+    /// which only fetches the candles since their stored series. Entries older than
+    /// `VOLATILITY_CACHE_EVICT_AFTER_SECS` are dropped, and beyond
+    /// `VOLATILITY_CACHE_MAX_TOKENS`, the least recently requested ones. Returns the number
+    /// of tokens restored; always `0` without the `persistence` feature or a database.
+    pub async fn restore(&self) -> usize {
+        #[cfg(feature = "persistence")]
+        if let Some(store) = &self.store {
+            let tokens = store.load::<TokenSnapshot>();
+            // Tokens left out of the cache are deleted by the next save
+            *self.saved.lock().unwrap() = tokens
+                .iter()
+                .map(|token| (token.token_address.clone(), saved_state(token)))
+                .collect();
+            return self.restore_tokens(tokens).await;
+        }
+        #[cfg(not(feature = "persistence"))]
+        if self.config.volatility_cache_db_path.is_some() {
+            warn!("VOLATILITY_CACHE_DB_PATH is set but the persistence feature is disabled, starting empty");
+        }
+        0
+    }
+
+    /// Insert snapshotted tokens into the cache, as described in [`VolatilityCache::restore`]
+    #[cfg_attr(not(feature = "persistence"), allow(dead_code))]
+    async fn restore_tokens(&self, mut tokens: Vec<TokenSnapshot>) -> usize {
+        let evict_after = ChronoDuration::seconds(self.config.volatility_cache_evict_after_secs as i64);
+        let now = Utc::now();
+        tokens.retain(|token| now - token.last_updated <= evict_after);
+        tokens.sort_by_key(|token| std::cmp::Reverse(token.last_requested));
+        tokens.truncate(self.config.volatility_cache_max_tokens);
+
+        let mut cache = self.cache.write().await;
+        let mut series = self.series.write().await;
+        let mut pinned = self.pinned.write().await;
+        for token in &tokens {
+            cache.insert(
+                token.token_address.clone(),
+                self.cached_from(token, token.last_requested, token.demand),
+            );
+            if !token.candles.is_empty() {
+                series.insert(
+                    token.token_address.clone(),
+                    StoredSeries {
+                        hash: content_hash(&token.candles),
+                        candles: token.candles.clone(),
+                    },
+                );
+            }
+            if token.pinned {
+                pinned.insert(token.token_address.clone());
+            }
+        }
+        tokens.len()
+    }
+
+    /// Cache entry holding a snapshotted token's values, with the given request history
+    fn cached_from(&self, token: &TokenSnapshot, last_requested: DateTime<Utc>, demand: f64) -> CachedVolatility {
+        // Providers are named by `&'static str`, so map the stored name back to one
+        let provider = ProviderKind::ALL
+            .into_iter()
+            .map(ProviderKind::name)
+            .find(|name| *name == token.provider)
+            .unwrap_or_else(|| self.provider.name());
+        CachedVolatility {
+            volatility: token.volatility,
+            windows: token.windows,
+            last_updated: token.last_updated,
+            changed_at: token.changed_at,
+            provider,
+            data_points: token.data_points,
+            last_requested,
+            demand,
+        }
+    }
+
+    /// Every cached token with its stored series
+    #[cfg_attr(not(feature = "persistence"), allow(dead_code))]
+    async fn snapshot(&self) -> Vec<TokenSnapshot> {
+        let cache = self.cache.read().await;
+        let series = self.series.read().await;
+        let pinned = self.pinned.read().await;
+        cache
+            .iter()
+            .map(|(token_address, entry)| {
+                snapshot_of(token_address, entry, series.get(token_address), pinned.contains(token_address))
+            })
+            .collect()
+    }
+
+    /// Volatility over each of [`CACHED_WINDOW_DAYS`] the token caches, reusing the headline
+    /// `volatility` for its full window
+    fn window_volatilities(
+        &self,
+        token_address: &str,
+        candles: &[Candle],
+        volatility: f64,
+        estimator: &dyn VolatilityEstimator,
+        periods_per_year: f64,
+    ) -> [Option<f64>; CACHED_WINDOW_DAYS.len()] {
+        let window_days = self.window_days(token_address);
+        CACHED_WINDOW_DAYS.map(|days| {
+            if !self.registry.caches_window(token_address, days) {
+                None
+            } else if days == window_days {
+                Some(volatility)
+            } else {
+                trailing_volatility(candles, days as usize, estimator, periods_per_year)
+            }
+        })
+    }
+}
+
+/// Database at `VOLATILITY_CACHE_DB_PATH`, if set and it could be opened
+#[cfg(feature = "persistence")]
+fn open_store(config: &AppConfig) -> Option<Arc<CacheStore>> {
+    let path = config.volatility_cache_db_path.as_deref()?;
+    match CacheStore::open(Path::new(path)) {
+        Ok(store) => Some(Arc::new(store)),
+        Err(e) => {
+            error!(path = %path, error = %e, "Failed to open the volatility cache database, not persisting");
+            None
+        }
+    }
+}
+
+/// Whether a token can be added by [`VolatilityCache::make_room`]
+enum Room {
+    /// The cache has room, or already holds the token
+    Available,
+    /// The token fits in place of this evicted one
+    Evicted(String),
+    /// Every cached token is pinned, so none can be evicted
+    Full,
+}
+
+/// Claim on a token's refresh, released when the refresh ends or, should it panic or be
+/// cancelled, when dropped
+struct RefreshClaim {
+    backend: Arc<dyn CacheBackend>,
+    /// `None` once released
+    token_address: Option<String>,
+}
+
+impl RefreshClaim {
+    /// Release the claim, so the next refresh of the token can take it
+    async fn release(mut self) {
+        if let Some(token_address) = self.token_address.take() {
+            release_claim(self.backend.as_ref(), &token_address).await;
+        }
+    }
+}
+
+impl Drop for RefreshClaim {
+    fn drop(&mut self) {
+        if let Some(token_address) = self.token_address.take() {
+            let backend = self.backend.clone();
+            tokio::spawn(async move { release_claim(backend.as_ref(), &token_address).await });
+        }
+    }
+}
+
+async fn release_claim(backend: &dyn CacheBackend, token_address: &str) {
+    if let Err(e) = backend.release_refresh(token_address).await {
+        warn!(backend = backend.name(), error = %e, "Failed to release token refresh claim");
+    }
+}
+
+/// Snapshot of a token's cache entry and stored series
+fn snapshot_of(
+    token_address: &str,
+    entry: &CachedVolatility,
+    series: Option<&StoredSeries>,
+    pinned: bool,
+) -> TokenSnapshot {
+    TokenSnapshot {
+        token_address: token_address.to_string(),
+        volatility: entry.volatility,
+        windows: entry.windows,
+        last_updated: entry.last_updated,
+        changed_at: entry.changed_at,
+        provider: entry.provider.to_string(),
+        data_points: entry.data_points,
+        last_requested: entry.last_requested,
+        demand: entry.demand,
+        pinned,
+        candles: series.map(|stored| stored.candles.clone()).unwrap_or_default(),
+    }
+}
+
+/// Saved state of a snapshotted token, compared against the cache by
+/// [`VolatilityCache::persist_changed`]
+#[cfg(feature = "persistence")]
+fn saved_state(token: &TokenSnapshot) -> SavedState {
+    let hash = (!token.candles.is_empty()).then(|| content_hash(&token.candles));
+    (hash, token.pinned)
+}
+
+/// First time of `schedule` after `after`, and the time from it to the following one
+fn next_cycle(schedule: &Schedule, after: DateTime<Utc>) -> Option<(DateTime<Utc>, ChronoDuration)> {
+    let mut upcoming = schedule.after(&after);
+    let start = upcoming.next()?;
+    let next = upcoming.next()?;
+    Some((start, next - start))
+}
+
+/// Message of a caught panic, when it carries one
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Random fraction in `[0, 1)`, different at every call for the same token
+fn jitter(token_address: &str) -> f64 {
+    (RandomState::new().hash_one(token_address) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Hash of a price series' timestamps and exact price bits, used to detect unchanged refreshes
+fn content_hash(candles: &[Candle]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for candle in candles {
+        candle.unix_time.hash(&mut hasher);
+        for price in [candle.open, candle.high, candle.low, candle.close] {
+            price.to_bits().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ProviderError;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+
+    /// Serves a fixed daily series, failing for `bad` once `failing` is set.
+    #[derive(Default)]
+    struct StubProvider {
+        failing: AtomicBool,
+        /// History fetches served so far
+        calls: AtomicUsize,
+        /// Start of every requested range
+        requested_from: Mutex<Vec<DateTime<Utc>>>,
+        /// Fetches in progress, and the most ever in progress at once
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+        /// Panics on the next fetch when set
+        panic_once: AtomicBool,
+    }
+
+    #[async_trait]
+    impl PriceProvider for StubProvider {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        fn history_endpoint(&self, _ohlc: bool) -> String {
+            String::new()
+        }
+
+        async fn fetch_history(
+            &self,
+            token_address: &str,
+            _interval: Interval,
+            from_date: DateTime<Utc>,
+            _to_date: DateTime<Utc>,
+            _ohlc: bool,
+        ) -> Result<ProviderSeries, ProviderError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.panic_once.swap(false, Ordering::SeqCst) {
+                panic!("stub provider panicked");
+            }
+            self.requested_from.lock().unwrap().push(from_date);
+            // Let concurrent callers run while the fetch is pending
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            if token_address == "bad" && self.failing.load(Ordering::SeqCst) {
+                return Err("provider unavailable".into());
+            }
+            let start = from_date.timestamp();
+            Ok(ProviderSeries {
+                provider: self.name(),
+                candles: [100.0, 104.0, 99.0]
+                    .into_iter()
+                    .enumerate()
+                    .map(|(day, price)| Candle::from_price(start + day as i64 * 86_400, price))
+                    .collect(),
+            })
+        }
+
+        async fn fetch_current_price(&self, _token_address: &str) -> Result<f64, ProviderError> {
+            Ok(100.0)
+        }
+    }
+
+    /// Fixed current time, so successive refreshes request comparable windows
+    fn fixed_clock() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_lookups_and_refresh_errors() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache::with_provider(AppConfig::default(), provider.clone());
+        for token_address in ["good", "bad"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        assert!(cache.lookup("good").await.is_some());
+        assert!(cache.lookup("missing").await.is_none());
+
+        provider.failing.store(true, Ordering::SeqCst);
+        cache.update_all_tokens().await;
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 2);
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert_eq!(stats.hit_rate, 0.5);
+        assert_eq!((stats.refreshes, stats.refresh_errors), (2, 1));
+        assert!(stats.last_refresh_at.is_some());
+        assert!(stats.average_staleness_secs.is_some_and(|age| age >= 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_stale_entries_are_not_served_and_failing_ones_evicted() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache::with_provider(AppConfig::default(), provider.clone());
+        for token_address in ["good", "bad"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        let backdate = |secs: u64| Utc::now() - ChronoDuration::seconds(secs as i64 + 1);
+        {
+            let mut entries = cache.cache.write().await;
+            for entry in entries.values_mut() {
+                entry.last_updated = backdate(cache.config.volatility_cache_max_age_secs);
+            }
+        }
+        assert!(cache.get_volatility("good").await.is_none());
+        assert!(cache.get_window_volatility("good", 30).await.is_none());
+        assert!(cache.peek_volatility("good").await.is_some());
+        assert_eq!(cache.stats().await.stale_entries, 2);
+
+        cache.cache.write().await.get_mut("bad").unwrap().last_updated =
+            backdate(cache.config.volatility_cache_evict_after_secs);
+        // The shared values age along with the cached ones
+        for token_address in ["good", "bad"] {
+            cache.share(token_address).await;
+        }
+        provider.failing.store(true, Ordering::SeqCst);
+        cache.update_all_tokens().await;
+
+        assert!(cache.get_volatility("good").await.is_some());
+        assert_eq!(cache.tracked_tokens().await, vec!["good".to_string()]);
+        let stats = cache.stats().await;
+        assert_eq!((stats.stale_entries, stats.stale_evictions), (0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_full_cache_evicts_least_recently_requested_token() {
+        let config = AppConfig {
+            volatility_cache_max_tokens: 2,
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, Arc::new(StubProvider::default()));
+        for token_address in ["first", "second"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        assert!(cache.lookup("first").await.is_some());
+
+        cache.add_token("third".to_string()).await.expect("token should be cached");
+
+        assert_eq!(cache.tracked_tokens().await, vec!["first".to_string(), "third".to_string()]);
+        assert_eq!(cache.stats().await.capacity_evictions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_share_one_fetch() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache::with_provider(AppConfig::default(), provider.clone());
+
+        let results = futures::future::join_all(
+            (0..10).map(|_| cache.add_token("good".to_string())),
+        )
+        .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_fetches_only_from_the_last_stored_candle() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache {
+            clock: fixed_clock,
+            ..VolatilityCache::with_provider(AppConfig::default(), provider.clone())
+        };
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        let last_candle = cache.series.read().await["good"].candles.last().unwrap().unix_time;
+
+        cache.update_all_tokens().await;
+
+        let requested_from = provider.requested_from.lock().unwrap().clone();
+        assert_eq!(requested_from.len(), 2);
+        assert_eq!(requested_from[0], fixed_clock() - ChronoDuration::days(CACHE_WINDOW_DAYS));
+        assert_eq!(requested_from[1].timestamp(), last_candle);
+        // Stored candles before the refetched last one are kept
+        assert_eq!(cache.data_points("good").await, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_forced_refresh_refetches_the_whole_window() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache {
+            clock: fixed_clock,
+            ..VolatilityCache::with_provider(AppConfig::default(), provider.clone())
+        };
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        let volatility = cache.peek_volatility("good").await;
+        // Corrupt the stored series, keeping its hash
+        for candle in &mut cache.series.write().await.get_mut("good").unwrap().candles {
+            candle.close *= 1000.0;
+        }
+
+        cache.force_refresh_token("good".to_string()).await.expect("token should be refreshed");
+
+        let requested_from = provider.requested_from.lock().unwrap().clone();
+        // From the window start again, not from the last stored candle
+        assert_eq!(requested_from, vec![requested_from[0]; 2]);
+        assert_eq!(cache.data_points("good").await, Some(3));
+        assert!(cache.series.read().await["good"].candles.iter().all(|candle| candle.close < 1000.0));
+        assert_eq!(cache.peek_volatility("good").await, volatility);
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_are_spread_over_the_cycle() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache::with_provider(AppConfig::default(), provider.clone());
+        for token_address in ["first", "second"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+
+        let started = Instant::now();
+        cache.refresh_tokens(cache.tracked_tokens().await, Duration::from_millis(200)).await;
+
+        // The second token starts somewhere in the second half of the spread
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 4);
+        assert!((0..100).map(|_| jitter("first")).all(|j| (0.0..1.0).contains(&j)));
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_run_concurrently_up_to_the_limit() {
+        let provider = Arc::new(StubProvider::default());
+        let config = AppConfig {
+            refresh_concurrency: 3,
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, provider.clone());
+        for index in 0..8 {
+            cache.add_token(format!("token{index}")).await.expect("token should be cached");
+        }
+        assert_eq!(provider.max_active.load(Ordering::SeqCst), 1);
+
+        cache.update_all_tokens().await;
+
+        assert_eq!(provider.max_active.load(Ordering::SeqCst), 3);
+        assert_eq!(cache.stats().await.refreshes, 8);
+    }
+
+    #[tokio::test]
+    async fn test_only_hot_and_overdue_tokens_are_due() {
+        let config = AppConfig {
+            hot_token_min_requests: 3.0,
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, Arc::new(StubProvider::default()));
+        for token_address in ["hot", "cold", "overdue"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        for _ in 0..3 {
+            cache.lookup("hot").await;
+        }
+        cache.cache.write().await.get_mut("overdue").unwrap().last_updated =
+            Utc::now() - ChronoDuration::seconds(cache.config.cold_refresh_interval_secs as i64);
+
+        let mut due = cache.due_tokens().await;
+        due.sort();
+        assert_eq!(due, vec!["hot".to_string(), "overdue".to_string()]);
+        assert!(cache.is_hot_token("hot").await);
+        assert_eq!(cache.stats().await.hot_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unrequested_tokens_are_evicted_unless_pinned() {
+        let cache = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        for token_address in ["idle", "pinned", "requested"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        cache.pin_token("pinned").await;
+        {
+            let retention = ChronoDuration::seconds(cache.config.token_retention_secs as i64 + 1);
+            let mut entries = cache.cache.write().await;
+            for token_address in ["idle", "pinned"] {
+                entries.get_mut(token_address).unwrap().last_requested = Utc::now() - retention;
+            }
+        }
+
+        cache.update_all_tokens().await;
+
+        assert_eq!(cache.tracked_tokens().await, vec!["pinned".to_string(), "requested".to_string()]);
+        assert_eq!(cache.stats().await.idle_evictions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_eviction_spares_pinned_tokens() {
+        let config = AppConfig {
+            volatility_cache_max_tokens: 2,
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, Arc::new(StubProvider::default()));
+        for token_address in ["pinned", "other"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+        cache.pin_token("pinned").await;
+        // The pinned token is the least recently requested, but the unpinned one goes
+        cache.cache.write().await.get_mut("pinned").unwrap().last_requested -= ChronoDuration::hours(1);
+
+        cache.add_token("new".to_string()).await.expect("token should be cached");
+        assert_eq!(cache.tracked_tokens().await, vec!["new".to_string(), "pinned".to_string()]);
+
+        cache.pin_token("new").await;
+        assert!(cache.add_token("another".to_string()).await.is_err());
+        assert_eq!(cache.tracked_tokens().await, vec!["new".to_string(), "pinned".to_string()]);
+        assert_eq!(cache.stats().await.capacity_evictions, 1);
+    }
+
+    #[tokio::test]
+    async fn test_configured_tokens_follow_their_own_interval_and_windows() {
+        let provider = Arc::new(StubProvider::default());
+        let config = AppConfig {
+            token_refresh_config: Some(
+                r#"{ "hourly": { "refreshIntervalSecs": 3600, "windowDays": [7, 30] } }"#.to_string(),
+            ),
+            hot_token_min_requests: 0.0,
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, provider.clone());
+        for token_address in ["hourly", "default"] {
+            cache.add_token(token_address.to_string()).await.expect("token should be cached");
+        }
+
+        // Every unconfigured token is hot with no request threshold, the hourly one isn't due
+        assert_eq!(cache.due_tokens().await, vec!["default".to_string()]);
+        cache.cache.write().await.get_mut("hourly").unwrap().last_updated =
+            Utc::now() - ChronoDuration::seconds(3600);
+        assert_eq!(cache.due_tokens().await.len(), 2);
+
+        assert_eq!(cache.window_days("hourly"), 30);
+        assert_eq!(cache.get_window_volatility("hourly", 90).await, None);
+        assert_eq!(cache.get_window_volatility("hourly", 30).await, cache.get_volatility("hourly").await);
+        let requested_from = provider.requested_from.lock().unwrap()[0];
+        assert!((Utc::now() - requested_from).num_days() == 30);
+    }
+
+    #[tokio::test]
+    async fn test_panicked_refresh_task_is_restarted() {
+        let provider = Arc::new(StubProvider::default());
+        let cache = VolatilityCache::with_provider(AppConfig::default(), provider.clone());
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        let refreshed_at = cache.last_updated("good").await;
+
+        provider.panic_once.store(true, Ordering::SeqCst);
+        cache.start_background_task(CancellationToken::new()).await;
+        tokio::time::sleep(MIN_RESTART_BACKOFF + Duration::from_millis(500)).await;
+
+        assert_eq!(cache.stats().await.task_restarts, 1);
+        // The respawned task refreshed the token again
+        assert!(cache.last_updated("good").await > refreshed_at);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_refresh_task_stops() {
+        let cache = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        let shutdown = CancellationToken::new();
+        let task = cache.start_background_task(shutdown.clone()).await;
+
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("task should stop")
+            .expect("task should not panic");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_holds_tokens_and_their_series() {
+        let cache = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        cache.pin_token("good").await;
+
+        let tokens = cache.snapshot().await;
+        let token = &tokens[0];
+        assert_eq!(token.token_address, "good");
+        assert_eq!(Some(token.volatility), cache.peek_volatility("good").await);
+        assert!(token.pinned);
+        assert_eq!(token.candles.len(), token.data_points);
+    }
+
+    #[tokio::test]
+    async fn test_instances_share_values_and_refresh_work() {
+        let backend: Arc<dyn CacheBackend> = Arc::new(cache_backend::InMemoryBackend::default());
+        let (provider_a, provider_b) = (Arc::new(StubProvider::default()), Arc::new(StubProvider::default()));
+        let a = VolatilityCache::with_provider(AppConfig::default(), provider_a.clone()).with_backend(backend.clone());
+        let b = VolatilityCache::with_provider(AppConfig::default(), provider_b.clone()).with_backend(backend.clone());
+
+        a.add_token("good".to_string()).await.expect("token should be cached");
+        b.add_token("good".to_string()).await.expect("token should be cached");
+        assert_eq!(b.get_volatility("good").await, a.get_volatility("good").await);
+        assert_eq!(provider_b.calls.load(Ordering::SeqCst), 0);
+        assert_eq!(b.stats().await.shared_hits, 1);
+
+        // While another instance holds the refresh, background cycles leave the token to it
+        assert!(backend.claim_refresh("good", REFRESH_CLAIM_TTL).await.unwrap());
+        b.update_all_tokens().await;
+        assert_eq!(provider_b.calls.load(Ordering::SeqCst), 0);
+        backend.release_refresh("good").await.unwrap();
+        b.update_all_tokens().await;
+        assert_eq!(provider_b.calls.load(Ordering::SeqCst), 1);
+
+        // ...and take the values it shared afterwards
+        a.update_all_tokens().await;
+        assert_eq!(provider_a.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(a.stats().await.shared_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_computed_values_are_recorded_to_history() {
+        let cache = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+
+        let since = Utc::now() - ChronoDuration::hours(1);
+        for days in CACHED_WINDOW_DAYS {
+            let points = cache.history().points("good", days, since).await.unwrap();
+            let recorded = points.first().map(|point| point.volatility);
+            assert_eq!(recorded, cache.get_window_volatility("good", days).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restored_tokens_are_stale_by_age() {
+        let cache = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        cache.add_token("old".to_string()).await.expect("token should be cached");
+        cache.add_token("ancient".to_string()).await.expect("token should be cached");
+        let mut tokens = cache.snapshot().await;
+        for token in &mut tokens {
+            match token.token_address.as_str() {
+                "old" => token.last_updated -= ChronoDuration::hours(1),
+                "ancient" => token.last_updated -= ChronoDuration::days(2),
+                _ => {}
+            }
+        }
+
+        let restored = VolatilityCache::with_provider(AppConfig::default(), Arc::new(StubProvider::default()));
+        assert_eq!(restored.restore_tokens(tokens).await, 2);
+
+        assert_eq!(restored.get_volatility("good").await, cache.peek_volatility("good").await);
+        // Older than the max age: kept for its series, but not served until refreshed
+        assert_eq!(restored.get_volatility("old").await, None);
+        assert!(restored.peek_volatility("old").await.is_some());
+        assert_eq!(restored.peek_volatility("ancient").await, None);
+        assert_eq!(restored.provider_of("good").await, Some("stub"));
+        assert!(restored.series.read().await.contains_key("old"));
+    }
+
+    #[cfg(feature = "persistence")]
+    #[tokio::test]
+    async fn test_persisted_cache_is_restored() {
+        let path = std::env::temp_dir().join(format!("volatility-cache-db-{}", std::process::id()));
+        let config = AppConfig {
+            volatility_cache_db_path: Some(path.to_string_lossy().into_owned()),
+            ..AppConfig::default()
+        };
+        {
+            let cache = VolatilityCache::with_provider(config.clone(), Arc::new(StubProvider::default()));
+            cache.add_token("good".to_string()).await.expect("token should be cached");
+            cache.pin_token("good").await;
+            cache.persist().await;
+        }
+
+        let provider = Arc::new(StubProvider::default());
+        let restored = VolatilityCache::with_provider(config, provider.clone());
+        assert_eq!(restored.restore().await, 1);
+        assert!(restored.get_volatility("good").await.is_some());
+        assert!(restored.pinned.read().await.contains("good"));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 0);
+        drop(restored);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[cfg(feature = "persistence")]
+    #[tokio::test]
+    async fn test_cycles_persist_only_changed_tokens() {
+        let path = std::env::temp_dir().join(format!("volatility-cache-changes-db-{}", std::process::id()));
+        let config = AppConfig {
+            volatility_cache_db_path: Some(path.to_string_lossy().into_owned()),
+            ..AppConfig::default()
+        };
+        let cache = VolatilityCache::with_provider(config, Arc::new(StubProvider::default()));
+        cache.add_token("good".to_string()).await.expect("token should be cached");
+        cache.add_token("other".to_string()).await.expect("token should be cached");
+        cache.persist_changed().await;
+        let store = cache.store.clone().unwrap();
+        assert_eq!(store.load::<TokenSnapshot>().len(), 2);
+
+        // Overwrite the saved record, which an unchanged cycle leaves alone
+        let mut record = cache.snapshot().await.into_iter().find(|token| token.token_address == "good").unwrap();
+        record.volatility = -1.0;
+        store.update([("good".to_string(), record)], []).await.unwrap();
+        cache.persist_changed().await;
+        let saved_volatility = |tokens: Vec<TokenSnapshot>| {
+            tokens.into_iter().find(|token| token.token_address == "good").map(|token| token.volatility)
+        };
+        assert_eq!(saved_volatility(store.load()), Some(-1.0));
+
+        // Pinning changes the token, removing one deletes it
+        cache.pin_token("good").await;
+        cache.remove_token("other").await;
+        cache.persist_changed().await;
+        let tokens = store.load::<TokenSnapshot>();
+        assert_eq!(tokens.len(), 1);
+        assert!(tokens[0].pinned);
+        assert_eq!(saved_volatility(tokens), cache.peek_volatility("good").await);
+
+        drop((store, cache));
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_next_cycle_follows_the_cron_schedule() {
+        use chrono::TimeZone;
+
+        let schedule = Schedule::from_str("0 */5 * * * *").unwrap();
+        let after = Utc.with_ymd_and_hms(2025, 1, 1, 12, 3, 30).unwrap();
+
+        let (start, until_next) = next_cycle(&schedule, after).unwrap();
+
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 1, 1, 12, 5, 0).unwrap());
+        assert_eq!(until_next, ChronoDuration::minutes(5));
+        assert_eq!(next_cycle(&schedule, start).unwrap().0, Utc.with_ymd_and_hms(2025, 1, 1, 12, 10, 0).unwrap());
+    }
+
+    #[test]
+    fn test_content_hash_detects_changed_candles() {
+        let series = vec![Candle::from_price(1, 100.0), Candle::from_price(2, 101.0)];
+        let mut updated = series.clone();
+        updated[1].close = 101.5;
+
+        assert_eq!(content_hash(&series), content_hash(&series.clone()));
+        assert_ne!(content_hash(&series), content_hash(&updated));
+    }
+}
@@ -0,0 +1,265 @@
+//! # Volatility History
+//!
+//! Record of every volatility value the cache computes, behind a common [`HistoryStore`]
+//! trait. The in-memory store keeps recent points for the current process; with the
+//! `postgres` feature and `DATABASE_URL` set, points go to a Postgres (or Timescale) table,
+//! surviving restarts and giving operators an audit trail of what the API reported.
+//!
+//! A background compaction job thins out old points according to a [`RetentionPolicy`], so
+//! either store stays bounded.
+
+use crate::config::AppConfig;
+use crate::utils::timestamp_format;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+/// Time between two compactions of the history store
+pub const COMPACTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Error returned by a history store call, carrying the underlying failure message.
+pub type HistoryError = Box<dyn std::error::Error + Send + Sync>;
+
+/// One computed volatility value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityPoint {
+    pub token_address: String,
+    /// Length in days of the trailing window the value was computed over.
+    pub window_days: u32,
+    /// Annualized volatility.
+    pub volatility: f64,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub computed_at: DateTime<Utc>,
+}
+
+/// How long points are kept at each resolution, from `HISTORY_RAW_RETENTION_DAYS` and
+/// `HISTORY_HOURLY_RETENTION_DAYS`.
+///
+/// Every point is kept for `raw`; after that, only the last point of each hour until
+/// `hourly`, and then only the last point of each day, forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub raw: ChronoDuration,
+    pub hourly: ChronoDuration,
+}
+
+impl RetentionPolicy {
+    /// Policy configured in `config`.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            raw: ChronoDuration::days(config.history_raw_retention_days as i64),
+            hourly: ChronoDuration::days(config.history_hourly_retention_days as i64),
+        }
+    }
+
+    /// Bucket a point computed at `computed_at` is thinned out to as of `now`, as a
+    /// resolution in seconds and an index; `None` while every point is kept.
+    pub fn bucket(&self, computed_at: DateTime<Utc>, now: DateTime<Utc>) -> Option<(i64, i64)> {
+        let age = now - computed_at;
+        let resolution = if age < self.raw {
+            return None;
+        } else if age < self.hourly {
+            60 * 60
+        } else {
+            24 * 60 * 60
+        };
+        Some((resolution, computed_at.timestamp().div_euclid(resolution)))
+    }
+}
+
+/// Store of computed volatility points.
+#[async_trait]
+pub trait HistoryStore: Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &'static str;
+
+    /// Append `points` to the history.
+    async fn record(&self, points: &[VolatilityPoint]) -> Result<(), HistoryError>;
+
+    /// A token's points for a window computed at or after `since`, oldest first.
+    async fn points(
+        &self,
+        token_address: &str,
+        window_days: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<VolatilityPoint>, HistoryError>;
+
+    /// Drop the points `policy` no longer keeps as of `now`, returning how many were dropped.
+    async fn compact(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<u64, HistoryError>;
+}
+
+/// History store for `config`: Postgres when `DATABASE_URL` is set and the `postgres`
+/// feature enabled, otherwise in-memory.
+pub fn from_config(config: &AppConfig) -> Arc<dyn HistoryStore> {
+    #[cfg(feature = "postgres")]
+    if let Some(url) = config.database_url.as_deref() {
+        match postgres::PostgresHistory::new(url) {
+            Ok(store) => return Arc::new(store),
+            Err(e) => warn!(error = %e, "Invalid DATABASE_URL, keeping volatility history in memory"),
+        }
+    }
+    #[cfg(not(feature = "postgres"))]
+    if config.database_url.is_some() {
+        warn!("DATABASE_URL is set but the postgres feature is disabled, keeping volatility history in memory");
+    }
+
+    Arc::new(InMemoryHistory::default())
+}
+
+/// Compact `history` now and every [`COMPACTION_INTERVAL`] after.
+pub fn start_compaction(history: Arc<dyn HistoryStore>, policy: RetentionPolicy) {
+    tokio::spawn(async move {
+        loop {
+            match history.compact(&policy, Utc::now()).await {
+                Ok(dropped) => info!(store = history.name(), dropped = %dropped, "Compacted volatility history"),
+                Err(e) => warn!(store = history.name(), error = %e, "Failed to compact volatility history"),
+            }
+            tokio::time::sleep(COMPACTION_INTERVAL).await;
+        }
+    });
+}
+
+/// History kept in the process.
+#[derive(Default)]
+pub struct InMemoryHistory {
+    /// Map of token address and window length to its points, oldest first
+    series: RwLock<HashMap<(String, u32), Vec<VolatilityPoint>>>,
+}
+
+#[async_trait]
+impl HistoryStore for InMemoryHistory {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn record(&self, points: &[VolatilityPoint]) -> Result<(), HistoryError> {
+        let mut series = self.series.write().await;
+        for point in points {
+            series
+                .entry((point.token_address.clone(), point.window_days))
+                .or_default()
+                .push(point.clone());
+        }
+        Ok(())
+    }
+
+    async fn points(
+        &self,
+        token_address: &str,
+        window_days: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<VolatilityPoint>, HistoryError> {
+        let series = self.series.read().await;
+        Ok(series
+            .get(&(token_address.to_string(), window_days))
+            .map(|stored| {
+                stored
+                    .iter()
+                    .filter(|point| point.computed_at >= since)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn compact(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<u64, HistoryError> {
+        let mut series = self.series.write().await;
+        let mut dropped = 0;
+        for stored in series.values_mut() {
+            // Keep each point unless the next one falls in the same bucket
+            let buckets: Vec<_> = stored
+                .iter()
+                .map(|point| policy.bucket(point.computed_at, now))
+                .collect();
+            let before = stored.len();
+            let mut index = 0;
+            stored.retain(|_| {
+                let bucket = buckets[index];
+                index += 1;
+                bucket.is_none() || buckets.get(index) != Some(&bucket)
+            });
+            dropped += (before - stored.len()) as u64;
+        }
+        Ok(dropped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn point(window_days: u32, volatility: f64, computed_at: DateTime<Utc>) -> VolatilityPoint {
+        VolatilityPoint {
+            token_address: "mint".to_string(),
+            window_days,
+            volatility,
+            computed_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_history_filters_by_window_and_time() {
+        let history = InMemoryHistory::default();
+        let now = Utc::now();
+        history
+            .record(&[
+                point(30, 0.5, now - Duration::days(2)),
+                point(30, 0.6, now),
+                point(90, 0.7, now),
+            ])
+            .await
+            .unwrap();
+
+        let points = history.points("mint", 30, now - Duration::days(1)).await.unwrap();
+        assert_eq!(points, vec![point(30, 0.6, now)]);
+        assert_eq!(history.points("mint", 30, now - Duration::days(3)).await.unwrap().len(), 2);
+        assert!(history.points("other", 30, now - Duration::days(3)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compaction_keeps_last_point_per_hour_then_per_day() {
+        let history = InMemoryHistory::default();
+        let policy = RetentionPolicy {
+            raw: Duration::days(7),
+            hourly: Duration::days(90),
+        };
+        let now = "2025-06-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let at = |days: i64, minutes: i64| now - Duration::days(days) + Duration::minutes(minutes);
+        history
+            .record(&[
+                // Older than the hourly retention: the last point of the day kept
+                point(30, 0.30, at(100, 0)),
+                point(30, 0.31, at(100, 120)),
+                // Within it: the last point of each hour kept
+                point(30, 0.20, at(10, 0)),
+                point(30, 0.21, at(10, 30)),
+                point(30, 0.22, at(10, 60)),
+                // Within the raw retention: every point kept
+                point(30, 0.10, at(1, 0)),
+                point(30, 0.11, at(1, 1)),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(history.compact(&policy, now).await.unwrap(), 2);
+        let kept: Vec<f64> = history
+            .points("mint", 30, now - Duration::days(365))
+            .await
+            .unwrap()
+            .iter()
+            .map(|point| point.volatility)
+            .collect();
+        assert_eq!(kept, vec![0.31, 0.21, 0.22, 0.10, 0.11]);
+        assert_eq!(history.compact(&policy, now).await.unwrap(), 0);
+    }
+}
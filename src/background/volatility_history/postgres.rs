@@ -0,0 +1,133 @@
+//! Postgres store, writing one row per point to the `volatility_history` table.
+//!
+//! The table is created on first use. On Timescale it can be turned into a hypertable with
+//! `SELECT create_hypertable('volatility_history', 'computed_at', migrate_data => true)`.
+
+use super::{HistoryError, HistoryStore, RetentionPolicy, VolatilityPoint};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::{QueryBuilder, Row};
+use tokio::sync::OnceCell;
+
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS volatility_history (
+    token_address TEXT NOT NULL,
+    window_days INTEGER NOT NULL,
+    volatility DOUBLE PRECISION NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL
+)";
+
+const CREATE_INDEX: &str = "CREATE INDEX IF NOT EXISTS volatility_history_series
+    ON volatility_history (token_address, window_days, computed_at)";
+
+/// Deletes every point computed before `$1` but the last of its series in each `$2`-long
+/// UTC bucket (`'hour'` or `'day'`)
+const THIN_OUT: &str = "DELETE FROM volatility_history h
+    USING (
+        SELECT token_address, window_days, computed_at, ROW_NUMBER() OVER (
+            PARTITION BY token_address, window_days, date_trunc($2, computed_at AT TIME ZONE 'UTC')
+            ORDER BY computed_at DESC
+        ) AS position
+        FROM volatility_history
+        WHERE computed_at < $1
+    ) ranked
+    WHERE ranked.position > 1
+        AND h.token_address = ranked.token_address
+        AND h.window_days = ranked.window_days
+        AND h.computed_at = ranked.computed_at";
+
+/// Store backed by a Postgres connection pool.
+pub struct PostgresHistory {
+    pool: PgPool,
+    /// Set once the table and its index exist
+    schema: OnceCell<()>,
+}
+
+impl PostgresHistory {
+    /// Store for the database at `url`, connecting on first use.
+    ///
+    /// # Errors
+    /// - Returns the sqlx error when `url` is not a valid Postgres URL.
+    pub fn new(url: &str) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            pool: PgPoolOptions::new().max_connections(4).connect_lazy(url)?,
+            schema: OnceCell::new(),
+        })
+    }
+
+    /// Pool of a database whose table exists, creating it on the first call.
+    async fn pool(&self) -> Result<&PgPool, HistoryError> {
+        self.schema
+            .get_or_try_init(|| async {
+                sqlx::query(CREATE_TABLE).execute(&self.pool).await?;
+                sqlx::query(CREATE_INDEX).execute(&self.pool).await?;
+                Ok::<_, sqlx::Error>(())
+            })
+            .await?;
+        Ok(&self.pool)
+    }
+}
+
+#[async_trait]
+impl HistoryStore for PostgresHistory {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn record(&self, points: &[VolatilityPoint]) -> Result<(), HistoryError> {
+        if points.is_empty() {
+            return Ok(());
+        }
+        let pool = self.pool().await?;
+        let mut query = QueryBuilder::new(
+            "INSERT INTO volatility_history (token_address, window_days, volatility, computed_at) ",
+        );
+        query.push_values(points, |mut row, point| {
+            row.push_bind(&point.token_address)
+                .push_bind(point.window_days as i32)
+                .push_bind(point.volatility)
+                .push_bind(point.computed_at);
+        });
+        query.build().execute(pool).await?;
+        Ok(())
+    }
+
+    async fn points(
+        &self,
+        token_address: &str,
+        window_days: u32,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<VolatilityPoint>, HistoryError> {
+        let pool = self.pool().await?;
+        let rows = sqlx::query(
+            "SELECT volatility, computed_at FROM volatility_history
+             WHERE token_address = $1 AND window_days = $2 AND computed_at >= $3
+             ORDER BY computed_at",
+        )
+        .bind(token_address)
+        .bind(window_days as i32)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(VolatilityPoint {
+                    token_address: token_address.to_string(),
+                    window_days,
+                    volatility: row.try_get("volatility")?,
+                    computed_at: row.try_get("computed_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn compact(&self, policy: &RetentionPolicy, now: DateTime<Utc>) -> Result<u64, HistoryError> {
+        let pool = self.pool().await?;
+        let mut dropped = 0;
+        for (cutoff, bucket) in [(now - policy.raw, "hour"), (now - policy.hourly, "day")] {
+            let result = sqlx::query(THIN_OUT).bind(cutoff).bind(bucket).execute(pool).await?;
+            dropped += result.rows_affected();
+        }
+        Ok(dropped)
+    }
+}
@@ -0,0 +1,468 @@
+//! Typed Rust client for this API, built on the request and response types the server
+//! itself uses, so calling services can't drift from the wire format.
+//!
+//! Enabled with the `client` feature:
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), historical_volatility_api::client::ClientError> {
+//! use historical_volatility_api::client::Client;
+//! use historical_volatility_api::extractors::query_extractor::HistoricalVolatilityParams;
+//!
+//! let client = Client::new("http://localhost:3000");
+//! let response = client
+//!     .historical_volatility(&HistoricalVolatilityParams {
+//!         token_address: "So11111111111111111111111111111111111111112".to_string(),
+//!         days: Some(30),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! println!("{}", response.historical_volatility);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::extractors::query_extractor::HistoricalVolatilityParams;
+#[cfg(feature = "admin")]
+use crate::background::volatility_cache::CacheStats;
+#[cfg(feature = "admin")]
+use crate::presets::Preset;
+#[cfg(feature = "admin")]
+use crate::routes::admin::{
+    ImportResponse, StateSnapshot, TrackTokenRequest, TrackTokenResponse, VerifyResponse,
+};
+use crate::routes::backtest::{BacktestQuery, BacktestResponse};
+use crate::routes::beta::{BetaQuery, BetaResponse};
+use crate::routes::correlation::{CorrelationQuery, CorrelationResponse};
+use crate::routes::correlation_matrix::{CorrelationMatrixRequest, CorrelationMatrixResponse};
+use crate::routes::deprecations::DeprecationsResponse;
+use crate::routes::drawdown::{DrawdownQuery, DrawdownResponse};
+use crate::routes::explain::ExplainResponse;
+#[cfg(feature = "parquet")]
+use crate::routes::export::VolatilitySeriesExportQuery;
+use crate::routes::health_check::HealthCheckResponse;
+use crate::routes::heatmap::{HeatmapQuery, HeatmapResponse};
+use crate::routes::historical_volatility::HistoricalVolatilityResponse;
+use crate::routes::hv_rank::{HvRankQuery, HvRankResponse};
+use crate::routes::methodology::{ChangeLogQuery, ChangeLogResponse};
+use crate::routes::option_price::{OptionPriceQuery, OptionPriceResponse};
+use crate::routes::presets::PresetsResponse;
+use crate::routes::prices::{PricesQuery, PricesResponse};
+use crate::routes::return_distribution::{ReturnDistributionQuery, ReturnDistributionResponse};
+use crate::routes::returns::{ReturnsQuery, ReturnsResponse};
+use crate::routes::risk_adjusted_returns::{RiskAdjustedReturnsQuery, RiskAdjustedReturnsResponse};
+#[cfg(feature = "simulation")]
+use crate::routes::simulate::{MonteCarloRequest, MonteCarloResponse};
+use crate::routes::status::StatusResponse;
+use crate::routes::stress_test::{StressTestRequest, StressTestResponse};
+use crate::routes::token_metadata::TokenMetadataResponse;
+use crate::routes::token_search::{TokenSearchQuery, TokenSearchResponse};
+use crate::routes::tracked_tokens::TrackedTokensResponse;
+use crate::routes::updates::{UpdatesQuery, UpdatesResponse};
+use crate::routes::value_at_risk::{ValueAtRiskQuery, ValueAtRiskResponse};
+use crate::routes::vol_of_vol::{VolOfVolQuery, VolOfVolResponse};
+use crate::routes::vol_regime::{VolRegimeQuery, VolRegimeResponse};
+use crate::routes::volatility_history::{VolatilityHistoryQuery, VolatilityHistoryResponse};
+use crate::utils::address_type::AddressType;
+use crate::utils::chain::Chain;
+use reqwest::{Method, RequestBuilder, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt;
+
+/// Header carrying the admin key, as expected by the server's admin guard.
+#[cfg(feature = "admin")]
+const ADMIN_KEY_HEADER: &str = crate::extractors::admin_auth::ADMIN_KEY_HEADER;
+
+/// Failure of a client call.
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request could not be sent, or the response could not be read or decoded.
+    Http(reqwest::Error),
+    /// The server answered with an error status and its JSON error body.
+    Api {
+        status: u16,
+        error: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Http(err) => write!(f, "request failed: {}", err),
+            ClientError::Api {
+                status,
+                error,
+                message,
+            } => write!(f, "{} {}: {}", status, error, message),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        ClientError::Http(err)
+    }
+}
+
+/// Error body the server returns with every error status.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+    message: String,
+}
+
+/// Client for one instance of the API.
+///
+/// Cheap to clone; clones share the connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    http: reqwest::Client,
+    chain: Option<Chain>,
+    address_type: Option<AddressType>,
+    #[cfg(feature = "admin")]
+    admin_key: Option<String>,
+}
+
+impl Client {
+    /// Create a client for the API served at `base_url`, e.g. `http://localhost:3000`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Create a client sending requests through `http`, e.g. one with timeouts configured.
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            http,
+            chain: None,
+            address_type: None,
+            #[cfg(feature = "admin")]
+            admin_key: None,
+        }
+    }
+
+    /// Query tokens on `chain` instead of the server's default, Solana.
+    pub fn chain(mut self, chain: Chain) -> Self {
+        self.chain = Some(chain);
+        self
+    }
+
+    /// Treat token addresses as `address_type` (e.g. pair addresses).
+    pub fn address_type(mut self, address_type: AddressType) -> Self {
+        self.address_type = Some(address_type);
+        self
+    }
+
+    /// Admin key sent with `/admin` calls.
+    #[cfg(feature = "admin")]
+    pub fn admin_key(mut self, admin_key: impl Into<String>) -> Self {
+        self.admin_key = Some(admin_key.into());
+        self
+    }
+
+    /// `GET /historicalVolatility`.
+    pub async fn historical_volatility(
+        &self,
+        params: &HistoricalVolatilityParams,
+    ) -> Result<HistoricalVolatilityResponse, ClientError> {
+        self.get("/historicalVolatility", params).await
+    }
+
+    /// `GET /historicalVolatility/explain`.
+    pub async fn explain_historical_volatility(
+        &self,
+        params: &HistoricalVolatilityParams,
+    ) -> Result<ExplainResponse, ClientError> {
+        self.get("/historicalVolatility/explain", params).await
+    }
+
+    /// `GET /presets`.
+    pub async fn presets(&self) -> Result<PresetsResponse, ClientError> {
+        self.get("/presets", &()).await
+    }
+
+    /// `GET /methodology/changelog`.
+    pub async fn change_log(&self, query: &ChangeLogQuery) -> Result<ChangeLogResponse, ClientError> {
+        self.get("/methodology/changelog", query).await
+    }
+
+    /// `GET /deprecations`.
+    pub async fn deprecations(&self) -> Result<DeprecationsResponse, ClientError> {
+        self.get("/deprecations", &()).await
+    }
+
+    /// `GET /returns`.
+    pub async fn returns(&self, query: &ReturnsQuery) -> Result<ReturnsResponse, ClientError> {
+        self.get("/returns", query).await
+    }
+
+    /// `GET /prices`.
+    pub async fn prices(&self, query: &PricesQuery) -> Result<PricesResponse, ClientError> {
+        self.get("/prices", query).await
+    }
+
+    /// `GET /export/prices.parquet`, returning the Parquet file's bytes.
+    #[cfg(feature = "parquet")]
+    pub async fn export_prices(&self, query: &PricesQuery) -> Result<Vec<u8>, ClientError> {
+        self.download("/export/prices.parquet", query).await
+    }
+
+    /// `GET /export/volatilitySeries.parquet`, returning the Parquet file's bytes.
+    #[cfg(feature = "parquet")]
+    pub async fn export_volatility_series(
+        &self,
+        query: &VolatilitySeriesExportQuery,
+    ) -> Result<Vec<u8>, ClientError> {
+        self.download("/export/volatilitySeries.parquet", query).await
+    }
+
+    /// `GET /correlation`.
+    pub async fn correlation(&self, query: &CorrelationQuery) -> Result<CorrelationResponse, ClientError> {
+        self.get("/correlation", query).await
+    }
+
+    /// `GET /beta`.
+    pub async fn beta(&self, query: &BetaQuery) -> Result<BetaResponse, ClientError> {
+        self.get("/beta", query).await
+    }
+
+    /// `GET /hvRank`.
+    pub async fn hv_rank(&self, query: &HvRankQuery) -> Result<HvRankResponse, ClientError> {
+        self.get("/hvRank", query).await
+    }
+
+    /// `GET /volOfVol`.
+    pub async fn vol_of_vol(&self, query: &VolOfVolQuery) -> Result<VolOfVolResponse, ClientError> {
+        self.get("/volOfVol", query).await
+    }
+
+    /// `GET /volRegime`.
+    pub async fn vol_regime(&self, query: &VolRegimeQuery) -> Result<VolRegimeResponse, ClientError> {
+        self.get("/volRegime", query).await
+    }
+
+    /// `GET /backtest`.
+    pub async fn backtest(&self, query: &BacktestQuery) -> Result<BacktestResponse, ClientError> {
+        self.get("/backtest", query).await
+    }
+
+    /// `POST /correlationMatrix`.
+    pub async fn correlation_matrix(
+        &self,
+        request: &CorrelationMatrixRequest,
+    ) -> Result<CorrelationMatrixResponse, ClientError> {
+        self.post("/correlationMatrix", request).await
+    }
+
+    /// `GET /heatmap`.
+    pub async fn heatmap(&self, query: &HeatmapQuery) -> Result<HeatmapResponse, ClientError> {
+        self.get("/heatmap", query).await
+    }
+
+    /// `GET /updates`.
+    pub async fn updates(&self, query: &UpdatesQuery) -> Result<UpdatesResponse, ClientError> {
+        self.get("/updates", query).await
+    }
+
+    /// `GET /volatilityHistory`.
+    pub async fn volatility_history(
+        &self,
+        query: &VolatilityHistoryQuery,
+    ) -> Result<VolatilityHistoryResponse, ClientError> {
+        self.get("/volatilityHistory", query).await
+    }
+
+    /// `GET /drawdown`.
+    pub async fn drawdown(&self, query: &DrawdownQuery) -> Result<DrawdownResponse, ClientError> {
+        self.get("/drawdown", query).await
+    }
+
+    /// `GET /returnDistribution`.
+    pub async fn return_distribution(
+        &self,
+        query: &ReturnDistributionQuery,
+    ) -> Result<ReturnDistributionResponse, ClientError> {
+        self.get("/returnDistribution", query).await
+    }
+
+    /// `GET /riskAdjustedReturns`.
+    pub async fn risk_adjusted_returns(
+        &self,
+        query: &RiskAdjustedReturnsQuery,
+    ) -> Result<RiskAdjustedReturnsResponse, ClientError> {
+        self.get("/riskAdjustedReturns", query).await
+    }
+
+    /// `GET /var`.
+    pub async fn value_at_risk(&self, query: &ValueAtRiskQuery) -> Result<ValueAtRiskResponse, ClientError> {
+        self.get("/var", query).await
+    }
+
+    /// `GET /optionPrice`.
+    pub async fn option_price(&self, query: &OptionPriceQuery) -> Result<OptionPriceResponse, ClientError> {
+        self.get("/optionPrice", query).await
+    }
+
+    /// `POST /simulate`.
+    #[cfg(feature = "simulation")]
+    pub async fn simulate(&self, request: &MonteCarloRequest) -> Result<MonteCarloResponse, ClientError> {
+        self.post("/simulate", request).await
+    }
+
+    /// `POST /simulate/stress`.
+    pub async fn stress_test(&self, request: &StressTestRequest) -> Result<StressTestResponse, ClientError> {
+        self.post("/simulate/stress", request).await
+    }
+
+    /// `GET /tokens`.
+    pub async fn tracked_tokens(&self) -> Result<TrackedTokensResponse, ClientError> {
+        self.get("/tokens", &()).await
+    }
+
+    /// `GET /tokens/search`.
+    pub async fn search_tokens(&self, query: &TokenSearchQuery) -> Result<TokenSearchResponse, ClientError> {
+        self.get("/tokens/search", query).await
+    }
+
+    /// Metadata of the token at `address`, which may also be a symbol such as `JUP`.
+    pub async fn token_metadata(&self, address: &str) -> Result<TokenMetadataResponse, ClientError> {
+        self.get(&format!("/token/{}", address), &()).await
+    }
+
+    /// `GET /healthCheck`.
+    pub async fn health_check(&self) -> Result<HealthCheckResponse, ClientError> {
+        self.get("/healthCheck", &()).await
+    }
+
+    /// `GET /status`.
+    pub async fn status(&self) -> Result<StatusResponse, ClientError> {
+        self.get("/status", &()).await
+    }
+
+    /// `GET /admin/cacheStats`.
+    #[cfg(feature = "admin")]
+    pub async fn cache_stats(&self) -> Result<CacheStats, ClientError> {
+        self.get("/admin/cacheStats", &()).await
+    }
+
+    /// `GET /admin/export`.
+    #[cfg(feature = "admin")]
+    pub async fn export_state(&self) -> Result<StateSnapshot, ClientError> {
+        self.get("/admin/export", &()).await
+    }
+
+    /// `POST /admin/import`.
+    #[cfg(feature = "admin")]
+    pub async fn import_state(&self, snapshot: &StateSnapshot) -> Result<ImportResponse, ClientError> {
+        self.post("/admin/import", snapshot).await
+    }
+
+    /// `PUT /admin/presets/{name}`.
+    #[cfg(feature = "admin")]
+    pub async fn put_preset(&self, name: &str, preset: &Preset) -> Result<Preset, ClientError> {
+        let request = self
+            .request(Method::PUT, &format!("/admin/presets/{}", name))
+            .json(preset);
+        decode(send(request).await?).await
+    }
+
+    /// `DELETE /admin/presets/{name}`.
+    #[cfg(feature = "admin")]
+    pub async fn delete_preset(&self, name: &str) -> Result<(), ClientError> {
+        send(self.request(Method::DELETE, &format!("/admin/presets/{}", name)))
+            .await
+            .map(|_| ())
+    }
+
+    /// `POST /admin/tokens`.
+    #[cfg(feature = "admin")]
+    pub async fn track_token(&self, request: &TrackTokenRequest) -> Result<TrackTokenResponse, ClientError> {
+        self.post("/admin/tokens", request).await
+    }
+
+    /// `DELETE /admin/tokens/{address}`.
+    #[cfg(feature = "admin")]
+    pub async fn untrack_token(&self, token_address: &str) -> Result<(), ClientError> {
+        send(self.request(Method::DELETE, &format!("/admin/tokens/{}", token_address)))
+            .await
+            .map(|_| ())
+    }
+
+    /// `GET /admin/verify/{token}`.
+    #[cfg(feature = "admin")]
+    pub async fn verify_token(&self, token_address: &str) -> Result<VerifyResponse, ClientError> {
+        self.get(&format!("/admin/verify/{}", token_address), &()).await
+    }
+
+    async fn get<Q, T>(&self, path: &str, query: &Q) -> Result<T, ClientError>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        decode(send(self.request(Method::GET, path).query(query)).await?).await
+    }
+
+    #[cfg(feature = "parquet")]
+    async fn download<Q>(&self, path: &str, query: &Q) -> Result<Vec<u8>, ClientError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let response = send(self.request(Method::GET, path).query(query)).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn post<B, T>(&self, path: &str, body: &B) -> Result<T, ClientError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        decode(send(self.request(Method::POST, path).json(body)).await?).await
+    }
+
+    /// Request to `path` carrying the client-wide chain, address type and admin key.
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let mut request = self.http.request(method, format!("{}{}", self.base_url, path));
+        if let Some(chain) = self.chain {
+            request = request.query(&[("chain", chain.as_str())]);
+        }
+        if let Some(address_type) = self.address_type {
+            request = request.query(&[("addressType", address_type.as_str())]);
+        }
+        #[cfg(feature = "admin")]
+        if let Some(admin_key) = &self.admin_key {
+            request = request.header(ADMIN_KEY_HEADER, admin_key);
+        }
+        request
+    }
+}
+
+/// Sends `request`, turning error statuses into `ClientError::Api`.
+async fn send(request: RequestBuilder) -> Result<Response, ClientError> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let text = response.text().await?;
+    let (error, message) = match serde_json::from_str::<ErrorBody>(&text) {
+        Ok(body) => (body.error, body.message),
+        // e.g. the panic handler's body, or a proxy's error page
+        Err(_) => (
+            status.canonical_reason().unwrap_or_default().to_string(),
+            text,
+        ),
+    };
+    Err(ClientError::Api {
+        status: status.as_u16(),
+        error,
+        message,
+    })
+}
+
+async fn decode<T: DeserializeOwned>(response: Response) -> Result<T, ClientError> {
+    Ok(response.json::<T>().await?)
+}
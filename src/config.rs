@@ -1,15 +1,355 @@
+use crate::background::token_registry::TokenRegistry;
+use crate::cors;
+use crate::math::regime::RegimeThresholds;
+use crate::presets::Methodology;
+use crate::provider::feed_registry::FeedRegistry;
+use crate::provider::{ProviderEnvironment, ProviderKind};
+use crate::math::{EstimatorKind, DAYS_PER_YEAR};
 use serde::Deserialize;
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub birdeye_api_key: String,
     pub birdeye_base_url: String,
-    pub app_server_port: u16
+    pub app_server_port: u16,
+    /// Birdeye OHLCV endpoint, used by the range-based estimators.
+    #[serde(default = "default_birdeye_ohlcv_url")]
+    pub birdeye_ohlcv_url: String,
+    /// Birdeye pair OHLCV endpoint, used by range-based estimators with `addressType=pair`.
+    #[serde(default = "default_birdeye_pair_ohlcv_url")]
+    pub birdeye_pair_ohlcv_url: String,
+    /// Birdeye current price endpoint.
+    #[serde(default = "default_birdeye_price_url")]
+    pub birdeye_price_url: String,
+    /// Birdeye token search endpoint, used by `/tokens/search`.
+    #[serde(default = "default_birdeye_search_url")]
+    pub birdeye_search_url: String,
+    /// Birdeye token metadata endpoint, used by `/token/{address}`.
+    #[serde(default = "default_birdeye_token_metadata_url")]
+    pub birdeye_token_metadata_url: String,
+    /// Estimator the background cache maintains values for.
+    #[serde(default)]
+    pub volatility_estimator: EstimatorKind,
+    /// Trading days per year used to annualize volatility (365 for crypto, 252 for TradFi).
+    #[serde(default = "default_annualization_days")]
+    pub annualization_days: u32,
+    /// How long fetched price series are reused before refetching from Birdeye.
+    #[serde(default = "default_price_cache_ttl_secs")]
+    pub price_cache_ttl_secs: u64,
+    /// Most price series kept in the price cache; expired series are dropped first, then the
+    /// oldest fetched.
+    #[serde(default = "default_price_cache_max_entries")]
+    pub price_cache_max_entries: usize,
+    /// Age in seconds beyond which cached volatility is no longer served, and is recomputed
+    /// on request instead.
+    #[serde(default = "default_volatility_cache_max_age_secs")]
+    pub volatility_cache_max_age_secs: u64,
+    /// Seconds without a successful refresh after which a token is evicted from the
+    /// volatility cache and no longer refreshed.
+    #[serde(default = "default_volatility_cache_evict_after_secs")]
+    pub volatility_cache_evict_after_secs: u64,
+    /// Maximum number of tokens in the volatility cache; tracking another evicts the least
+    /// recently requested one.
+    #[serde(default = "default_volatility_cache_max_tokens")]
+    pub volatility_cache_max_tokens: usize,
+    /// Fraction of each refresh cycle the background task spreads token refreshes over, with
+    /// jitter, instead of firing them back to back; `0` refreshes them all at once.
+    #[serde(default = "default_refresh_spread_ratio")]
+    pub refresh_spread_ratio: f64,
+    /// Maximum number of token refreshes the background task runs at once.
+    #[serde(default = "default_refresh_concurrency")]
+    pub refresh_concurrency: usize,
+    /// Requests over roughly the last `HOT_TOKEN_WINDOW_SECS` that make a tracked token hot,
+    /// refreshed every cycle rather than every `COLD_REFRESH_INTERVAL_SECS`.
+    #[serde(default = "default_hot_token_min_requests")]
+    pub hot_token_min_requests: f64,
+    /// Time constant in seconds of the decaying request count deciding whether a token is hot.
+    #[serde(default = "default_hot_token_window_secs")]
+    pub hot_token_window_secs: u64,
+    /// Seconds between refreshes of tracked tokens that aren't hot.
+    #[serde(default = "default_cold_refresh_interval_secs")]
+    pub cold_refresh_interval_secs: u64,
+    /// Seconds without a request after which a token is dropped from the volatility cache,
+    /// unless it was tracked at startup or through the admin API.
+    #[serde(default = "default_token_retention_secs")]
+    pub token_retention_secs: u64,
+    /// Cron expression with seconds (e.g. `0 */5 * * * *`, UTC) on which the background task
+    /// starts refresh cycles, instead of every 60 seconds.
+    #[serde(default)]
+    pub cache_refresh_cron: Option<String>,
+    /// JSON object of token mint to `{ "refreshIntervalSecs", "windowDays" }`, overriding
+    /// how the background task refreshes those tokens.
+    #[serde(default)]
+    pub token_refresh_config: Option<String>,
+    /// Directory of the database the volatility cache is saved to after every refresh cycle
+    /// and restored from at startup. Requires the `persistence` feature.
+    #[serde(default)]
+    pub volatility_cache_db_path: Option<String>,
+    /// Redis server volatility values and refresh claims are shared through, so replicas
+    /// don't each refresh every token. Requires the `redis` feature.
+    #[serde(default)]
+    pub redis_url: Option<String>,
+    /// Postgres (or Timescale) database every computed volatility value is recorded to,
+    /// instead of memory. Requires the `postgres` feature.
+    #[serde(default)]
+    pub database_url: Option<String>,
+    /// Days every recorded volatility value is kept, before being thinned to one per hour.
+    #[serde(default = "default_history_raw_retention_days")]
+    pub history_raw_retention_days: u32,
+    /// Days recorded volatility values are kept at one per hour, before being thinned to
+    /// one per day, kept forever.
+    #[serde(default = "default_history_hourly_retention_days")]
+    pub history_hourly_retention_days: u32,
+    /// Token `/beta` is measured against when no `benchmark` is given (SOL by default).
+    #[serde(default = "default_benchmark_token_address")]
+    pub benchmark_token_address: String,
+    /// Maximum number of tokens accepted by `/correlationMatrix`.
+    #[serde(default = "default_max_matrix_tokens")]
+    pub max_matrix_tokens: usize,
+    /// Longest `fromDate`..`toDate` range accepted, in days.
+    #[serde(default = "default_max_date_range_days")]
+    pub max_date_range_days: u32,
+    /// Annualized risk-free rate as a decimal, used by the risk-adjusted return ratios.
+    #[serde(default)]
+    pub risk_free_rate: f64,
+    /// Key required in the `X-Admin-Key` header by `/admin` endpoints. They are disabled when unset.
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
+    /// Whether `forceRefresh=true` requires the `X-Admin-Key` header, so anonymous callers
+    /// can't drive provider calls by bypassing the caches.
+    #[serde(default)]
+    pub force_refresh_requires_admin: bool,
+    /// Provider retried when Birdeye fails or returns no data. No fallback when unset.
+    /// Ignored when `PROVIDERS` is set.
+    #[serde(default)]
+    pub fallback_provider: Option<ProviderKind>,
+    /// `sandbox` points providers at their sandbox or testnet endpoints and labels every
+    /// response as such.
+    #[serde(default)]
+    pub provider_environment: ProviderEnvironment,
+    /// Comma-separated providers tried in order, e.g. `birdeye,pyth,coingecko`.
+    #[serde(default)]
+    pub providers: Option<Vec<ProviderKind>>,
+    /// CoinGecko API base URL, used when CoinGecko is the fallback provider.
+    #[serde(default = "default_coingecko_base_url")]
+    pub coingecko_base_url: String,
+    /// CoinGecko API key, sent as `x-cg-pro-api-key` when set.
+    #[serde(default)]
+    pub coingecko_api_key: Option<String>,
+    /// Pyth Benchmarks API base URL, serving historical prices.
+    #[serde(default = "default_pyth_benchmarks_url")]
+    pub pyth_benchmarks_url: String,
+    /// Pyth Hermes API base URL, serving latest prices.
+    #[serde(default = "default_pyth_hermes_url")]
+    pub pyth_hermes_url: String,
+    /// JSON object of token mint to `{ "symbol", "feedId" }`, adding to or replacing the
+    /// built-in Pyth feeds.
+    #[serde(default)]
+    pub pyth_price_feeds: Option<String>,
+    /// Host clock skew against the provider's `Date` header, in seconds, beyond which the
+    /// skew is reported.
+    #[serde(default = "default_clock_skew_threshold_secs")]
+    pub clock_skew_threshold_secs: u64,
+    /// Comma-separated browser origins allowed to call the public endpoints, or `*` for any.
+    #[serde(default = "default_cors_public_origins")]
+    pub cors_public_origins: Vec<String>,
+    /// Comma-separated browser origins allowed to call `/admin` endpoints. None when unset.
+    #[serde(default)]
+    pub cors_admin_origins: Vec<String>,
+    /// Z-score of the short-window volatility at or below which `/volRegime` reports `low`.
+    #[serde(default = "default_vol_regime_low_z")]
+    pub vol_regime_low_z: f64,
+    /// Z-score at or above which `/volRegime` reports `high`.
+    #[serde(default = "default_vol_regime_high_z")]
+    pub vol_regime_high_z: f64,
+    /// Z-score at or above which `/volRegime` reports `extreme`.
+    #[serde(default = "default_vol_regime_extreme_z")]
+    pub vol_regime_extreme_z: f64,
+}
+
+fn default_birdeye_ohlcv_url() -> String {
+    "https://public-api.birdeye.so/defi/ohlcv".to_string()
+}
+
+fn default_birdeye_pair_ohlcv_url() -> String {
+    "https://public-api.birdeye.so/defi/ohlcv/pair".to_string()
+}
+
+fn default_birdeye_price_url() -> String {
+    "https://public-api.birdeye.so/defi/price".to_string()
+}
+
+fn default_birdeye_search_url() -> String {
+    "https://public-api.birdeye.so/defi/v3/search".to_string()
+}
+
+fn default_birdeye_token_metadata_url() -> String {
+    "https://public-api.birdeye.so/defi/v3/token/meta-data/single".to_string()
+}
+
+fn default_annualization_days() -> u32 {
+    DAYS_PER_YEAR as u32
+}
+
+fn default_price_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_price_cache_max_entries() -> usize {
+    5000
+}
+
+fn default_benchmark_token_address() -> String {
+    "So11111111111111111111111111111111111111112".to_string()
+}
+
+fn default_max_matrix_tokens() -> usize {
+    20
+}
+
+fn default_max_date_range_days() -> u32 {
+    730
+}
+
+fn default_coingecko_base_url() -> String {
+    "https://api.coingecko.com/api/v3".to_string()
+}
+
+fn default_pyth_benchmarks_url() -> String {
+    "https://benchmarks.pyth.network".to_string()
+}
+
+fn default_pyth_hermes_url() -> String {
+    "https://hermes.pyth.network".to_string()
+}
+
+/// Hermes endpoint serving Pyth's testnet (beta) price feeds.
+const SANDBOX_PYTH_HERMES_URL: &str = "https://hermes-beta.pyth.network";
+
+fn default_volatility_cache_max_age_secs() -> u64 {
+    900
+}
+
+fn default_volatility_cache_evict_after_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_volatility_cache_max_tokens() -> usize {
+    1000
+}
+
+fn default_refresh_spread_ratio() -> f64 {
+    0.8
+}
+
+fn default_refresh_concurrency() -> usize {
+    4
+}
+
+fn default_hot_token_min_requests() -> f64 {
+    5.0
+}
+
+fn default_hot_token_window_secs() -> u64 {
+    60 * 60
+}
+
+fn default_cold_refresh_interval_secs() -> u64 {
+    600
+}
+
+fn default_token_retention_secs() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+fn default_history_raw_retention_days() -> u32 {
+    7
+}
+
+fn default_history_hourly_retention_days() -> u32 {
+    90
+}
+
+fn default_clock_skew_threshold_secs() -> u64 {
+    30
+}
+
+fn default_cors_public_origins() -> Vec<String> {
+    vec![cors::ANY_ORIGIN.to_string()]
+}
+
+fn default_vol_regime_low_z() -> f64 {
+    -1.0
+}
+
+fn default_vol_regime_high_z() -> f64 {
+    1.0
+}
+
+fn default_vol_regime_extreme_z() -> f64 {
+    2.0
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            birdeye_api_key: String::new(),
+            birdeye_base_url: "https://public-api.birdeye.so/defi/history_price".to_string(),
+            app_server_port: 3000,
+            birdeye_ohlcv_url: default_birdeye_ohlcv_url(),
+            birdeye_pair_ohlcv_url: default_birdeye_pair_ohlcv_url(),
+            birdeye_price_url: default_birdeye_price_url(),
+            birdeye_search_url: default_birdeye_search_url(),
+            birdeye_token_metadata_url: default_birdeye_token_metadata_url(),
+            volatility_estimator: EstimatorKind::default(),
+            annualization_days: default_annualization_days(),
+            price_cache_ttl_secs: default_price_cache_ttl_secs(),
+            price_cache_max_entries: default_price_cache_max_entries(),
+            volatility_cache_max_age_secs: default_volatility_cache_max_age_secs(),
+            volatility_cache_evict_after_secs: default_volatility_cache_evict_after_secs(),
+            volatility_cache_max_tokens: default_volatility_cache_max_tokens(),
+            refresh_spread_ratio: default_refresh_spread_ratio(),
+            refresh_concurrency: default_refresh_concurrency(),
+            hot_token_min_requests: default_hot_token_min_requests(),
+            hot_token_window_secs: default_hot_token_window_secs(),
+            cold_refresh_interval_secs: default_cold_refresh_interval_secs(),
+            token_retention_secs: default_token_retention_secs(),
+            cache_refresh_cron: None,
+            token_refresh_config: None,
+            volatility_cache_db_path: None,
+            redis_url: None,
+            database_url: None,
+            history_raw_retention_days: default_history_raw_retention_days(),
+            history_hourly_retention_days: default_history_hourly_retention_days(),
+            benchmark_token_address: default_benchmark_token_address(),
+            max_matrix_tokens: default_max_matrix_tokens(),
+            max_date_range_days: default_max_date_range_days(),
+            risk_free_rate: 0.0,
+            admin_api_key: None,
+            force_refresh_requires_admin: false,
+            fallback_provider: None,
+            provider_environment: ProviderEnvironment::default(),
+            providers: None,
+            coingecko_base_url: default_coingecko_base_url(),
+            coingecko_api_key: None,
+            pyth_benchmarks_url: default_pyth_benchmarks_url(),
+            pyth_hermes_url: default_pyth_hermes_url(),
+            pyth_price_feeds: None,
+            clock_skew_threshold_secs: default_clock_skew_threshold_secs(),
+            cors_public_origins: default_cors_public_origins(),
+            cors_admin_origins: Vec::new(),
+            vol_regime_low_z: default_vol_regime_low_z(),
+            vol_regime_high_z: default_vol_regime_high_z(),
+            vol_regime_extreme_z: default_vol_regime_extreme_z(),
+        }
+    }
 }
 
 impl AppConfig {
     pub fn from_env() -> Result<Self, envy::Error> {
-        let config = envy::from_env::<AppConfig>()?;
+        let mut config = envy::from_env::<AppConfig>()?;
+        config.apply_provider_environment();
 
         if config.birdeye_api_key.trim().is_empty() {
             return Err(envy::Error::Custom(
@@ -22,6 +362,176 @@ impl AppConfig {
                 "BIRDEYE_BASE_URL cannot be empty.".to_string(),
             ));
         }
+        if config.birdeye_ohlcv_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "BIRDEYE_OHLCV_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config.birdeye_pair_ohlcv_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "BIRDEYE_PAIR_OHLCV_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config.birdeye_price_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "BIRDEYE_PRICE_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config.birdeye_search_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "BIRDEYE_SEARCH_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config.birdeye_token_metadata_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "BIRDEYE_TOKEN_METADATA_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config
+            .admin_api_key
+            .as_ref()
+            .is_some_and(|key| key.trim().is_empty())
+        {
+            return Err(envy::Error::Custom(
+                "ADMIN_API_KEY cannot be empty when set.".to_string(),
+            ));
+        }
+
+        if let Some(providers) = &config.providers {
+            let distinct = providers
+                .iter()
+                .enumerate()
+                .all(|(i, kind)| !providers[..i].contains(kind));
+            if providers.is_empty() || !distinct {
+                return Err(envy::Error::Custom(
+                    "PROVIDERS must list at least one provider, each at most once.".to_string(),
+                ));
+            }
+        }
+
+        if config.coingecko_base_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "COINGECKO_BASE_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if config.pyth_benchmarks_url.trim().is_empty() || config.pyth_hermes_url.trim().is_empty() {
+            return Err(envy::Error::Custom(
+                "PYTH_BENCHMARKS_URL and PYTH_HERMES_URL cannot be empty.".to_string(),
+            ));
+        }
+
+        if let Some(feeds) = &config.pyth_price_feeds {
+            FeedRegistry::with_overrides(feeds).map_err(envy::Error::Custom)?;
+        }
+
+        if let Some(expression) = &config.cache_refresh_cron {
+            cron::Schedule::from_str(expression).map_err(|e| {
+                envy::Error::Custom(format!("CACHE_REFRESH_CRON is not a valid cron expression: {}", e))
+            })?;
+        }
+
+        if let Some(tokens) = &config.token_refresh_config {
+            TokenRegistry::parse(tokens, config.volatility_cache_evict_after_secs)
+                .map_err(envy::Error::Custom)?;
+        }
+
+        if config.annualization_days == 0 {
+            return Err(envy::Error::Custom(
+                "ANNUALIZATION_DAYS cannot be 0.".to_string(),
+            ));
+        }
+
+        if config.max_date_range_days == 0 {
+            return Err(envy::Error::Custom(
+                "MAX_DATE_RANGE_DAYS cannot be 0.".to_string(),
+            ));
+        }
+
+        if config.volatility_cache_max_age_secs == 0
+            || config.volatility_cache_evict_after_secs < config.volatility_cache_max_age_secs
+        {
+            return Err(envy::Error::Custom(
+                "VOLATILITY_CACHE_MAX_AGE_SECS must be positive and at most VOLATILITY_CACHE_EVICT_AFTER_SECS.".to_string(),
+            ));
+        }
+
+        if config.volatility_cache_max_tokens == 0 {
+            return Err(envy::Error::Custom(
+                "VOLATILITY_CACHE_MAX_TOKENS cannot be 0.".to_string(),
+            ));
+        }
+
+        if !(0.0..1.0).contains(&config.refresh_spread_ratio) {
+            return Err(envy::Error::Custom(
+                "REFRESH_SPREAD_RATIO must be at least 0 and below 1.".to_string(),
+            ));
+        }
+
+        if config.refresh_concurrency == 0 {
+            return Err(envy::Error::Custom(
+                "REFRESH_CONCURRENCY cannot be 0.".to_string(),
+            ));
+        }
+
+        if !(config.hot_token_min_requests.is_finite() && config.hot_token_min_requests >= 0.0) {
+            return Err(envy::Error::Custom(
+                "HOT_TOKEN_MIN_REQUESTS must be a non-negative number.".to_string(),
+            ));
+        }
+
+        if config.hot_token_window_secs == 0 {
+            return Err(envy::Error::Custom(
+                "HOT_TOKEN_WINDOW_SECS cannot be 0.".to_string(),
+            ));
+        }
+
+        // Cold tokens must be refreshed before their values go stale
+        if config.cold_refresh_interval_secs >= config.volatility_cache_max_age_secs {
+            return Err(envy::Error::Custom(
+                "COLD_REFRESH_INTERVAL_SECS must be below VOLATILITY_CACHE_MAX_AGE_SECS.".to_string(),
+            ));
+        }
+
+        if config.token_retention_secs == 0 {
+            return Err(envy::Error::Custom(
+                "TOKEN_RETENTION_SECS cannot be 0.".to_string(),
+            ));
+        }
+
+        if config.history_hourly_retention_days < config.history_raw_retention_days {
+            return Err(envy::Error::Custom(
+                "HISTORY_HOURLY_RETENTION_DAYS cannot be below HISTORY_RAW_RETENTION_DAYS.".to_string(),
+            ));
+        }
+
+        if !config.risk_free_rate.is_finite() {
+            return Err(envy::Error::Custom(
+                "RISK_FREE_RATE must be a finite number.".to_string(),
+            ));
+        }
+
+        cors::validate_origins(&config.cors_public_origins)
+            .map_err(|msg| envy::Error::Custom(format!("CORS_PUBLIC_ORIGINS: {}", msg)))?;
+        cors::validate_origins(&config.cors_admin_origins)
+            .map_err(|msg| envy::Error::Custom(format!("CORS_ADMIN_ORIGINS: {}", msg)))?;
+
+        let thresholds = config.vol_regime_thresholds();
+        if !(thresholds.low.is_finite()
+            && thresholds.extreme.is_finite()
+            && thresholds.low < thresholds.high
+            && thresholds.high < thresholds.extreme)
+        {
+            return Err(envy::Error::Custom(
+                "VOL_REGIME_LOW_Z, VOL_REGIME_HIGH_Z and VOL_REGIME_EXTREME_Z must be finite and increasing.".to_string(),
+            ));
+        }
+
         if config.app_server_port == 0 {
             return Err(envy::Error::Custom(
                 "APP_SERVER_PORT cannot be 0.".to_string(),
@@ -30,4 +540,47 @@ impl AppConfig {
 
         Ok(config)
     }
+
+    /// Z-score cut-offs `/volRegime` classifies with.
+    pub fn vol_regime_thresholds(&self) -> RegimeThresholds {
+        RegimeThresholds {
+            low: self.vol_regime_low_z,
+            high: self.vol_regime_high_z,
+            extreme: self.vol_regime_extreme_z,
+        }
+    }
+
+    /// Swaps production provider endpoints left at their defaults for the sandbox ones when
+    /// `PROVIDER_ENVIRONMENT=sandbox`. Endpoints set explicitly are kept.
+    ///
+    /// Only Pyth publishes a testnet endpoint; Birdeye and CoinGecko have no public sandbox,
+    /// so their URLs must be pointed at one (or a mock) in configuration.
+    pub fn apply_provider_environment(&mut self) {
+        if self.provider_environment == ProviderEnvironment::Sandbox
+            && self.pyth_hermes_url == default_pyth_hermes_url()
+        {
+            self.pyth_hermes_url = SANDBOX_PYTH_HERMES_URL.to_string();
+        }
+    }
+
+    /// Providers in the order they are tried.
+    pub fn provider_order(&self) -> Vec<ProviderKind> {
+        if let Some(providers) = &self.providers {
+            return providers.clone();
+        }
+        match self.fallback_provider {
+            Some(kind) if kind != ProviderKind::Birdeye => vec![ProviderKind::Birdeye, kind],
+            _ => vec![ProviderKind::Birdeye],
+        }
+    }
+
+    /// Configured defaults that the methodology version is derived from.
+    pub fn methodology(&self) -> Methodology {
+        Methodology {
+            volatility_estimator: self.volatility_estimator,
+            annualization_days: self.annualization_days,
+            risk_free_rate: self.risk_free_rate,
+            vol_regime_thresholds: self.vol_regime_thresholds(),
+        }
+    }
 }
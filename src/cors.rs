@@ -0,0 +1,117 @@
+//! Cross-origin access per route group. Public read endpoints are open to the browser
+//! origins configured in `CORS_PUBLIC_ORIGINS`; `/admin` endpoints only to those in
+//! `CORS_ADMIN_ORIGINS`, none by default, so a page on another origin can never drive them
+//! from an operator's browser.
+
+#[cfg(feature = "admin")]
+use crate::extractors::admin_auth::ADMIN_KEY_HEADER;
+use axum::http::{header::CONTENT_TYPE, HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Origin entry allowing every origin.
+pub const ANY_ORIGIN: &str = "*";
+
+/// Routes sharing a cross-origin policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// Read-only endpoints, including the `POST` ones that only compute.
+    Public,
+    /// Endpoints changing the service's state, guarded by the admin key.
+    #[cfg(feature = "admin")]
+    Admin,
+}
+
+impl RouteGroup {
+    fn methods(self) -> Vec<Method> {
+        match self {
+            RouteGroup::Public => vec![Method::GET, Method::POST],
+            #[cfg(feature = "admin")]
+            RouteGroup::Admin => vec![Method::GET, Method::POST, Method::PUT, Method::DELETE],
+        }
+    }
+
+    fn headers(self) -> Vec<HeaderName> {
+        match self {
+            RouteGroup::Public => vec![CONTENT_TYPE],
+            #[cfg(feature = "admin")]
+            RouteGroup::Admin => vec![CONTENT_TYPE, HeaderName::from_static(ADMIN_KEY_HEADER)],
+        }
+    }
+}
+
+/// Checks a list of allowed origins: either `*` alone, or `http(s)://` origins.
+///
+/// # Errors
+/// - A message naming the first invalid entry.
+pub fn validate_origins(origins: &[String]) -> Result<(), String> {
+    let origins: Vec<&str> = non_blank(origins).collect();
+    if origins.contains(&ANY_ORIGIN) {
+        if origins.len() > 1 {
+            return Err("'*' cannot be combined with other origins.".to_string());
+        }
+        return Ok(());
+    }
+    for origin in origins {
+        let valid = (origin.starts_with("http://") || origin.starts_with("https://"))
+            && !origin.ends_with('/')
+            && HeaderValue::from_str(origin).is_ok();
+        if !valid {
+            return Err(format!(
+                "'{}' is not an origin like https://app.example.com.",
+                origin
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// CORS layer for `group` allowing `origins`, or `None` when no origin is allowed and
+/// responses should carry no CORS headers at all.
+///
+/// Credentials are never allowed: the API is keyed by header, not cookies.
+pub fn layer(group: RouteGroup, origins: &[String]) -> Option<CorsLayer> {
+    let origins: Vec<&str> = non_blank(origins).collect();
+    let allow_origin = if origins.is_empty() {
+        return None;
+    } else if origins.contains(&ANY_ORIGIN) {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            origins
+                .into_iter()
+                .filter_map(|origin| HeaderValue::from_str(origin).ok()),
+        )
+    };
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(group.methods())
+            .allow_headers(group.headers())
+            // Lets browsers read the provider, methodology and deprecation headers
+            .expose_headers(Any),
+    )
+}
+
+fn non_blank(origins: &[String]) -> impl Iterator<Item = &str> {
+    origins
+        .iter()
+        .map(|origin| origin.trim())
+        .filter(|origin| !origin.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_origins() {
+        let origins = |list: &[&str]| list.iter().map(|o| o.to_string()).collect::<Vec<_>>();
+        assert!(validate_origins(&origins(&["*"])).is_ok());
+        assert!(validate_origins(&origins(&[])).is_ok());
+        assert!(validate_origins(&origins(&["https://app.example.com", "http://localhost:5173"])).is_ok());
+        assert!(validate_origins(&origins(&["*", "https://app.example.com"])).is_err());
+        assert!(validate_origins(&origins(&["app.example.com"])).is_err());
+        assert!(validate_origins(&origins(&["https://app.example.com/"])).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Deprecated routes and query parameters: responses using them carry `Deprecation` and
+//! `Sunset` headers, and their use is counted so the report at `/deprecations` shows when
+//! nobody relies on them anymore.
+
+use crate::state::AppState;
+use crate::utils::timestamp_format;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Response header announcing when the used feature was deprecated (RFC 9745).
+pub const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Response header announcing when the used feature stops working (RFC 8594).
+pub const SUNSET_HEADER: &str = "sunset";
+
+/// A deprecated route, or a deprecated query parameter of a route.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Deprecation {
+    /// Route as registered in the router, e.g. `/token/{address}`.
+    pub route: String,
+    /// Deprecated query parameter; `None` deprecates the whole route.
+    pub parameter: Option<String>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub deprecated_at: DateTime<Utc>,
+    /// When the feature is removed, if decided.
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub sunset_at: Option<DateTime<Utc>>,
+    /// Documentation of what to use instead, linked from responses with `rel="deprecation"`.
+    pub link: Option<String>,
+}
+
+impl Deprecation {
+    /// Identifier of the feature in the usage report: the route, with `?parameter` when
+    /// only a parameter is deprecated.
+    pub fn feature(&self) -> String {
+        match &self.parameter {
+            Some(parameter) => format!("{}?{}", self.route, parameter),
+            None => self.route.clone(),
+        }
+    }
+
+    fn applies_to(&self, route: &str, parameters: &[String]) -> bool {
+        self.route == route
+            && self
+                .parameter
+                .as_ref()
+                .is_none_or(|parameter| parameters.contains(parameter))
+    }
+}
+
+/// Deprecations in effect. Add an entry here when deprecating a route or parameter, and
+/// remove it together with the feature once the report shows no more use.
+pub fn builtin() -> Vec<Deprecation> {
+    Vec::new()
+}
+
+/// How often a deprecated feature was used since startup.
+#[derive(Debug, Clone, Copy, Default)]
+struct Usage {
+    count: u64,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+/// A deprecated feature and its use, as listed by `/deprecations`.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecationUsage {
+    pub feature: String,
+    #[serde(flatten)]
+    pub deprecation: Deprecation,
+    /// Requests that used the feature since startup.
+    pub uses: u64,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Deprecations in effect and the use of each since startup.
+#[derive(Clone)]
+pub struct DeprecationRegistry {
+    deprecations: Arc<Vec<Deprecation>>,
+    usage: Arc<RwLock<HashMap<String, Usage>>>,
+}
+
+impl Default for DeprecationRegistry {
+    fn default() -> Self {
+        Self::new(builtin())
+    }
+}
+
+impl DeprecationRegistry {
+    /// Create a registry of `deprecations` with no recorded use.
+    pub fn new(deprecations: Vec<Deprecation>) -> Self {
+        Self {
+            deprecations: Arc::new(deprecations),
+            usage: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Deprecations a request to `route` with the given query parameters runs into.
+    pub fn matching(&self, route: &str, parameters: &[String]) -> Vec<&Deprecation> {
+        self.deprecations
+            .iter()
+            .filter(|deprecation| deprecation.applies_to(route, parameters))
+            .collect()
+    }
+
+    /// Count one use of each of `deprecations`.
+    pub async fn record(&self, deprecations: &[&Deprecation]) {
+        let now = Utc::now();
+        let mut usage = self.usage.write().await;
+        for deprecation in deprecations {
+            let entry = usage.entry(deprecation.feature()).or_default();
+            entry.count += 1;
+            entry.last_used_at = Some(now);
+        }
+    }
+
+    /// Every deprecation with its use, soonest sunset first.
+    pub async fn report(&self) -> Vec<DeprecationUsage> {
+        let usage = self.usage.read().await;
+        let mut report: Vec<DeprecationUsage> = self
+            .deprecations
+            .iter()
+            .map(|deprecation| {
+                let feature = deprecation.feature();
+                let used = usage.get(&feature).copied().unwrap_or_default();
+                DeprecationUsage {
+                    feature,
+                    deprecation: deprecation.clone(),
+                    uses: used.count,
+                    last_used_at: used.last_used_at,
+                }
+            })
+            .collect();
+        // Features without a sunset date sort last
+        report.sort_by_key(|entry| (entry.deprecation.sunset_at.is_none(), entry.deprecation.sunset_at));
+        report
+    }
+}
+
+/// Middleware adding `Deprecation`, `Sunset` and `Link` headers to responses of requests
+/// using deprecated routes or parameters, and counting that use.
+///
+/// When a request runs into several deprecations, the headers report the earliest dates.
+pub async fn mark_deprecations(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let parameters: Vec<String> = request
+        .uri()
+        .query()
+        .and_then(|query| serde_urlencoded::from_str::<Vec<(String, String)>>(query).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    let registry = &state.deprecations;
+    let deprecations = registry.matching(&route, &parameters);
+    if deprecations.is_empty() {
+        return next.run(request).await;
+    }
+    registry.record(&deprecations).await;
+
+    let deprecated_at = deprecations.iter().map(|d| d.deprecated_at).min();
+    let sunset_at = deprecations.iter().filter_map(|d| d.sunset_at).min();
+    let links: Vec<String> = deprecations
+        .iter()
+        .filter_map(|d| d.link.as_ref())
+        .map(|link| format!("<{}>; rel=\"deprecation\"", link))
+        .collect();
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    if let Some(value) = deprecated_at.and_then(|at| HeaderValue::from_str(&format!("@{}", at.timestamp())).ok()) {
+        headers.insert(DEPRECATION_HEADER, value);
+    }
+    if let Some(value) = sunset_at.and_then(|at| HeaderValue::from_str(&http_date(at)).ok()) {
+        headers.insert(SUNSET_HEADER, value);
+    }
+    for link in links {
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            headers.append(axum::http::header::LINK, value);
+        }
+    }
+    response
+}
+
+/// Formats a time as an HTTP-date, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn deprecation(parameter: Option<&str>, sunset_at: Option<DateTime<Utc>>) -> Deprecation {
+        Deprecation {
+            route: "/prices".to_string(),
+            parameter: parameter.map(str::to_string),
+            deprecated_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            sunset_at,
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_parameter_deprecations_apply_only_when_given() {
+        let registry = DeprecationRegistry::new(vec![deprecation(Some("interval"), None)]);
+        assert!(registry.matching("/prices", &[]).is_empty());
+        assert!(registry.matching("/returns", &["interval".to_string()]).is_empty());
+        assert_eq!(registry.matching("/prices", &["interval".to_string()]).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_report_counts_use_and_orders_by_sunset() {
+        let sunset = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let registry = DeprecationRegistry::new(vec![
+            deprecation(Some("interval"), None),
+            deprecation(None, Some(sunset)),
+        ]);
+        let used = registry.matching("/prices", &["interval".to_string()]);
+        registry.record(&used).await;
+
+        let report = registry.report().await;
+        assert_eq!(report[0].feature, "/prices");
+        assert_eq!(report[0].uses, 1);
+        assert_eq!(report[1].feature, "/prices?interval");
+        assert_eq!(report[1].uses, 1);
+        assert_eq!(http_date(sunset), "Thu, 01 Jan 2026 00:00:00 GMT");
+    }
+}
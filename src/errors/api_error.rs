@@ -12,6 +12,13 @@ pub enum ApiError {
     InternalServerError,
     NotEnoughData,
     InvalidQuery(String),
+    InvalidBody(String),
+    Unauthorized(String),
+    NotFound(String),
+    FromDateAfterToDate,
+    ToDateInFuture,
+    DateRangeTooLong(u32),
+    DeadlineExceeded,
 }
 
 #[derive(Serialize)]
@@ -34,6 +41,29 @@ impl IntoResponse for ApiError {
                 "Not enough price points to calculate volatility".to_owned(),
             ),
             ApiError::InvalidQuery(msg) => (StatusCode::BAD_REQUEST, "Bad Request", msg.clone()),
+            ApiError::InvalidBody(msg) => (StatusCode::BAD_REQUEST, "Bad Request", msg.clone()),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "Unauthorized", msg.clone()),
+            ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, "Not Found", msg.clone()),
+            ApiError::FromDateAfterToDate => (
+                StatusCode::BAD_REQUEST,
+                "Bad Request",
+                "fromDate cannot be after toDate.".to_owned(),
+            ),
+            ApiError::ToDateInFuture => (
+                StatusCode::BAD_REQUEST,
+                "Bad Request",
+                "toDate cannot be in the future.".to_owned(),
+            ),
+            ApiError::DateRangeTooLong(max_days) => (
+                StatusCode::BAD_REQUEST,
+                "Bad Request",
+                format!("Date range cannot exceed {} days.", max_days),
+            ),
+            ApiError::DeadlineExceeded => (
+                StatusCode::GATEWAY_TIMEOUT,
+                "Gateway Timeout",
+                "Request deadline exceeded.".to_owned(),
+            ),
         };
 
         let body = ApiErrorResponse { error, message };
@@ -62,6 +92,15 @@ impl fmt::Display for ApiError {
             ApiError::InternalServerError => write!(f, "Internal server error"),
             ApiError::NotEnoughData => write!(f, "Not enough data"),
             ApiError::InvalidQuery(msg) => write!(f, "Invalid query: {}", msg),
+            ApiError::InvalidBody(msg) => write!(f, "Invalid body: {}", msg),
+            ApiError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
+            ApiError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            ApiError::FromDateAfterToDate => write!(f, "fromDate cannot be after toDate."),
+            ApiError::ToDateInFuture => write!(f, "toDate cannot be in the future."),
+            ApiError::DateRangeTooLong(max_days) => {
+                write!(f, "Date range cannot exceed {} days.", max_days)
+            }
+            ApiError::DeadlineExceeded => write!(f, "Deadline exceeded"),
         }
     }
 }
@@ -0,0 +1,43 @@
+use crate::{errors::api_error::ApiError, state::AppState};
+use axum::{extract::FromRequestParts, http::request::Parts};
+use tracing::warn;
+
+/// Header carrying the admin key.
+pub const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+/// Guard for `/admin` handlers: succeeds only when the `X-Admin-Key` header matches the
+/// configured `ADMIN_API_KEY`. Admin endpoints are disabled when no key is configured.
+#[derive(Debug)]
+pub struct AdminAuth;
+
+impl FromRequestParts<AppState> for AdminAuth {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let Some(expected) = state.config.admin_api_key.as_deref() else {
+            return Err(ApiError::Unauthorized(
+                "Admin endpoints are disabled.".to_string(),
+            ));
+        };
+
+        let provided = parts
+            .headers
+            .get(ADMIN_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+            warn!(path = %parts.uri.path(), "Rejected admin request with missing or invalid key");
+            return Err(ApiError::Unauthorized(
+                "Missing or invalid admin key.".to_string(),
+            ));
+        }
+
+        Ok(AdminAuth)
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,23 @@
+use crate::errors::api_error::ApiError;
+use axum::extract::{FromRequest, Json, Request};
+use serde::de::DeserializeOwned;
+
+/// JSON body extractor that reports rejections as `ApiError::InvalidBody`,
+/// so malformed bodies get the same JSON error shape as malformed queries.
+#[derive(Debug)]
+pub struct ApiJson<T>(pub T);
+
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(body)) => Ok(ApiJson(body)),
+            Err(err) => Err(ApiError::InvalidBody(err.body_text())),
+        }
+    }
+}
@@ -1 +1,4 @@
-pub mod query_extractor;
\ No newline at end of file
+pub mod query_extractor;
+pub mod json_extractor;
+#[cfg(feature = "admin")]
+pub mod admin_auth;
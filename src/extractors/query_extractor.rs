@@ -1,41 +1,192 @@
-use crate::{errors::api_error::ApiError, utils::custom_date_serde};
+use crate::presets::{Preset, PresetStore};
+use crate::state::AppState;
+use crate::utils::base58;
+use crate::utils::chain::{self, Chain};
+use crate::utils::date_window::{default_range, validate_range};
+use crate::{errors::api_error::ApiError, math::{EstimatorKind, Interval}, utils::custom_date_serde};
 use axum::{
     extract::{FromRequestParts, Query},
-    http::request::Parts,
-    RequestPartsExt,
+    http::{request::Parts, Uri},
 };
-use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use chrono::{DateTime, Duration, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tracing::info;
 
-/// Query parameters for the volatility request.
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+/// How the volatility request's date range was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateRange {
+    /// Neither dates nor `days`: the default 90 days ending yesterday.
+    Default,
+    /// A trailing window of this many days ending now, from `days` or the preset.
+    Trailing(u32),
+    /// Explicit `fromDate` and `toDate`.
+    Explicit,
+}
+
+/// Query parameters for the volatility request, with any `preset` applied.
+#[derive(Debug)]
 pub struct HistoricalVolatilityQuery {
-    #[serde(with = "custom_date_serde")]
     pub from_date: DateTime<Utc>,
-    #[serde(with = "custom_date_serde")]
     pub to_date: DateTime<Utc>,
+    /// How `from_date` and `to_date` were given, deciding whether the volatility cache answers.
+    pub range: DateRange,
+    pub token_address: String,
+    /// Name of the preset the parameters were resolved with, if any.
+    pub preset: Option<String>,
+    /// Volatility estimator to use. Defaults to close-to-close.
+    pub estimator: EstimatorKind,
+    /// Candle size of the price series. Defaults to daily candles.
+    pub interval: Interval,
+    /// Overrides the configured `ANNUALIZATION_DAYS`, e.g. 252 to compare against TradFi assets.
+    pub annualization_days: Option<u32>,
+    /// Adds mean, skewness, excess kurtosis and min/max of the returns to the response.
+    pub extended_stats: bool,
+    /// Adds the price series the volatility was computed from to the response.
+    pub include_prices: bool,
+    /// Refetches the prices and recomputes the volatility even when cached values exist.
+    pub force_refresh: bool,
+}
+
+/// Raw query string of the volatility request, before its `preset` is applied.
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalVolatilityParams {
+    #[serde(
+        default,
+        deserialize_with = "custom_date_serde::deserialize_option",
+        serialize_with = "custom_date_serde::serialize_option"
+    )]
+    pub from_date: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        deserialize_with = "custom_date_serde::deserialize_option",
+        serialize_with = "custom_date_serde::serialize_option"
+    )]
+    pub to_date: Option<DateTime<Utc>>,
+    /// Trailing window in days ending now, instead of `fromDate`/`toDate`.
+    pub days: Option<u32>,
     pub token_address: String,
+    pub preset: Option<String>,
+    pub estimator: Option<EstimatorKind>,
+    pub interval: Option<Interval>,
+    pub annualization_days: Option<u32>,
+    pub extended_stats: Option<bool>,
+    pub include_prices: Option<bool>,
+    pub force_refresh: Option<bool>,
+}
+
+impl HistoricalVolatilityQuery {
+    /// Parses the volatility request's query string and fills unset parameters from the
+    /// named preset, returning a message describing the first invalid parameter.
+    ///
+    /// A `days` look-back is capped at `max_days`; explicit ranges are left to
+    /// [`validate_range`].
+    pub async fn from_uri(uri: &Uri, presets: &PresetStore, max_days: u32) -> Result<Self, String> {
+        let params = Query::<HistoricalVolatilityParams>::try_from_uri(uri)
+            .map_err(|err| err.body_text())?
+            .0;
+
+        // Catch malformed addresses here rather than forwarding them to the provider
+        match chain::current() {
+            Chain::Solana => base58::validate_pubkey(&params.token_address)?,
+            chain => chain.validate_address(&params.token_address)?,
+        }
+
+        let preset = match &params.preset {
+            Some(name) => presets
+                .get(name)
+                .await
+                .ok_or_else(|| format!("Unknown preset '{}'.", name))?,
+            None => Preset::default(),
+        };
+
+        if params.days.is_some() && (params.from_date.is_some() || params.to_date.is_some()) {
+            return Err("Pass either days or fromDate and toDate, not both.".to_string());
+        }
+        if params.days == Some(0) {
+            return Err("days must be greater than 0.".to_string());
+        }
+
+        let (from_date, to_date, range) = match (params.from_date, params.to_date, params.days.or(preset.days)) {
+            (Some(from_date), Some(to_date), _) => (from_date, to_date, DateRange::Explicit),
+            (None, None, Some(days)) => {
+                if days > max_days {
+                    return Err(format!("Date range cannot exceed {} days.", max_days));
+                }
+                let to_date = Utc::now();
+                let from_date = to_date
+                    .checked_sub_signed(Duration::days(days as i64))
+                    .ok_or_else(|| format!("days {} is out of range.", days))?;
+                (from_date, to_date, DateRange::Trailing(days))
+            }
+            (None, None, None) => {
+                let (from_date, to_date) = default_range();
+                (from_date, to_date, DateRange::Default)
+            }
+            _ => return Err("fromDate and toDate must be given together.".to_string()),
+        };
+
+        let annualization_days = params.annualization_days.or(preset.annualization_days);
+        if annualization_days == Some(0) {
+            return Err("annualizationDays must be greater than 0.".to_string());
+        }
+
+        Ok(HistoricalVolatilityQuery {
+            from_date,
+            to_date,
+            range,
+            token_address: params.token_address,
+            preset: params.preset,
+            estimator: params.estimator.or(preset.estimator).unwrap_or_default(),
+            interval: params.interval.or(preset.interval).unwrap_or_default(),
+            annualization_days,
+            extended_stats: params
+                .extended_stats
+                .or(preset.extended_stats)
+                .unwrap_or_default(),
+            include_prices: params.include_prices.unwrap_or_default(),
+            force_refresh: params.force_refresh.unwrap_or_default(),
+        })
+    }
 }
 
-impl<S> FromRequestParts<S> for HistoricalVolatilityQuery
+impl FromRequestParts<AppState> for HistoricalVolatilityQuery {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let query = HistoricalVolatilityQuery::from_uri(&parts.uri, &state.presets, state.config.max_date_range_days)
+            .await
+            .map_err(ApiError::InvalidQuery)?;
+        validate_range(query.from_date, query.to_date, state.config.max_date_range_days)?;
+
+        info!(
+            from_date = %query.from_date,
+            to_date = %query.to_date,
+            token_address = %query.token_address,
+            preset = ?query.preset,
+            estimator = %query.estimator,
+            interval = %query.interval,
+            "Extracted HistoricalVolatilityQuery successfully."
+        );
+        Ok(query)
+    }
+}
+
+/// Generic query extractor that reports deserialization failures as `ApiError::InvalidQuery`,
+/// so every endpoint returns the same JSON error body for malformed query strings.
+#[derive(Debug)]
+pub struct ApiQuery<T>(pub T);
+
+impl<S, T> FromRequestParts<S> for ApiQuery<T>
 where
     S: Send + Sync,
+    T: DeserializeOwned,
 {
     type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        match parts.extract::<Query<HistoricalVolatilityQuery>>().await {
-            Ok(Query(query)) => {
-                info!(
-                    from_date = %query.from_date,
-                    to_date = %query.to_date,
-                    token_address = %query.token_address,
-                    "Extracted HistoricalVolatilityQuery successfully."
-                );
-                Ok(query)
-            }
+        match Query::<T>::try_from_uri(&parts.uri) {
+            Ok(Query(query)) => Ok(ApiQuery(query)),
             Err(err) => Err(ApiError::InvalidQuery(err.body_text())),
         }
     }
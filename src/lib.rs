@@ -4,4 +4,14 @@ pub mod utils;
 pub mod config;
 pub mod extractors;
 pub mod background;
-pub mod state;
\ No newline at end of file
+pub mod math;
+pub mod state;
+pub mod risk;
+pub mod provider;
+pub mod presets;
+pub mod deprecations;
+pub mod symbols;
+pub mod cors;
+pub mod self_test;
+#[cfg(feature = "client")]
+pub mod client;
@@ -1,10 +1,16 @@
 use dotenvy::dotenv;
 use historical_volatility_api::{
-    background::volatility_cache::VolatilityCache,
+    background::{
+        clock_skew::start_clock_skew_check,
+        volatility_cache::VolatilityCache,
+        volatility_history::{start_compaction, RetentionPolicy},
+    },
     config::AppConfig, 
     routes::register_routes,
+    self_test::{self, SELF_TEST_FLAG},
     state::AppState,
 };
+use tokio_util::sync::CancellationToken;
 
 // TODO (Pen):
 // - More documentation + tests, make sure everything is correct + how to actually use the API
@@ -15,11 +21,24 @@ async fn main() {
     tracing_subscriber::fmt().init();
 
     let config = AppConfig::from_env().expect("Should have loaded config.");
+
+    // Exercise every route against a mock provider and exit, for post-deploy checks
+    if std::env::args().any(|arg| arg == SELF_TEST_FLAG) {
+        let failures = self_test::run(config).await;
+        std::process::exit(if failures == 0 { 0 } else { 1 });
+    }
     
     // Initialize the volatility cache
     let volatility_cache = VolatilityCache::new(config.clone());
+
+    // Resume from the tokens persisted before the last shutdown, if any
+    let restored = volatility_cache.restore().await;
+    if restored > 0 {
+        tracing::info!("Restored {} tokens to the volatility cache", restored);
+    }
     
-    // Add SOL token to cache on startup
+    // Add SOL token to cache on startup, keeping it whether or not it is requested
+    volatility_cache.pin_token("So11111111111111111111111111111111111111112").await;
     match volatility_cache.add_token("So11111111111111111111111111111111111111112".to_string()).await {
         Ok(_) => tracing::info!("Added SOL token to volatility cache"),
         Err(e) => tracing::error!("Failed to add SOL token to cache: {}", e),
@@ -27,15 +46,27 @@ async fn main() {
     
     // Optionally add more tokens here
     // Example: USDC token
+    volatility_cache.pin_token("LABSh5DTebUcUbEoLzXKCiXFJLecDFiDWiBGUU1GpxR").await;
     match volatility_cache.add_token("LABSh5DTebUcUbEoLzXKCiXFJLecDFiDWiBGUU1GpxR".to_string()).await {
         Ok(_) => tracing::info!("Added USDC token to volatility cache"),
         Err(e) => tracing::error!("Failed to add LABS token to cache: {}", e),
     }
     
-    // Start the background task that updates volatility data every 60 seconds
-    volatility_cache.start_background_task().await;
+    // Start the background task that updates volatility data every 60 seconds, until shutdown
+    let shutdown = CancellationToken::new();
+    let refresh_task = volatility_cache.start_background_task(shutdown.clone()).await;
 
-    let state = AppState::new(config, volatility_cache);
+    // Compare the host clock against the provider's, at startup and periodically
+    start_clock_skew_check(
+        volatility_cache.provider().history_endpoint(false),
+        volatility_cache.monitor().clone(),
+        config.clock_skew_threshold_secs,
+    );
+
+    // Thin out old volatility history points, hourly
+    start_compaction(volatility_cache.history().clone(), RetentionPolicy::from_config(&config));
+
+    let state = AppState::new(config, volatility_cache.clone());
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", state.config.app_server_port))
         .await
@@ -45,5 +76,41 @@ async fn main() {
     tracing::info!("Listening on {}", addr);
 
     let app = register_routes(state);
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal(shutdown))
+        .await
+        .unwrap();
+
+    // In-flight requests have finished; wait for the refresh task before flushing the cache
+    // to the persistence store it is restored from at startup
+    refresh_task.await.ok();
+    volatility_cache.persist().await;
+    tracing::info!("Shut down");
+}
+
+/// Wait for Ctrl-C or, on Unix, SIGTERM, then cancel `shutdown` so the background task
+/// stops while the server finishes in-flight requests
+async fn shutdown_signal(shutdown: CancellationToken) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Should have installed the Ctrl-C handler.");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Should have installed the SIGTERM handler.")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+    tracing::info!("Shutdown signal received, finishing in-flight requests");
+    shutdown.cancel();
 }
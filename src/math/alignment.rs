@@ -0,0 +1,93 @@
+//! Aligns several price series on common timestamps.
+//!
+//! Providers occasionally skip candles (no trades, outages), and two tokens rarely have
+//! identical gaps. Returns must therefore be computed on the intersection of timestamps,
+//! otherwise a return spanning a gap on one side gets paired with a one-candle return
+//! on the other.
+
+use super::{sorted_valid_candles, Candle};
+use std::collections::{BTreeMap, HashMap};
+
+/// Closing prices of several series sampled on the same timestamps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlignedSeries {
+    pub timestamps: Vec<i64>,
+    /// One column of closes per input series, each `timestamps.len()` long.
+    pub closes: Vec<Vec<f64>>,
+}
+
+impl AlignedSeries {
+    /// Log returns of each series between consecutive aligned timestamps.
+    pub fn log_returns(&self) -> Vec<Vec<f64>> {
+        self.closes
+            .iter()
+            .map(|closes| {
+                closes
+                    .windows(2)
+                    .map(|window| (window[1] / window[0]).ln())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Keeps only the timestamps present in every series.
+///
+/// Timestamps are floored to `bucket_secs` first, so candles stamped a few seconds apart
+/// still line up. If a series has several candles in one bucket the last one wins.
+pub fn align_closes(series: &[Vec<Candle>], bucket_secs: i64) -> AlignedSeries {
+    let bucketed: Vec<HashMap<i64, f64>> = series
+        .iter()
+        .map(|candles| {
+            sorted_valid_candles(candles)
+                .into_iter()
+                .map(|candle| {
+                    let bucket = candle.unix_time - candle.unix_time.rem_euclid(bucket_secs);
+                    (bucket, candle.close)
+                })
+                .collect()
+        })
+        .collect();
+
+    let Some((first, rest)) = bucketed.split_first() else {
+        return AlignedSeries {
+            timestamps: Vec::new(),
+            closes: Vec::new(),
+        };
+    };
+
+    let common: BTreeMap<i64, ()> = first
+        .keys()
+        .filter(|timestamp| rest.iter().all(|other| other.contains_key(timestamp)))
+        .map(|timestamp| (*timestamp, ()))
+        .collect();
+    let timestamps: Vec<i64> = common.into_keys().collect();
+
+    let closes = bucketed
+        .iter()
+        .map(|closes| timestamps.iter().map(|timestamp| closes[timestamp]).collect())
+        .collect();
+
+    AlignedSeries { timestamps, closes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_closes_drops_timestamps_missing_on_either_side() {
+        let a = vec![
+            Candle::from_price(0, 1.0),
+            Candle::from_price(100, 2.0),
+            Candle::from_price(200, 3.0),
+        ];
+        // Missing the 100 candle, and stamped a few seconds late
+        let b = vec![Candle::from_price(5, 10.0), Candle::from_price(203, 30.0)];
+
+        let aligned = align_closes(&[a, b], 100);
+        assert_eq!(aligned.timestamps, vec![0, 200]);
+        assert_eq!(aligned.closes, vec![vec![1.0, 3.0], vec![10.0, 30.0]]);
+        assert_eq!(aligned.log_returns()[0], vec![3.0_f64.ln()]);
+    }
+}
@@ -0,0 +1,99 @@
+//! Black-Scholes pricing of European options.
+
+use super::normal::{cdf as normal_cdf, pdf as normal_pdf};
+use serde::{Deserialize, Serialize};
+
+/// Right conveyed by the option.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// Theoretical value and first-order sensitivities of a European option.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionQuote {
+    pub price: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    /// Price change per 1 percentage point of volatility.
+    pub vega: f64,
+    /// Price change per calendar day.
+    pub theta: f64,
+}
+
+/// Prices a European option.
+///
+/// `volatility` and `rate` are annualized decimals (0.8 for 80%), `years` is the time to
+/// expiry. Returns `None` for non-positive spot, strike, volatility or time to expiry.
+pub fn price_european(
+    option_type: OptionType,
+    spot: f64,
+    strike: f64,
+    years: f64,
+    volatility: f64,
+    rate: f64,
+) -> Option<OptionQuote> {
+    if spot <= 0.0 || strike <= 0.0 || years <= 0.0 || volatility <= 0.0 {
+        return None;
+    }
+
+    let sqrt_t = years.sqrt();
+    let d1 = ((spot / strike).ln() + (rate + 0.5 * volatility * volatility) * years) / (volatility * sqrt_t);
+    let d2 = d1 - volatility * sqrt_t;
+    let discount = (-rate * years).exp();
+    let density = normal_pdf(d1);
+
+    let (price, delta, rate_theta) = match option_type {
+        OptionType::Call => (
+            spot * normal_cdf(d1) - strike * discount * normal_cdf(d2),
+            normal_cdf(d1),
+            -rate * strike * discount * normal_cdf(d2),
+        ),
+        OptionType::Put => (
+            strike * discount * normal_cdf(-d2) - spot * normal_cdf(-d1),
+            normal_cdf(d1) - 1.0,
+            rate * strike * discount * normal_cdf(-d2),
+        ),
+    };
+    let annual_theta = -spot * density * volatility / (2.0 * sqrt_t) + rate_theta;
+
+    Some(OptionQuote {
+        price,
+        delta,
+        gamma: density / (spot * volatility * sqrt_t),
+        vega: spot * density * sqrt_t / 100.0,
+        theta: annual_theta / 365.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_reference_prices() {
+        // Hull, Options, Futures and Other Derivatives, example 15.6
+        let call = price_european(OptionType::Call, 42.0, 40.0, 0.5, 0.2, 0.1).unwrap();
+        let put = price_european(OptionType::Put, 42.0, 40.0, 0.5, 0.2, 0.1).unwrap();
+        assert!((call.price - 4.76).abs() < 0.01);
+        assert!((put.price - 0.81).abs() < 0.01);
+        assert!((call.delta - put.delta - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_put_call_parity() {
+        let (spot, strike, years, rate) = (150.0, 160.0, 0.25, 0.03);
+        let call = price_european(OptionType::Call, spot, strike, years, 0.9, rate).unwrap();
+        let put = price_european(OptionType::Put, spot, strike, years, 0.9, rate).unwrap();
+        let parity = spot - strike * (-rate * years).exp();
+        assert!((call.price - put.price - parity).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_rejects_expired_options() {
+        assert!(price_european(OptionType::Call, 100.0, 100.0, 0.0, 0.5, 0.0).is_none());
+    }
+}
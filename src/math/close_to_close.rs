@@ -0,0 +1,114 @@
+use super::{log_returns, Candle, VolatilityEstimator};
+
+/// Classic close-to-close estimator: population variance of log returns.
+///
+/// # Requirements
+/// - At least two price points.
+///
+/// # Example
+/// 1. Calculate log returns: ln(P₁/P₀), ln(P₂/P₁), etc.
+/// 2. Find the variance of these returns
+/// 3. Annualize with √365 (crypto markets trade 24/7/365, unlike the 252 days of stock markets)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloseToClose;
+
+impl VolatilityEstimator for CloseToClose {
+    fn name(&self) -> &'static str {
+        "close_to_close"
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let returns = log_returns(candles);
+        if returns.is_empty() {
+            return None;
+        }
+
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns
+            .iter()
+            .map(|&return_value| (return_value - mean).powi(2))
+            .sum::<f64>()
+            / returns.len() as f64;
+
+        Some(variance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::DAYS_PER_YEAR;
+
+    fn prices(points: &[(i64, f64)]) -> Vec<Candle> {
+        points
+            .iter()
+            .map(|&(unix_time, price)| Candle::from_price(unix_time, price))
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_volatility_with_three_prices() {
+        let candles = prices(&[(1, 100.0), (2, 105.0), (3, 95.0)]);
+        let result = CloseToClose
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+
+        // With log returns: ln(105/100) ≈ 0.049, ln(95/105) ≈ -0.100
+        // Mean of log returns: (0.049 + (-0.100))/2 ≈ -0.026
+        // Variance: ((0.049-(-0.026))² + (-0.100-(-0.026))²)/2 ≈ 0.0055
+        // Daily volatility: √0.0055 ≈ 0.0744
+        // Annualized: 0.0744 * √365 ≈ 1.422
+        // As percentage: 1.422 * 100 ≈ 142.2%
+        assert!((result - 142.2).abs() < 1.0); // Allow some floating point error
+    }
+
+    #[test]
+    fn test_calculate_volatility_with_two_prices() {
+        let candles = prices(&[(1, 200.0), (2, 180.0)]);
+        let result = CloseToClose
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+
+        // A single return has no deviation from its own mean, so the variance is 0
+        assert!(result.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_calculate_volatility_with_more_realistic_data() {
+        let candles = prices(&[
+            (1, 100.0),
+            (2, 102.0),
+            (3, 99.0),
+            (4, 101.0),
+            (5, 103.0),
+            (6, 102.5),
+            (7, 103.5),
+        ]);
+        let result = CloseToClose
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+
+        // For crypto with ~1-2% daily moves, annualized volatility using 365 days
+        // would typically be higher than stock markets, often between 20-80%
+        assert!(result > 15.0 && result < 85.0);
+    }
+
+    #[test]
+    fn test_calculate_volatility_with_unsorted_data() {
+        // Out of order on purpose to ensure the estimator sorts correctly
+        let candles = prices(&[(3, 95.0), (1, 100.0), (2, 105.0)]);
+        let result = CloseToClose
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+
+        // Same expected result as test_calculate_volatility_with_three_prices
+        assert!((result - 142.2).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_calculate_volatility_with_insufficient_prices() {
+        assert!(CloseToClose
+            .annualized_volatility(&prices(&[(1, 100.0)]), DAYS_PER_YEAR)
+            .is_none());
+    }
+}
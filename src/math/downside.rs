@@ -0,0 +1,56 @@
+use super::{log_returns, Candle, VolatilityEstimator};
+
+/// Downside deviation (semi-volatility): only returns below zero contribute.
+///
+/// Squared negative log returns are averaged over *all* returns, so a series that
+/// rarely falls scores low even when its rallies are violent. Useful for sizing
+/// protective puts, where upside variance is irrelevant.
+///
+/// # Requirements
+/// - At least two price points.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Downside;
+
+impl VolatilityEstimator for Downside {
+    fn name(&self) -> &'static str {
+        "downside"
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let returns = log_returns(candles);
+        if returns.is_empty() {
+            return None;
+        }
+
+        let semi_variance = returns
+            .iter()
+            .map(|&return_value| return_value.min(0.0).powi(2))
+            .sum::<f64>()
+            / returns.len() as f64;
+
+        Some(semi_variance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downside_ignores_gains() {
+        let rally: Vec<Candle> = [100.0, 110.0, 130.0, 160.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle::from_price(i as i64, price))
+            .collect();
+        assert_eq!(Downside.variance(&rally), Some(0.0));
+
+        let mixed: Vec<Candle> = [100.0, 120.0, 90.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle::from_price(i as i64, price))
+            .collect();
+        let expected = (90.0f64 / 120.0).ln().powi(2) / 2.0;
+        assert!((Downside.variance(&mixed).unwrap() - expected).abs() < 1e-12);
+    }
+}
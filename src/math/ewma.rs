@@ -0,0 +1,67 @@
+use super::{log_returns, Candle, VolatilityEstimator};
+
+/// RiskMetrics decay factor for daily data.
+pub const DEFAULT_LAMBDA: f64 = 0.94;
+
+/// Exponentially weighted moving average of squared log returns.
+///
+/// σ²ₜ = λ·σ²ₜ₋₁ + (1 − λ)·r²ₜ, seeded with the mean squared return of the window.
+/// Recent moves dominate, so the estimate reacts faster than close-to-close.
+///
+/// # Requirements
+/// - At least two price points.
+#[derive(Debug, Clone, Copy)]
+pub struct Ewma {
+    pub lambda: f64,
+}
+
+impl Default for Ewma {
+    fn default() -> Self {
+        Self {
+            lambda: DEFAULT_LAMBDA,
+        }
+    }
+}
+
+impl VolatilityEstimator for Ewma {
+    fn name(&self) -> &'static str {
+        "ewma"
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let returns = log_returns(candles);
+        if returns.is_empty() {
+            return None;
+        }
+
+        let seed = returns.iter().map(|r| r.powi(2)).sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().fold(seed, |variance, return_value| {
+            self.lambda * variance + (1.0 - self.lambda) * return_value.powi(2)
+        });
+
+        Some(variance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ewma_weights_recent_returns_more() {
+        // Same returns, opposite order: the series ending with the large move must be more volatile
+        let calm_then_wild = [100.0, 100.5, 101.0, 101.5, 120.0];
+        let wild_then_calm = [100.0, 118.0, 118.5, 119.0, 119.5];
+        let to_candles = |prices: &[f64]| -> Vec<Candle> {
+            prices
+                .iter()
+                .enumerate()
+                .map(|(i, &price)| Candle::from_price(i as i64, price))
+                .collect()
+        };
+
+        let recent = Ewma::default().variance(&to_candles(&calm_then_wild)).unwrap();
+        let old = Ewma::default().variance(&to_candles(&wild_then_calm)).unwrap();
+        assert!(recent > old);
+    }
+}
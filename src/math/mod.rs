@@ -0,0 +1,229 @@
+//! # Volatility Math
+//!
+//! Volatility estimators behind a common [`VolatilityEstimator`] trait, so handlers and the
+//! background cache can pick a model by name without knowing how it is computed.
+//!
+//! Estimators return the **per-period variance** of the series they are given; annualization
+//! and the conversion to a percentage happen in [`annualize_variance`].
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+pub mod alignment;
+pub mod black_scholes;
+pub mod close_to_close;
+pub mod downside;
+pub mod ewma;
+pub mod normal;
+pub mod range;
+pub mod regime;
+pub mod returns;
+pub mod rolling;
+#[cfg(feature = "simulation")]
+pub mod simulation;
+pub mod stats;
+
+use close_to_close::CloseToClose;
+use downside::Downside;
+use ewma::Ewma;
+use range::{GarmanKlass, Parkinson, YangZhang};
+
+/// Number of days per year used to annualize variance (crypto trades 24/7/365).
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Candle size of a price series, selectable via the `interval` query parameter.
+///
+/// Serialized with Birdeye's `type` notation (`5m`, `15m`, `1H`, `1D`).
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Interval {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "1H")]
+    OneHour,
+    #[default]
+    #[serde(rename = "1D")]
+    OneDay,
+}
+
+impl Interval {
+    /// Birdeye `type` value for this interval.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Interval::FiveMinutes => "5m",
+            Interval::FifteenMinutes => "15m",
+            Interval::OneHour => "1H",
+            Interval::OneDay => "1D",
+        }
+    }
+
+    /// Length of one candle in seconds.
+    pub fn seconds(self) -> i64 {
+        match self {
+            Interval::FiveMinutes => 5 * 60,
+            Interval::FifteenMinutes => 15 * 60,
+            Interval::OneHour => 60 * 60,
+            Interval::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Number of candles in a year of `days_per_year` days, e.g. 365×24 for hourly candles.
+    pub fn periods_per_year(self, days_per_year: f64) -> f64 {
+        days_per_year * (Interval::OneDay.seconds() / self.seconds()) as f64
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single price bar fed into the estimators.
+///
+/// Close-only series (e.g. Birdeye `history_price`) are represented with
+/// `open == high == low == close`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candle {
+    pub unix_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl Candle {
+    /// Builds a bar from a single observed price.
+    pub fn from_price(unix_time: i64, price: f64) -> Self {
+        Self {
+            unix_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+}
+
+/// A model that estimates the variance of a price series.
+pub trait VolatilityEstimator: Send + Sync {
+    /// Name the estimator is selected by, e.g. `close_to_close`.
+    fn name(&self) -> &'static str;
+
+    /// Whether the estimator needs real open/high/low data rather than closes only.
+    fn requires_ohlc(&self) -> bool {
+        false
+    }
+
+    /// Returns the per-period variance of the series, or `None` if there is not enough data.
+    ///
+    /// Candles may be passed in any order.
+    fn variance(&self, candles: &[Candle]) -> Option<f64>;
+
+    /// Returns the annualized volatility in percent.
+    ///
+    /// `periods_per_year` must match the sampling frequency of the candles,
+    /// see [`Interval::periods_per_year`].
+    fn annualized_volatility(&self, candles: &[Candle], periods_per_year: f64) -> Option<f64> {
+        self.variance(candles)
+            .map(|variance| annualize_variance(variance, periods_per_year))
+    }
+}
+
+/// Annualizes a per-period variance and converts it to a percentage.
+pub fn annualize_variance(variance: f64, periods_per_year: f64) -> f64 {
+    variance.max(0.0).sqrt() * periods_per_year.sqrt() * 100.0
+}
+
+/// Estimators selectable by name via the `estimator` query parameter or configuration.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimatorKind {
+    /// Standard deviation of close-to-close log returns.
+    #[default]
+    CloseToClose,
+    /// Exponentially weighted moving average of squared returns (RiskMetrics).
+    Ewma,
+    /// High/low range estimator.
+    Parkinson,
+    /// Open/high/low/close estimator.
+    GarmanKlass,
+    /// Drift-independent estimator that also accounts for overnight jumps.
+    YangZhang,
+    /// Semi-volatility: deviation of negative close-to-close log returns only.
+    Downside,
+}
+
+impl EstimatorKind {
+    /// Returns the estimator implementation for this kind.
+    pub fn estimator(self) -> Box<dyn VolatilityEstimator> {
+        match self {
+            EstimatorKind::CloseToClose => Box::new(CloseToClose),
+            EstimatorKind::Ewma => Box::new(Ewma::default()),
+            EstimatorKind::Parkinson => Box::new(Parkinson),
+            EstimatorKind::GarmanKlass => Box::new(GarmanKlass),
+            EstimatorKind::YangZhang => Box::new(YangZhang),
+            EstimatorKind::Downside => Box::new(Downside),
+        }
+    }
+}
+
+impl fmt::Display for EstimatorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.estimator().name())
+    }
+}
+
+impl FromStr for EstimatorKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_string()))
+            .map_err(|_| format!("Unknown estimator `{}`.", s))
+    }
+}
+
+/// Sorts candles chronologically, dropping any with non-positive prices
+/// since their log returns and ranges are undefined.
+pub(crate) fn sorted_valid_candles(candles: &[Candle]) -> Vec<Candle> {
+    let mut candles: Vec<Candle> = candles
+        .iter()
+        .filter(|candle| {
+            candle.open > 0.0 && candle.high > 0.0 && candle.low > 0.0 && candle.close > 0.0
+        })
+        .copied()
+        .collect();
+    candles.sort_by_key(|candle| candle.unix_time);
+    candles
+}
+
+/// Computes chronological close-to-close log returns.
+pub(crate) fn log_returns(candles: &[Candle]) -> Vec<f64> {
+    sorted_valid_candles(candles)
+        .windows(2)
+        .map(|window| (window[1].close / window[0].close).ln())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimator_kind_from_name() {
+        assert_eq!("garman_klass".parse::<EstimatorKind>(), Ok(EstimatorKind::GarmanKlass));
+        assert_eq!(EstimatorKind::YangZhang.to_string(), "yang_zhang");
+        assert_eq!("downside".parse::<EstimatorKind>(), Ok(EstimatorKind::Downside));
+        assert!("garch".parse::<EstimatorKind>().is_err());
+    }
+
+    #[test]
+    fn test_interval_periods_per_year() {
+        assert_eq!(Interval::OneDay.periods_per_year(DAYS_PER_YEAR), 365.0);
+        assert_eq!(Interval::OneHour.periods_per_year(DAYS_PER_YEAR), 365.0 * 24.0);
+        assert_eq!(Interval::FiveMinutes.periods_per_year(252.0), 252.0 * 288.0);
+    }
+}
@@ -0,0 +1,203 @@
+//! Range-based estimators that use the full open/high/low/close bar.
+//!
+//! These are considerably more data-efficient than close-to-close and are the
+//! usual choice for short windows.
+
+use super::{sorted_valid_candles, Candle, VolatilityEstimator};
+
+/// Parkinson (1980) high/low estimator.
+///
+/// σ² = 1 / (4·n·ln 2) · Σ ln(Hᵢ/Lᵢ)²
+///
+/// # Requirements
+/// - At least one candle.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Parkinson;
+
+impl VolatilityEstimator for Parkinson {
+    fn name(&self) -> &'static str {
+        "parkinson"
+    }
+
+    fn requires_ohlc(&self) -> bool {
+        true
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let candles = sorted_valid_candles(candles);
+        if candles.is_empty() {
+            return None;
+        }
+
+        let sum_squared_ranges: f64 = candles
+            .iter()
+            .map(|candle| (candle.high / candle.low).ln().powi(2))
+            .sum();
+
+        Some(sum_squared_ranges / (4.0 * candles.len() as f64 * 2.0_f64.ln()))
+    }
+}
+
+/// Garman-Klass (1980) estimator.
+///
+/// σ² = 1/n · Σ [½·ln(Hᵢ/Lᵢ)² − (2·ln 2 − 1)·ln(Cᵢ/Oᵢ)²]
+///
+/// # Requirements
+/// - At least one candle.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GarmanKlass;
+
+impl VolatilityEstimator for GarmanKlass {
+    fn name(&self) -> &'static str {
+        "garman_klass"
+    }
+
+    fn requires_ohlc(&self) -> bool {
+        true
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let candles = sorted_valid_candles(candles);
+        if candles.is_empty() {
+            return None;
+        }
+
+        let close_open_weight = 2.0 * 2.0_f64.ln() - 1.0;
+        let sum: f64 = candles
+            .iter()
+            .map(|candle| {
+                0.5 * (candle.high / candle.low).ln().powi(2)
+                    - close_open_weight * (candle.close / candle.open).ln().powi(2)
+            })
+            .sum();
+
+        // The estimator can dip marginally below zero on tiny samples; clamp it
+        Some((sum / candles.len() as f64).max(0.0))
+    }
+}
+
+/// Yang-Zhang (2000) estimator.
+///
+/// Combines the overnight (previous close → open) variance, the open-to-close
+/// variance and the Rogers-Satchell variance:
+///
+/// σ² = σ²_overnight + k·σ²_open_close + (1 − k)·σ²_rs,  k = 0.34 / (1.34 + (n + 1)/(n − 1))
+///
+/// # Requirements
+/// - At least three candles (two periods with a previous close).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YangZhang;
+
+impl VolatilityEstimator for YangZhang {
+    fn name(&self) -> &'static str {
+        "yang_zhang"
+    }
+
+    fn requires_ohlc(&self) -> bool {
+        true
+    }
+
+    fn variance(&self, candles: &[Candle]) -> Option<f64> {
+        let candles = sorted_valid_candles(candles);
+        if candles.len() < 3 {
+            return None;
+        }
+
+        let periods = &candles[1..];
+        let n = periods.len() as f64;
+
+        let overnight: Vec<f64> = candles
+            .windows(2)
+            .map(|window| (window[1].open / window[0].close).ln())
+            .collect();
+        let open_close: Vec<f64> = periods
+            .iter()
+            .map(|candle| (candle.close / candle.open).ln())
+            .collect();
+        let rogers_satchell = periods
+            .iter()
+            .map(|candle| {
+                (candle.high / candle.close).ln() * (candle.high / candle.open).ln()
+                    + (candle.low / candle.close).ln() * (candle.low / candle.open).ln()
+            })
+            .sum::<f64>()
+            / n;
+
+        let sample_variance = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / n;
+            values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        };
+
+        let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+        Some(
+            sample_variance(&overnight)
+                + k * sample_variance(&open_close)
+                + (1.0 - k) * rogers_satchell,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::DAYS_PER_YEAR;
+
+    fn candle(unix_time: i64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            unix_time,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_parkinson_volatility_with_constant_range() {
+        // ln(1.1)² / (4·ln 2) ≈ 0.003277 per day, √ ≈ 0.05724, annualized ≈ 109.4%
+        let candles = vec![
+            candle(1, 100.0, 110.0, 100.0, 105.0),
+            candle(2, 105.0, 115.5, 105.0, 110.0),
+        ];
+        let result = Parkinson
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+        assert!((result - 109.4).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_garman_klass_volatility_without_body_matches_half_squared_range() {
+        // With open == close the estimator reduces to √(½·ln(H/L)²)
+        let candles = vec![candle(1, 100.0, 110.0, 100.0, 100.0)];
+        let result = GarmanKlass
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+        let expected = (0.5 * (1.1_f64).ln().powi(2)).sqrt() * (365.0_f64).sqrt() * 100.0;
+        assert!((result - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_yang_zhang_volatility_is_zero_for_flat_series() {
+        let candles = vec![
+            candle(1, 100.0, 100.0, 100.0, 100.0),
+            candle(2, 100.0, 100.0, 100.0, 100.0),
+            candle(3, 100.0, 100.0, 100.0, 100.0),
+        ];
+        let result = YangZhang
+            .annualized_volatility(&candles, DAYS_PER_YEAR)
+            .expect("Should calculate volatility");
+        assert!(result.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_range_estimators_with_insufficient_candles() {
+        assert!(Parkinson.variance(&[]).is_none());
+        assert!(GarmanKlass.variance(&[]).is_none());
+        assert!(YangZhang
+            .variance(&[
+                candle(1, 100.0, 110.0, 90.0, 105.0),
+                candle(2, 105.0, 110.0, 100.0, 102.0),
+            ])
+            .is_none());
+    }
+}
@@ -0,0 +1,54 @@
+//! Classification of the current volatility environment against its own history.
+
+use serde::{Deserialize, Serialize};
+
+/// Volatility environment, from calm to stressed.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VolRegime {
+    Low,
+    Normal,
+    High,
+    Extreme,
+}
+
+/// Z-score cut-offs between regimes. A z-score at or below `low` is low, at or above
+/// `high` is high, and at or above `extreme` is extreme.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegimeThresholds {
+    pub low: f64,
+    pub high: f64,
+    pub extreme: f64,
+}
+
+/// Regime a z-score falls in under `thresholds`.
+pub fn classify(z_score: f64, thresholds: &RegimeThresholds) -> VolRegime {
+    if z_score >= thresholds.extreme {
+        VolRegime::Extreme
+    } else if z_score >= thresholds.high {
+        VolRegime::High
+    } else if z_score <= thresholds.low {
+        VolRegime::Low
+    } else {
+        VolRegime::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_by_thresholds() {
+        let thresholds = RegimeThresholds {
+            low: -1.0,
+            high: 1.0,
+            extreme: 2.0,
+        };
+        assert_eq!(classify(-1.5, &thresholds), VolRegime::Low);
+        assert_eq!(classify(0.3, &thresholds), VolRegime::Normal);
+        assert_eq!(classify(1.0, &thresholds), VolRegime::High);
+        assert_eq!(classify(2.7, &thresholds), VolRegime::Extreme);
+    }
+}
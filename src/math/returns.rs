@@ -0,0 +1,65 @@
+use super::{sorted_valid_candles, Candle};
+use crate::utils::timestamp_format;
+use serde::{Deserialize, Serialize};
+
+/// How consecutive closes are turned into returns.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnKind {
+    /// ln(Pₜ / Pₜ₋₁)
+    #[default]
+    Log,
+    /// Pₜ / Pₜ₋₁ − 1
+    Simple,
+}
+
+/// A single return, stamped with the time of the candle it ends on.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnPoint {
+    #[serde(serialize_with = "timestamp_format::serialize")]
+    pub unix_time: i64,
+    pub value: f64,
+}
+
+/// Computes the chronological close-to-close return series.
+///
+/// Candles with non-positive prices are skipped; fewer than two candles yield an empty series.
+pub fn return_series(candles: &[Candle], kind: ReturnKind) -> Vec<ReturnPoint> {
+    sorted_valid_candles(candles)
+        .windows(2)
+        .map(|window| {
+            let ratio = window[1].close / window[0].close;
+            ReturnPoint {
+                unix_time: window[1].unix_time,
+                value: match kind {
+                    ReturnKind::Log => ratio.ln(),
+                    ReturnKind::Simple => ratio - 1.0,
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_return_series_log_and_simple() {
+        let candles = [
+            Candle::from_price(2, 110.0),
+            Candle::from_price(1, 100.0),
+            Candle::from_price(3, 99.0),
+        ];
+
+        let simple = return_series(&candles, ReturnKind::Simple);
+        assert_eq!(simple.len(), 2);
+        assert_eq!(simple[0].unix_time, 2);
+        assert!((simple[0].value - 0.1).abs() < 1e-12);
+        assert!((simple[1].value + 0.1).abs() < 1e-12);
+
+        let log = return_series(&candles, ReturnKind::Log);
+        assert!((log[0].value - 1.1_f64.ln()).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,95 @@
+use super::{sorted_valid_candles, Candle, VolatilityEstimator};
+use crate::utils::timestamp_format;
+use serde::{Deserialize, Serialize};
+
+/// Volatility over the window ending at `unix_time`.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityPoint {
+    #[serde(serialize_with = "timestamp_format::serialize")]
+    pub unix_time: i64,
+    pub volatility: f64,
+}
+
+/// Computes annualized volatility over every window of `window` returns (`window + 1` candles).
+///
+/// Each point is stamped with the last candle of its window. Windows the estimator
+/// cannot compute are skipped; `window` must be at least 1.
+pub fn rolling_volatility(
+    candles: &[Candle],
+    window: usize,
+    estimator: &dyn VolatilityEstimator,
+    periods_per_year: f64,
+) -> Vec<VolatilityPoint> {
+    let candles = sorted_valid_candles(candles);
+    if window == 0 || candles.len() <= window {
+        return Vec::new();
+    }
+
+    candles
+        .windows(window + 1)
+        .filter_map(|slice| {
+            let volatility = estimator.annualized_volatility(slice, periods_per_year)?;
+            Some(VolatilityPoint {
+                unix_time: slice[slice.len() - 1].unix_time,
+                volatility,
+            })
+        })
+        .collect()
+}
+
+/// Annualized volatility over the last `window` returns of `candles`.
+///
+/// `None` when the series has `window` or fewer valid candles or the estimator cannot
+/// compute the window.
+pub fn trailing_volatility(
+    candles: &[Candle],
+    window: usize,
+    estimator: &dyn VolatilityEstimator,
+    periods_per_year: f64,
+) -> Option<f64> {
+    let candles = sorted_valid_candles(candles);
+    if window == 0 || candles.len() <= window {
+        return None;
+    }
+    estimator.annualized_volatility(&candles[candles.len() - window - 1..], periods_per_year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::close_to_close::CloseToClose;
+
+    #[test]
+    fn test_rolling_volatility_produces_one_point_per_window() {
+        let candles: Vec<Candle> = [100.0, 101.0, 99.0, 102.0, 98.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle::from_price(i as i64, price))
+            .collect();
+
+        let points = rolling_volatility(&candles, 2, &CloseToClose, 365.0);
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].unix_time, 2);
+        assert_eq!(
+            points[2].volatility,
+            CloseToClose.annualized_volatility(&candles[2..], 365.0).unwrap()
+        );
+        assert!(rolling_volatility(&candles, 5, &CloseToClose, 365.0).is_empty());
+    }
+
+    #[test]
+    fn test_trailing_volatility_uses_only_the_last_window() {
+        let candles: Vec<Candle> = [100.0, 150.0, 100.0, 101.0, 100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle::from_price(i as i64, price))
+            .collect();
+
+        assert_eq!(
+            trailing_volatility(&candles, 2, &CloseToClose, 365.0),
+            CloseToClose.annualized_volatility(&candles[2..], 365.0)
+        );
+        assert_eq!(trailing_volatility(&candles, 5, &CloseToClose, 365.0), None);
+    }
+}
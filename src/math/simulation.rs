@@ -0,0 +1,93 @@
+//! Monte Carlo simulation of terminal prices from a token's historical returns.
+
+use super::stats::{mean, variance};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+
+/// How per-period returns are drawn along each simulated path.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationMethod {
+    /// Geometric Brownian motion: normal log returns with the historical mean and volatility.
+    #[default]
+    Gbm,
+    /// Historical log returns resampled with replacement, keeping fat tails and skew.
+    Bootstrap,
+}
+
+/// Inputs for a batch of simulated price paths.
+#[derive(Debug, Clone)]
+pub struct SimulationParams {
+    pub start_price: f64,
+    /// Historical per-period log returns the drift, volatility and bootstrap samples come from.
+    pub log_returns: Vec<f64>,
+    /// Number of periods each path is stepped forward.
+    pub horizon: usize,
+    pub paths: usize,
+    pub seed: u64,
+}
+
+/// Simulates `params.paths` price paths and returns each path's terminal price.
+///
+/// The same seed always produces the same prices. Returns `None` when fewer than two
+/// historical returns are available.
+pub fn simulate_terminal_prices(params: &SimulationParams, method: SimulationMethod) -> Option<Vec<f64>> {
+    let returns = &params.log_returns;
+    if returns.len() < 2 {
+        return None;
+    }
+    let drift = mean(returns)?;
+    let volatility = variance(returns)?.sqrt();
+
+    let mut rng = StdRng::seed_from_u64(params.seed);
+    let terminal_prices = (0..params.paths)
+        .map(|_| {
+            let total_log_return: f64 = (0..params.horizon)
+                .map(|_| match method {
+                    SimulationMethod::Gbm => {
+                        let shock: f64 = rng.sample(StandardNormal);
+                        drift + volatility * shock
+                    }
+                    SimulationMethod::Bootstrap => returns[rng.gen_range(0..returns.len())],
+                })
+                .sum();
+            params.start_price * total_log_return.exp()
+        })
+        .collect();
+
+    Some(terminal_prices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(seed: u64) -> SimulationParams {
+        SimulationParams {
+            start_price: 100.0,
+            log_returns: vec![0.01, -0.02, 0.015, -0.005, 0.0],
+            horizon: 10,
+            paths: 200,
+            seed,
+        }
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible_for_a_seed() {
+        let first = simulate_terminal_prices(&params(7), SimulationMethod::Gbm).unwrap();
+        let second = simulate_terminal_prices(&params(7), SimulationMethod::Gbm).unwrap();
+        assert_eq!(first.len(), 200);
+        assert_eq!(first, second);
+        assert_ne!(first, simulate_terminal_prices(&params(8), SimulationMethod::Gbm).unwrap());
+    }
+
+    #[test]
+    fn test_bootstrap_stays_within_historical_extremes() {
+        let prices = simulate_terminal_prices(&params(1), SimulationMethod::Bootstrap).unwrap();
+        let lowest = 100.0 * (-0.02f64 * 10.0).exp();
+        let highest = 100.0 * (0.015f64 * 10.0).exp();
+        assert!(prices.iter().all(|price| (lowest - 1e-9..=highest + 1e-9).contains(price)));
+    }
+}
@@ -0,0 +1,249 @@
+//! Descriptive statistics shared by the analytics endpoints.
+
+use serde::{Deserialize, Serialize};
+
+/// Arithmetic mean, or `None` for an empty slice.
+pub fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Sample covariance (n − 1 denominator) of two equally long series.
+///
+/// Returns `None` if the lengths differ or there are fewer than two observations.
+pub fn covariance(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() != b.len() || a.len() < 2 {
+        return None;
+    }
+    let mean_a = mean(a)?;
+    let mean_b = mean(b)?;
+    let sum: f64 = a
+        .iter()
+        .zip(b)
+        .map(|(x, y)| (x - mean_a) * (y - mean_b))
+        .sum();
+    Some(sum / (a.len() - 1) as f64)
+}
+
+/// Sample variance (n − 1 denominator).
+pub fn variance(values: &[f64]) -> Option<f64> {
+    covariance(values, values)
+}
+
+/// Pearson correlation coefficient of two equally long series.
+///
+/// Returns `None` if either series is constant, since the coefficient is undefined.
+pub fn correlation(a: &[f64], b: &[f64]) -> Option<f64> {
+    let denominator = (variance(a)? * variance(b)?).sqrt();
+    if denominator == 0.0 {
+        return None;
+    }
+    Some((covariance(a, b)? / denominator).clamp(-1.0, 1.0))
+}
+
+/// Beta of `asset` returns against `benchmark` returns: cov(asset, benchmark) / var(benchmark).
+///
+/// Returns `None` if the benchmark series is flat.
+pub fn beta(asset: &[f64], benchmark: &[f64]) -> Option<f64> {
+    let benchmark_variance = variance(benchmark)?;
+    if benchmark_variance == 0.0 {
+        return None;
+    }
+    Some(covariance(asset, benchmark)? / benchmark_variance)
+}
+
+/// Shape of a return distribution, to show whether a volatility figure hides fat tails.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnStats {
+    pub mean_return: f64,
+    pub skewness: f64,
+    /// Kurtosis minus 3, so a normal distribution scores 0.
+    pub excess_kurtosis: f64,
+    pub min_return: f64,
+    pub max_return: f64,
+}
+
+/// Moment-based skewness and excess kurtosis plus mean and extremes of `returns`.
+///
+/// Returns `None` for fewer than two returns or when the returns don't vary.
+pub fn return_stats(returns: &[f64]) -> Option<ReturnStats> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let mean_return = mean(returns)?;
+    let central_moment = |power: i32| mean(&returns.iter().map(|r| (r - mean_return).powi(power)).collect::<Vec<_>>());
+    let second = central_moment(2)?;
+    if second == 0.0 {
+        return None;
+    }
+
+    Some(ReturnStats {
+        mean_return,
+        skewness: central_moment(3)? / second.powf(1.5),
+        excess_kurtosis: central_moment(4)? / (second * second) - 3.0,
+        min_return: returns.iter().copied().fold(f64::INFINITY, f64::min),
+        max_return: returns.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+/// Linearly interpolated quantile `q` (in `[0, 1]`) of `values`.
+///
+/// Returns `None` for an empty slice.
+pub fn quantile(values: &[f64], q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let weight = position - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * weight)
+}
+
+/// Percentage of `values` less than or equal to `value`, in `[0, 100]`.
+pub fn percentile_of(values: &[f64], value: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let at_or_below = values.iter().filter(|v| **v <= value).count();
+    Some(at_or_below as f64 / values.len() as f64 * 100.0)
+}
+
+/// Position of `value` between the minimum and maximum of `values`, in `[0, 100]`.
+///
+/// Returns `None` if `values` is empty or constant.
+pub fn min_max_rank(values: &[f64], value: f64) -> Option<f64> {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if values.is_empty() || max == min {
+        return None;
+    }
+    Some(((value - min) / (max - min) * 100.0).clamp(0.0, 100.0))
+}
+
+/// One equal-width bucket of a histogram, covering `[lower, upper)` (the last bin also
+/// includes its upper edge).
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: usize,
+    /// Share of all values falling in this bin.
+    pub frequency: f64,
+}
+
+/// Buckets `values` into `bins` equal-width bins spanning their minimum to maximum.
+///
+/// Returns `None` for an empty slice or zero bins. Constant values land in a single
+/// zero-width bin.
+pub fn histogram(values: &[f64], bins: usize) -> Option<Vec<HistogramBin>> {
+    if values.is_empty() || bins == 0 {
+        return None;
+    }
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let bins = if max == min { 1 } else { bins };
+    let width = (max - min) / bins as f64;
+
+    let mut counts = vec![0usize; bins];
+    for value in values {
+        let index = if width == 0.0 {
+            0
+        } else {
+            (((value - min) / width) as usize).min(bins - 1)
+        };
+        counts[index] += 1;
+    }
+
+    Some(
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| HistogramBin {
+                lower: min + width * i as f64,
+                upper: if i == bins - 1 { max } else { min + width * (i + 1) as f64 },
+                count,
+                frequency: count as f64 / values.len() as f64,
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_of_linear_relationships() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [2.0, 4.0, 6.0, 8.0];
+        let c = [4.0, 3.0, 2.0, 1.0];
+        assert!((correlation(&a, &b).unwrap() - 1.0).abs() < 1e-12);
+        assert!((correlation(&a, &c).unwrap() + 1.0).abs() < 1e-12);
+        assert!(correlation(&a, &[1.0, 1.0, 1.0, 1.0]).is_none());
+    }
+
+    #[test]
+    fn test_beta_of_leveraged_series() {
+        let benchmark = [0.01, -0.02, 0.03, 0.0];
+        let leveraged: Vec<f64> = benchmark.iter().map(|r| 2.0 * r).collect();
+        assert!((beta(&leveraged, &benchmark).unwrap() - 2.0).abs() < 1e-12);
+        assert!(beta(&leveraged, &[0.0, 0.0, 0.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn test_return_stats_of_symmetric_and_skewed_returns() {
+        let symmetric = return_stats(&[-0.02, -0.01, 0.0, 0.01, 0.02]).unwrap();
+        assert!(symmetric.skewness.abs() < 1e-12);
+        assert!((symmetric.excess_kurtosis + 1.3).abs() < 1e-12);
+        assert_eq!((symmetric.min_return, symmetric.max_return), (-0.02, 0.02));
+
+        let crash = return_stats(&[0.01, 0.01, 0.01, 0.01, -0.2]).unwrap();
+        assert!(crash.skewness < 0.0);
+        assert!(return_stats(&[0.01, 0.01]).is_none());
+    }
+
+    #[test]
+    fn test_quantile_interpolates_between_ranks() {
+        let values = [4.0, 1.0, 3.0, 2.0];
+        assert_eq!(quantile(&values, 0.0), Some(1.0));
+        assert_eq!(quantile(&values, 0.5), Some(2.5));
+        assert_eq!(quantile(&values, 1.0), Some(4.0));
+        assert_eq!(quantile(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_percentile_and_rank() {
+        let values = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(percentile_of(&values, 30.0), Some(75.0));
+        assert_eq!(min_max_rank(&values, 25.0), Some(50.0));
+        assert_eq!(min_max_rank(&[5.0, 5.0], 5.0), None);
+    }
+
+    #[test]
+    fn test_histogram_buckets_values_between_extremes() {
+        let bins = histogram(&[0.0, 0.1, 0.2, 0.3, 0.4, 1.0], 2).unwrap();
+        assert_eq!(bins.len(), 2);
+        assert_eq!((bins[0].lower, bins[0].upper), (0.0, 0.5));
+        assert_eq!((bins[0].count, bins[1].count), (5, 1));
+        assert!((bins[1].frequency - 1.0 / 6.0).abs() < 1e-12);
+
+        let flat = histogram(&[0.01, 0.01], 10).unwrap();
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].count, 2);
+        assert!(histogram(&[], 10).is_none());
+    }
+
+    #[test]
+    fn test_covariance_requires_matching_lengths() {
+        assert_eq!(covariance(&[1.0, 2.0], &[1.0, 2.0, 3.0]), None);
+        assert!((covariance(&[1.0, 3.0], &[2.0, 6.0]).unwrap() - 4.0).abs() < 1e-12);
+    }
+}
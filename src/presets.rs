@@ -0,0 +1,321 @@
+//! Named calculation presets, so teams asking for e.g. `preset=optionsDesk` all get the same
+//! methodology instead of each hand-assembling the query parameters, and the methodology
+//! version reported with every response so callers can tell when numbers shifted because
+//! the methodology did.
+
+use crate::math::regime::RegimeThresholds;
+use crate::math::{EstimatorKind, Interval};
+use crate::state::AppState;
+use crate::utils::timestamp_format;
+use axum::{
+    extract::{Query, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Longest accepted preset name.
+pub const MAX_PRESET_NAME_LEN: usize = 64;
+
+/// Number of preset changes kept in the change log; older entries are dropped.
+pub const MAX_CHANGES: usize = 1000;
+
+/// Response header carrying the methodology version the response was computed under.
+pub const METHODOLOGY_VERSION_HEADER: &str = "x-methodology-version";
+
+/// Configured defaults that shape results wherever a request and its preset leave a
+/// parameter unset.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Methodology {
+    pub volatility_estimator: EstimatorKind,
+    pub annualization_days: u32,
+    pub risk_free_rate: f64,
+    pub vol_regime_thresholds: RegimeThresholds,
+}
+
+/// What happened to a preset.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Defined,
+    Replaced,
+    Removed,
+}
+
+/// One entry of the preset change log.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetChange {
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub at: DateTime<Utc>,
+    pub preset: String,
+    pub change: ChangeKind,
+    /// The preset's definition after the change; `None` once removed.
+    pub definition: Option<Preset>,
+    /// Methodology version of requests using the preset after the change; `None` once removed.
+    pub methodology_version: Option<String>,
+}
+
+/// Methodology applied by a preset. Parameters given explicitly in a request take precedence
+/// over the preset's; unset fields fall back to the usual defaults.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Preset {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimator: Option<EstimatorKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval: Option<Interval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annualization_days: Option<u32>,
+    /// Trailing window used when a request gives neither `fromDate` nor `toDate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_stats: Option<bool>,
+}
+
+impl Preset {
+    /// Checks the preset's parameters, returning a message naming the bad field.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.annualization_days == Some(0) {
+            return Err("annualizationDays must be greater than 0.".to_string());
+        }
+        if self.days == Some(0) {
+            return Err("days must be greater than 0.".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Checks a preset name: 1 to [`MAX_PRESET_NAME_LEN`] ASCII letters, digits, `-` or `_`.
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if name.is_empty() || name.len() > MAX_PRESET_NAME_LEN || !valid_chars {
+        return Err(format!(
+            "Preset names must be 1 to {} letters, digits, '-' or '_'.",
+            MAX_PRESET_NAME_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// Identifier of the methodology behind a response: a hash of the configured defaults and the
+/// applied preset, if any. It changes exactly when one of them does, and agrees across
+/// instances running the same build and configuration.
+pub fn methodology_version(defaults: &Methodology, preset: Option<&Preset>) -> String {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(&(defaults, preset))
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// In-memory registry of presets, defined at runtime through the admin endpoints, along
+/// with a log of their changes.
+#[derive(Clone)]
+pub struct PresetStore {
+    defaults: Arc<Methodology>,
+    presets: Arc<RwLock<BTreeMap<String, Preset>>>,
+    changes: Arc<RwLock<VecDeque<PresetChange>>>,
+}
+
+impl PresetStore {
+    /// Create an empty store whose methodology versions build on `defaults`.
+    pub fn new(defaults: Methodology) -> Self {
+        Self {
+            defaults: Arc::new(defaults),
+            presets: Arc::new(RwLock::new(BTreeMap::new())),
+            changes: Arc::new(RwLock::new(VecDeque::new())),
+        }
+    }
+
+    /// The configured defaults.
+    pub fn defaults(&self) -> &Methodology {
+        &self.defaults
+    }
+
+    /// Methodology version of requests using `preset`, or none.
+    pub async fn version(&self, preset: Option<&str>) -> String {
+        let presets = self.presets.read().await;
+        methodology_version(&self.defaults, preset.and_then(|name| presets.get(name)))
+    }
+
+    /// Get a preset by name.
+    pub async fn get(&self, name: &str) -> Option<Preset> {
+        self.presets.read().await.get(name).cloned()
+    }
+
+    /// All presets, ordered by name.
+    pub async fn all(&self) -> BTreeMap<String, Preset> {
+        self.presets.read().await.clone()
+    }
+
+    /// Define or replace a preset, returning whether it already existed.
+    ///
+    /// Re-defining a preset unchanged is not logged.
+    pub async fn upsert(&self, name: String, preset: Preset) -> bool {
+        let previous = self.presets.write().await.insert(name.clone(), preset.clone());
+        if previous.as_ref() != Some(&preset) {
+            let change = if previous.is_some() {
+                ChangeKind::Replaced
+            } else {
+                ChangeKind::Defined
+            };
+            self.log(PresetChange {
+                at: Utc::now(),
+                methodology_version: Some(methodology_version(&self.defaults, Some(&preset))),
+                preset: name,
+                change,
+                definition: Some(preset),
+            })
+            .await;
+        }
+        previous.is_some()
+    }
+
+    /// Remove a preset, returning whether it existed.
+    pub async fn remove(&self, name: &str) -> bool {
+        let removed = self.presets.write().await.remove(name).is_some();
+        if removed {
+            self.log(PresetChange {
+                at: Utc::now(),
+                preset: name.to_string(),
+                change: ChangeKind::Removed,
+                definition: None,
+                methodology_version: None,
+            })
+            .await;
+        }
+        removed
+    }
+
+    /// Logged changes, oldest first, optionally only those of one preset.
+    pub async fn changes(&self, preset: Option<&str>) -> Vec<PresetChange> {
+        self.changes
+            .read()
+            .await
+            .iter()
+            .filter(|change| preset.is_none_or(|name| change.preset == name))
+            .cloned()
+            .collect()
+    }
+
+    async fn log(&self, change: PresetChange) {
+        let mut changes = self.changes.write().await;
+        if changes.len() == MAX_CHANGES {
+            changes.pop_front();
+        }
+        changes.push_back(change);
+    }
+}
+
+#[derive(Deserialize)]
+struct PresetQuery {
+    preset: Option<String>,
+}
+
+/// Middleware adding `X-Methodology-Version` to every response: the version of the request's
+/// `preset` when it names a defined one, of the configured defaults otherwise.
+pub async fn report_methodology_version(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let preset = Query::<PresetQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.preset);
+    let version = state.presets.version(preset.as_deref()).await;
+
+    let mut response = next.run(request).await;
+    if let Ok(value) = HeaderValue::from_str(&version) {
+        response.headers_mut().insert(METHODOLOGY_VERSION_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_name_validation() {
+        assert!(validate_name("optionsDesk").is_ok());
+        assert!(validate_name("risk_daily-v2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("has space").is_err());
+        assert!(validate_name(&"a".repeat(MAX_PRESET_NAME_LEN + 1)).is_err());
+    }
+
+    fn defaults() -> Methodology {
+        Methodology {
+            volatility_estimator: EstimatorKind::CloseToClose,
+            annualization_days: 365,
+            risk_free_rate: 0.0,
+            vol_regime_thresholds: RegimeThresholds {
+                low: -1.0,
+                high: 1.0,
+                extreme: 2.0,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_upserts_and_removes() {
+        let store = PresetStore::new(defaults());
+        let preset = Preset {
+            interval: Some(Interval::OneHour),
+            ..Preset::default()
+        };
+
+        assert!(!store.upsert("optionsDesk".to_string(), preset.clone()).await);
+        assert!(store.upsert("optionsDesk".to_string(), preset.clone()).await);
+        assert_eq!(store.get("optionsDesk").await, Some(preset));
+        assert!(store.remove("optionsDesk").await);
+        assert!(!store.remove("optionsDesk").await);
+        assert!(store.all().await.is_empty());
+
+        let changes: Vec<ChangeKind> = store
+            .changes(Some("optionsDesk"))
+            .await
+            .iter()
+            .map(|change| change.change)
+            .collect();
+        assert_eq!(changes, vec![ChangeKind::Defined, ChangeKind::Removed]);
+    }
+
+    #[tokio::test]
+    async fn test_version_changes_with_the_preset_only() {
+        let store = PresetStore::new(defaults());
+        let base = store.version(None).await;
+        assert_eq!(store.version(Some("riskDaily")).await, base);
+
+        store
+            .upsert(
+                "riskDaily".to_string(),
+                Preset {
+                    annualization_days: Some(252),
+                    ..Preset::default()
+                },
+            )
+            .await;
+        let risk_daily = store.version(Some("riskDaily")).await;
+        assert_ne!(risk_daily, base);
+        assert_eq!(store.version(None).await, base);
+
+        let annualized_252 = PresetStore::new(Methodology {
+            annualization_days: 252,
+            ..defaults()
+        });
+        assert_ne!(annualized_252.version(None).await, base);
+    }
+}
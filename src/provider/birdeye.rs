@@ -0,0 +1,368 @@
+//! Birdeye public API provider.
+
+use super::{PriceProvider, ProviderError, ProviderSeries};
+use crate::config::AppConfig;
+use crate::math::{Candle, Interval};
+use crate::utils::address_type::{self, AddressType};
+use crate::utils::chain;
+use crate::utils::deadline::with_deadline;
+use crate::utils::timestamp_format;
+use async_trait::async_trait;
+use axum::http::{HeaderMap, HeaderValue};
+use chrono::{DateTime, Utc};
+use reqwest::header::ACCEPT;
+use serde::{Deserialize, Serialize};
+
+//
+// ----------- Data Structures -----------
+//
+
+/// Raw structure of the response returned by the Birdeye API.
+#[derive(Debug, Deserialize)]
+pub struct BirdeyeHistoricalPriceResponse {
+    pub data: Option<HistoricalPriceData>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+/// Nested `data` field inside the Birdeye response.
+#[derive(Debug, Deserialize)]
+pub struct HistoricalPriceData {
+    pub items: Vec<HistoricalPricePoint>,
+}
+
+/// Represents a single historical price point.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct HistoricalPricePoint {
+    #[serde(rename = "unixTime", serialize_with = "timestamp_format::serialize")]
+    pub unix_time: i64,
+    pub value: f64,
+}
+
+/// Internal representation of Birdeye response, abstracting success and failure.
+#[derive(Debug)]
+pub enum BirdeyeResponse {
+    Success(HistoricalPriceData),
+    Failure(String),
+}
+
+/// Raw structure of the response returned by the Birdeye OHLCV API.
+#[derive(Debug, Deserialize)]
+pub struct BirdeyeOhlcvResponse {
+    pub data: Option<OhlcvData>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+/// Nested `data` field inside the Birdeye OHLCV response.
+#[derive(Debug, Deserialize)]
+pub struct OhlcvData {
+    pub items: Vec<OhlcvCandle>,
+}
+
+/// Represents a single OHLCV candle.
+#[derive(Debug, Deserialize, Clone)]
+pub struct OhlcvCandle {
+    #[serde(rename = "unixTime")]
+    pub unix_time: i64,
+    #[serde(rename = "o")]
+    pub open: f64,
+    #[serde(rename = "h")]
+    pub high: f64,
+    #[serde(rename = "l")]
+    pub low: f64,
+    #[serde(rename = "c")]
+    pub close: f64,
+    #[serde(rename = "v", default)]
+    pub volume: f64,
+}
+
+/// Raw structure of the response returned by the Birdeye price API.
+#[derive(Debug, Deserialize)]
+struct BirdeyePriceResponse {
+    data: Option<BirdeyePrice>,
+    success: bool,
+    message: Option<String>,
+}
+
+/// Nested `data` field inside the Birdeye price response.
+#[derive(Debug, Deserialize)]
+struct BirdeyePrice {
+    value: f64,
+}
+
+//
+// ----------- Conversions -----------
+//
+
+impl From<BirdeyeHistoricalPriceResponse> for BirdeyeResponse {
+    fn from(raw: BirdeyeHistoricalPriceResponse) -> Self {
+        if raw.success {
+            if let Some(data) = raw.data {
+                BirdeyeResponse::Success(data)
+            } else {
+                BirdeyeResponse::Failure("Missing data in successful Birdeye response.".to_string())
+            }
+        } else {
+            let message = raw.message.unwrap_or_else(|| "Unknown error".to_string());
+            BirdeyeResponse::Failure(message)
+        }
+    }
+}
+
+impl From<&HistoricalPricePoint> for Candle {
+    fn from(point: &HistoricalPricePoint) -> Self {
+        Candle::from_price(point.unix_time, point.value)
+    }
+}
+
+impl From<&OhlcvCandle> for Candle {
+    fn from(candle: &OhlcvCandle) -> Self {
+        Candle {
+            unix_time: candle.unix_time,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+        }
+    }
+}
+
+impl BirdeyeOhlcvResponse {
+    /// Returns the candles or Birdeye's failure message.
+    pub fn into_candles(self) -> Result<Vec<OhlcvCandle>, String> {
+        if self.success {
+            self.data
+                .map(|data| data.items)
+                .ok_or_else(|| "Missing data in successful Birdeye response.".to_string())
+        } else {
+            Err(self.message.unwrap_or_else(|| "Unknown error".to_string()))
+        }
+    }
+}
+
+//
+// ----------- Provider -----------
+//
+
+/// Fetches prices from the Birdeye public API (`history_price`, `ohlcv` and `price`).
+pub struct BirdeyeProvider {
+    client: reqwest::Client,
+    api_key: String,
+    history_url: String,
+    ohlcv_url: String,
+    pair_ohlcv_url: String,
+    price_url: String,
+}
+
+impl BirdeyeProvider {
+    /// Builds a provider from the Birdeye URLs and API key in `config`.
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: config.birdeye_api_key.clone(),
+            history_url: config.birdeye_base_url.clone(),
+            ohlcv_url: config.birdeye_ohlcv_url.clone(),
+            pair_ohlcv_url: config.birdeye_pair_ohlcv_url.clone(),
+            price_url: config.birdeye_price_url.clone(),
+        }
+    }
+
+    /// Headers every Birdeye request carries.
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(
+            "X-API-KEY",
+            HeaderValue::from_str(&self.api_key).expect("Invalid API key format"),
+        );
+        headers.insert("x-chain", HeaderValue::from_static(chain::current().as_str()));
+        headers
+    }
+
+    /// Fetches historical token prices from the Birdeye public API.
+    pub async fn fetch_price_history(
+        &self,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        token_address: &str,
+    ) -> Result<BirdeyeHistoricalPriceResponse, reqwest::Error> {
+        // Construct the query string with required parameters:
+        // - address: The token address to fetch prices for
+        // - address_type: "token" or "pair", from the request's `addressType`
+        // - type: Candle size, e.g. "1D" for daily or "1H" for hourly price data
+        // - time_from: Start timestamp
+        // - time_to: End timestamp
+        let query = format!(
+            "address={}&address_type={}&type={}&time_from={}&time_to={}",
+            token_address,
+            address_type::current(),
+            interval,
+            from_date.timestamp(),
+            to_date.timestamp()
+        );
+        let request_url = format!("{}?{}", self.history_url, query);
+
+        with_deadline(self.client.get(request_url).headers(self.headers()))
+            .send()
+            .await?
+            .json::<BirdeyeHistoricalPriceResponse>()
+            .await
+    }
+
+    /// Fetches OHLCV candles from the Birdeye public API, from the pair endpoint when the
+    /// request's `addressType` is `pair`.
+    pub async fn fetch_ohlcv(
+        &self,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        token_address: &str,
+    ) -> Result<BirdeyeOhlcvResponse, reqwest::Error> {
+        let query = format!(
+            "address={}&type={}&time_from={}&time_to={}",
+            token_address,
+            interval,
+            from_date.timestamp(),
+            to_date.timestamp()
+        );
+        let request_url = format!("{}?{}", self.history_endpoint(true), query);
+
+        with_deadline(self.client.get(request_url).headers(self.headers()))
+            .send()
+            .await?
+            .json::<BirdeyeOhlcvResponse>()
+            .await
+    }
+}
+
+#[async_trait]
+impl PriceProvider for BirdeyeProvider {
+    fn name(&self) -> &'static str {
+        "birdeye"
+    }
+
+    fn history_endpoint(&self, ohlc: bool) -> String {
+        if ohlc && address_type::current() == AddressType::Pair {
+            self.pair_ohlcv_url.clone()
+        } else if ohlc {
+            self.ohlcv_url.clone()
+        } else {
+            self.history_url.clone()
+        }
+    }
+
+    async fn fetch_history(
+        &self,
+        token_address: &str,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        ohlc: bool,
+    ) -> Result<ProviderSeries, ProviderError> {
+        let candles = if ohlc {
+            self.fetch_ohlcv(interval, from_date, to_date, token_address)
+                .await?
+                .into_candles()?
+                .iter()
+                .map(Candle::from)
+                .collect()
+        } else {
+            let response = self
+                .fetch_price_history(interval, from_date, to_date, token_address)
+                .await?;
+            match BirdeyeResponse::from(response) {
+                BirdeyeResponse::Success(data) => data.items.iter().map(Candle::from).collect(),
+                BirdeyeResponse::Failure(message) => return Err(message.into()),
+            }
+        };
+
+        Ok(ProviderSeries {
+            provider: self.name(),
+            candles,
+        })
+    }
+
+    async fn fetch_current_price(&self, token_address: &str) -> Result<f64, ProviderError> {
+        if address_type::current() == AddressType::Pair {
+            return Err("Birdeye serves current prices for tokens only, not pairs.".into());
+        }
+        let request = self
+            .client
+            .get(&self.price_url)
+            .query(&[("address", token_address)])
+            .headers(self.headers());
+        let response = with_deadline(request)
+            .send()
+            .await?
+            .json::<BirdeyePriceResponse>()
+            .await?;
+
+        match response.data {
+            Some(price) if response.success => Ok(price.value),
+            _ => Err(response
+                .message
+                .unwrap_or_else(|| "Unknown error".to_string())
+                .into()),
+        }
+    }
+}
+
+//
+// ----------- Tests -----------
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use dotenvy::dotenv;
+    use once_cell::sync::Lazy;
+
+    static INIT: Lazy<()> = Lazy::new(|| {
+        dotenv().ok();
+    });
+
+    fn test_config() -> AppConfig {
+        AppConfig {
+            birdeye_api_key: std::env::var("BIRDEYE_API_KEY")
+                .unwrap_or_else(|_| "dummy".to_string()),
+            birdeye_base_url: std::env::var("BIRDEYE_BASE_URL").unwrap_or_else(|_| {
+                "https://public-api.birdeye.so/token_price/history".to_string()
+            }),
+            app_server_port: 8080,
+            ..AppConfig::default()
+        }
+    }
+
+    fn from_and_to_dates(days: i64) -> (DateTime<Utc>, DateTime<Utc>) {
+        let to = Utc::now().date_naive() - Duration::days(1);
+        let from = to - Duration::days(days - 1);
+        (
+            from.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            to.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        )
+    }
+
+    #[tokio::test]
+    #[ignore = "Expensive - real HTTP call"]
+    async fn test_fetch_price_history_real() {
+        Lazy::force(&INIT);
+        let provider = BirdeyeProvider::new(&test_config());
+        let (from_date, to_date) = from_and_to_dates(10);
+
+        let response = provider
+            .fetch_price_history(
+                Interval::OneDay,
+                from_date,
+                to_date,
+                "So11111111111111111111111111111111111111112",
+            )
+            .await
+            .expect("Birdeye request should succeed");
+
+        let data = response.data.expect("Expected data field present");
+        assert_eq!(data.items.len(), 10);
+    }
+}
@@ -0,0 +1,301 @@
+//! Ordered failover across several providers, with per-provider health counters.
+
+use super::{PriceProvider, ProviderError, ProviderSeries};
+use crate::math::Interval;
+use crate::utils::timestamp_format;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::warn;
+
+/// A series whose latest candle is more than this many intervals older than the requested
+/// end counts as stale.
+pub const STALE_INTERVALS: i64 = 2;
+
+/// Call counters of one provider in a chain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealth {
+    pub provider: String,
+    /// Calls answered with usable data.
+    pub successes: u64,
+    /// Calls that failed outright.
+    pub errors: u64,
+    /// Calls answered with no data or stale data.
+    pub stale: u64,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_success_at: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// How one provider answered a call.
+enum Outcome<'a> {
+    Success,
+    Stale,
+    Error(&'a ProviderError),
+}
+
+/// Serves from the first provider of an ordered list that answers with fresh data, falling
+/// through to the next one on errors and on empty or stale series.
+pub struct ProviderChain {
+    providers: Vec<Arc<dyn PriceProvider>>,
+    health: RwLock<Vec<ProviderHealth>>,
+}
+
+impl ProviderChain {
+    /// Chain trying `providers` in order. Panics when `providers` is empty.
+    pub fn new(providers: Vec<Arc<dyn PriceProvider>>) -> Self {
+        assert!(
+            !providers.is_empty(),
+            "A provider chain needs at least one provider"
+        );
+        let health = providers
+            .iter()
+            .map(|provider| ProviderHealth {
+                provider: provider.name().to_string(),
+                ..ProviderHealth::default()
+            })
+            .collect();
+        Self {
+            providers,
+            health: RwLock::new(health),
+        }
+    }
+
+    async fn record(&self, index: usize, outcome: Outcome<'_>) {
+        let mut health = self.health.write().await;
+        let counters = &mut health[index];
+        match outcome {
+            Outcome::Success => {
+                counters.successes += 1;
+                counters.last_success_at = Some(Utc::now());
+            }
+            Outcome::Stale => counters.stale += 1,
+            Outcome::Error(e) => {
+                counters.errors += 1;
+                counters.last_error_at = Some(Utc::now());
+                counters.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// Whether `series` has no data, or ends more than [`STALE_INTERVALS`] candles before
+/// `to_date` over a range long enough to expect otherwise.
+fn is_stale(
+    series: &ProviderSeries,
+    interval: Interval,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+) -> bool {
+    let tolerance = STALE_INTERVALS * interval.seconds();
+    match series.candles.iter().map(|candle| candle.unix_time).max() {
+        None => true,
+        Some(latest) => {
+            (to_date - from_date).num_seconds() > tolerance
+                && latest < to_date.timestamp() - tolerance
+        }
+    }
+}
+
+fn latest_unix_time(series: &ProviderSeries) -> Option<i64> {
+    series.candles.iter().map(|candle| candle.unix_time).max()
+}
+
+#[async_trait]
+impl PriceProvider for ProviderChain {
+    fn name(&self) -> &'static str {
+        self.providers[0].name()
+    }
+
+    fn history_endpoint(&self, ohlc: bool) -> String {
+        self.providers[0].history_endpoint(ohlc)
+    }
+
+    /// Returns the first fresh series. When every provider fails or answers stale, the
+    /// freshest stale series is returned, or the last error if none answered at all.
+    async fn fetch_history(
+        &self,
+        token_address: &str,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        ohlc: bool,
+    ) -> Result<ProviderSeries, ProviderError> {
+        let mut best_stale: Option<ProviderSeries> = None;
+        let mut last_error = None;
+
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider
+                .fetch_history(token_address, interval, from_date, to_date, ohlc)
+                .await
+            {
+                Ok(series) if !is_stale(&series, interval, from_date, to_date) => {
+                    self.record(index, Outcome::Success).await;
+                    return Ok(series);
+                }
+                Ok(series) => {
+                    self.record(index, Outcome::Stale).await;
+                    warn!(
+                        token_address = %token_address,
+                        provider = provider.name(),
+                        "Provider returned no or stale data, trying the next one"
+                    );
+                    if best_stale
+                        .as_ref()
+                        .is_none_or(|best| latest_unix_time(&series) > latest_unix_time(best))
+                    {
+                        best_stale = Some(series);
+                    }
+                }
+                Err(e) => {
+                    self.record(index, Outcome::Error(&e)).await;
+                    warn!(
+                        token_address = %token_address,
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying the next one"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match (best_stale, last_error) {
+            (Some(series), _) => Ok(series),
+            (None, Some(e)) => Err(e),
+            (None, None) => Err("No price provider is configured.".into()),
+        }
+    }
+
+    async fn fetch_current_price(&self, token_address: &str) -> Result<f64, ProviderError> {
+        let mut last_error = None;
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider.fetch_current_price(token_address).await {
+                Ok(price) => {
+                    self.record(index, Outcome::Success).await;
+                    return Ok(price);
+                }
+                Err(e) => {
+                    self.record(index, Outcome::Error(&e)).await;
+                    warn!(
+                        token_address = %token_address,
+                        provider = provider.name(),
+                        error = %e,
+                        "Provider failed, trying the next one"
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| "No price provider is configured.".into()))
+    }
+
+    async fn health(&self) -> Vec<ProviderHealth> {
+        self.health.read().await.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Candle;
+
+    /// Provider answering every history call with a fixed result.
+    struct FixedProvider {
+        name: &'static str,
+        latest: Option<i64>,
+    }
+
+    #[async_trait]
+    impl PriceProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn history_endpoint(&self, _ohlc: bool) -> String {
+            String::new()
+        }
+
+        async fn fetch_history(
+            &self,
+            _token_address: &str,
+            _interval: Interval,
+            _from_date: DateTime<Utc>,
+            _to_date: DateTime<Utc>,
+            _ohlc: bool,
+        ) -> Result<ProviderSeries, ProviderError> {
+            match self.latest {
+                Some(unix_time) => Ok(ProviderSeries {
+                    provider: self.name,
+                    candles: vec![Candle::from_price(unix_time, 1.0)],
+                }),
+                None => Err("down".into()),
+            }
+        }
+
+        async fn fetch_current_price(&self, _token_address: &str) -> Result<f64, ProviderError> {
+            Err("down".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chain_skips_failed_and_stale_providers() {
+        let to_date = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let from_date = to_date - chrono::Duration::days(30);
+        let chain = ProviderChain::new(vec![
+            Arc::new(FixedProvider {
+                name: "down",
+                latest: None,
+            }),
+            Arc::new(FixedProvider {
+                name: "stale",
+                latest: Some(to_date.timestamp() - 10 * 86_400),
+            }),
+            Arc::new(FixedProvider {
+                name: "fresh",
+                latest: Some(to_date.timestamp()),
+            }),
+        ]);
+
+        let series = chain
+            .fetch_history("token", Interval::OneDay, from_date, to_date, false)
+            .await
+            .unwrap();
+        assert_eq!(series.provider, "fresh");
+
+        let health = chain.health().await;
+        assert_eq!(
+            (health[0].errors, health[0].last_error.as_deref()),
+            (1, Some("down"))
+        );
+        assert_eq!(health[1].stale, 1);
+        assert_eq!(health[2].successes, 1);
+    }
+
+    #[tokio::test]
+    async fn test_chain_returns_freshest_stale_series_when_none_is_fresh() {
+        let to_date = DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let from_date = to_date - chrono::Duration::days(30);
+        let chain = ProviderChain::new(vec![
+            Arc::new(FixedProvider {
+                name: "older",
+                latest: Some(to_date.timestamp() - 20 * 86_400),
+            }),
+            Arc::new(FixedProvider {
+                name: "newer",
+                latest: Some(to_date.timestamp() - 10 * 86_400),
+            }),
+        ]);
+
+        let series = chain
+            .fetch_history("token", Interval::OneDay, from_date, to_date, false)
+            .await
+            .unwrap();
+        assert_eq!(series.provider, "newer");
+    }
+}
@@ -0,0 +1,162 @@
+//! CoinGecko public API provider, looked up by contract address on the request's chain.
+
+use super::{PriceProvider, ProviderError, ProviderSeries};
+use crate::config::AppConfig;
+use crate::math::{Candle, Interval};
+use crate::utils::address_type::{self, AddressType};
+use crate::utils::chain;
+use crate::utils::deadline::with_deadline;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Raw structure of the `market_chart/range` response: `[unix millis, price]` pairs.
+#[derive(Debug, Deserialize)]
+struct MarketChartResponse {
+    prices: Vec<(f64, f64)>,
+}
+
+/// Fetches prices from the CoinGecko API (`market_chart/range` and `simple/token_price`).
+///
+/// CoinGecko serves close prices only, at a granularity it picks from the range length;
+/// prices are resampled to the requested interval by keeping the last one per candle.
+pub struct CoinGeckoProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl CoinGeckoProvider {
+    /// Builds a provider from the CoinGecko URL and optional API key in `config`.
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: config.coingecko_base_url.trim_end_matches('/').to_string(),
+            api_key: config.coingecko_api_key.clone(),
+        }
+    }
+
+    fn get(&self, url: String) -> reqwest::RequestBuilder {
+        let request = self.client.get(url).header("accept", "application/json");
+        match &self.api_key {
+            Some(key) => request.header("x-cg-pro-api-key", key),
+            None => request,
+        }
+    }
+}
+
+/// Buckets `[unix millis, price]` pairs into `interval` candles, keeping the last price of
+/// each and stamping it with the candle's start.
+fn resample(prices: &[(f64, f64)], interval: Interval) -> Vec<Candle> {
+    let seconds = interval.seconds();
+    let mut candles = BTreeMap::new();
+    for &(millis, price) in prices {
+        let unix_time = (millis / 1000.0) as i64;
+        candles.insert(unix_time - unix_time.rem_euclid(seconds), price);
+    }
+    candles
+        .into_iter()
+        .map(|(unix_time, price)| Candle::from_price(unix_time, price))
+        .collect()
+}
+
+#[async_trait]
+impl PriceProvider for CoinGeckoProvider {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    fn history_endpoint(&self, _ohlc: bool) -> String {
+        format!(
+            "{}/coins/{}/contract/{{address}}/market_chart/range",
+            self.base_url,
+            chain::current().coingecko_platform()
+        )
+    }
+
+    async fn fetch_history(
+        &self,
+        token_address: &str,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        ohlc: bool,
+    ) -> Result<ProviderSeries, ProviderError> {
+        if ohlc {
+            return Err("CoinGecko does not provide OHLC candles for arbitrary ranges.".into());
+        }
+        if address_type::current() == AddressType::Pair {
+            return Err("CoinGecko does not serve pair prices.".into());
+        }
+
+        let url = format!(
+            "{}/coins/{}/contract/{}/market_chart/range",
+            self.base_url,
+            chain::current().coingecko_platform(),
+            token_address
+        );
+        let request = self.get(url).query(&[
+            ("vs_currency", "usd".to_string()),
+            ("from", from_date.timestamp().to_string()),
+            ("to", to_date.timestamp().to_string()),
+        ]);
+        let response = with_deadline(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<MarketChartResponse>()
+            .await?;
+
+        Ok(ProviderSeries {
+            provider: self.name(),
+            candles: resample(&response.prices, interval),
+        })
+    }
+
+    async fn fetch_current_price(&self, token_address: &str) -> Result<f64, ProviderError> {
+        if address_type::current() == AddressType::Pair {
+            return Err("CoinGecko does not serve pair prices.".into());
+        }
+        let url = format!(
+            "{}/simple/token_price/{}",
+            self.base_url,
+            chain::current().coingecko_platform()
+        );
+        let request = self.get(url).query(&[
+            ("contract_addresses", token_address),
+            ("vs_currencies", "usd"),
+        ]);
+        let response = with_deadline(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<HashMap<String, HashMap<String, f64>>>()
+            .await?;
+
+        response
+            .into_iter()
+            .find(|(address, _)| address.eq_ignore_ascii_case(token_address))
+            .and_then(|(_, prices)| prices.get("usd").copied())
+            .ok_or_else(|| format!("CoinGecko has no price for {}.", token_address).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_keeps_last_price_per_candle() {
+        let prices = [
+            (1_700_006_400_000.0, 100.0),
+            (1_700_010_000_000.0, 101.0),
+            (1_700_092_800_000.0, 102.0),
+        ];
+        let candles = resample(&prices, Interval::OneDay);
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].unix_time, 1_700_006_400);
+        assert_eq!(candles[0].close, 101.0);
+        assert_eq!(candles[1].close, 102.0);
+    }
+}
@@ -0,0 +1,134 @@
+//! Registry mapping Solana token mints to Pyth price feeds.
+
+use crate::config::AppConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Feeds known out of the box: `(mint, Benchmarks symbol, price feed ID)`.
+const BUILTIN_FEEDS: &[(&str, &str, &str)] = &[
+    (
+        "So11111111111111111111111111111111111111112",
+        "Crypto.SOL/USD",
+        "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+    ),
+    (
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "Crypto.USDC/USD",
+        "eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a",
+    ),
+    (
+        "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+        "Crypto.USDT/USD",
+        "2b89b9dc8fdf9f34709a5b106b472f0f39bb6ca9ce04b0fd7f2e971688e2e53b",
+    ),
+];
+
+/// A Pyth price feed, addressed by symbol in the Benchmarks API and by ID in Hermes.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PythFeed {
+    /// Benchmarks symbol, e.g. `Crypto.SOL/USD`.
+    pub symbol: String,
+    /// Hex price feed ID, lowercase and without the `0x` prefix.
+    pub feed_id: String,
+}
+
+/// Pyth feeds by token mint: the built-in ones plus any configured in `PYTH_PRICE_FEEDS`.
+#[derive(Debug, Clone)]
+pub struct FeedRegistry {
+    feeds: HashMap<String, PythFeed>,
+}
+
+impl FeedRegistry {
+    /// Registry holding only the built-in feeds.
+    pub fn builtin() -> Self {
+        let feeds = BUILTIN_FEEDS
+            .iter()
+            .map(|&(mint, symbol, feed_id)| {
+                (
+                    mint.to_string(),
+                    PythFeed {
+                        symbol: symbol.to_string(),
+                        feed_id: feed_id.to_string(),
+                    },
+                )
+            })
+            .collect();
+        Self { feeds }
+    }
+
+    /// Built-in feeds extended by `overrides`, a JSON object of mint to feed; configured
+    /// feeds replace built-in ones for the same mint.
+    ///
+    /// # Errors
+    /// - Returns a message for malformed JSON or a feed ID that isn't 32 bytes of hex.
+    pub fn with_overrides(overrides: &str) -> Result<Self, String> {
+        let configured: HashMap<String, PythFeed> = serde_json::from_str(overrides)
+            .map_err(|e| format!("PYTH_PRICE_FEEDS is not a valid feed map: {}", e))?;
+
+        let mut registry = Self::builtin();
+        for (mint, mut feed) in configured {
+            feed.feed_id = normalize_feed_id(&feed.feed_id)
+                .ok_or_else(|| format!("PYTH_PRICE_FEEDS has an invalid feed ID for {}.", mint))?;
+            registry.feeds.insert(mint, feed);
+        }
+        Ok(registry)
+    }
+
+    /// Registry for `config`, falling back to the built-in feeds when `PYTH_PRICE_FEEDS` is
+    /// invalid (`AppConfig::from_env` rejects that case up front).
+    pub fn from_config(config: &AppConfig) -> Self {
+        match config.pyth_price_feeds.as_deref().map(Self::with_overrides) {
+            Some(Ok(registry)) => registry,
+            Some(Err(e)) => {
+                warn!(error = %e, "Ignoring configured Pyth feeds");
+                Self::builtin()
+            }
+            None => Self::builtin(),
+        }
+    }
+
+    /// The feed of a token mint, if it has one.
+    pub fn get(&self, mint: &str) -> Option<&PythFeed> {
+        self.feeds.get(mint)
+    }
+}
+
+/// Lowercases a feed ID and strips any `0x` prefix, or `None` unless it is 64 hex digits.
+fn normalize_feed_id(feed_id: &str) -> Option<String> {
+    let hex = feed_id.strip_prefix("0x").unwrap_or(feed_id);
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_extend_builtin_feeds() {
+        let registry = FeedRegistry::with_overrides(
+            r#"{ "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN": {
+                "symbol": "Crypto.JUP/USD",
+                "feedId": "0x0A0408D619E9380ABAD35060F9192039ED5042FA6F82301D0E48BB52BE830996"
+            } }"#,
+        )
+        .expect("should parse");
+
+        assert_eq!(
+            registry.get("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN").unwrap().feed_id,
+            "0a0408d619e9380abad35060f9192039ed5042fa6f82301d0e48bb52be830996"
+        );
+        assert!(registry.get("So11111111111111111111111111111111111111112").is_some());
+        assert!(registry.get("unknownMint").is_none());
+    }
+
+    #[test]
+    fn test_invalid_feed_id_is_rejected() {
+        assert!(FeedRegistry::with_overrides(
+            r#"{ "mint": { "symbol": "Crypto.X/USD", "feedId": "0x1234" } }"#
+        )
+        .is_err());
+        assert!(FeedRegistry::with_overrides("not json").is_err());
+    }
+}
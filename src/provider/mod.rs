@@ -0,0 +1,126 @@
+//! # Price Providers
+//!
+//! Price data sources behind a common [`PriceProvider`] trait, so handlers and the
+//! background cache fetch series without knowing which upstream API serves them.
+
+use crate::config::AppConfig;
+use crate::math::{Candle, Interval};
+use async_trait::async_trait;
+use birdeye::BirdeyeProvider;
+use chain::{ProviderChain, ProviderHealth};
+use chrono::{DateTime, Utc};
+use coingecko::CoinGeckoProvider;
+use pyth::PythProvider;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub mod birdeye;
+pub mod chain;
+pub mod coingecko;
+pub mod feed_registry;
+pub mod pyth;
+pub mod report;
+
+/// Error returned by a provider call, carrying the upstream failure message.
+pub type ProviderError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Providers selectable by name in configuration.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    Birdeye,
+    #[serde(rename = "coingecko")]
+    CoinGecko,
+    Pyth,
+}
+
+impl ProviderKind {
+    /// Every selectable provider.
+    pub const ALL: [ProviderKind; 3] = [ProviderKind::Birdeye, ProviderKind::CoinGecko, ProviderKind::Pyth];
+
+    /// Name the provider of this kind reports through [`PriceProvider::name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            ProviderKind::Birdeye => "birdeye",
+            ProviderKind::CoinGecko => "coingecko",
+            ProviderKind::Pyth => "pyth",
+        }
+    }
+
+    /// Builds the provider of this kind from `config`.
+    pub fn provider(self, config: &AppConfig) -> Arc<dyn PriceProvider> {
+        match self {
+            ProviderKind::Birdeye => Arc::new(BirdeyeProvider::new(config)),
+            ProviderKind::CoinGecko => Arc::new(CoinGeckoProvider::new(config)),
+            ProviderKind::Pyth => Arc::new(PythProvider::new(config)),
+        }
+    }
+}
+
+/// Which upstream environment the providers point at.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderEnvironment {
+    #[default]
+    Production,
+    /// Sandbox or testnet endpoints, for integration environments. Responses are labeled.
+    Sandbox,
+}
+
+impl ProviderEnvironment {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProviderEnvironment::Production => "production",
+            ProviderEnvironment::Sandbox => "sandbox",
+        }
+    }
+}
+
+/// A fetched price series and the provider that actually served it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderSeries {
+    pub provider: &'static str,
+    pub candles: Vec<Candle>,
+}
+
+/// Builds the configured provider chain: `PROVIDERS` in order when set, otherwise Birdeye
+/// backed by `FALLBACK_PROVIDER` if set.
+pub fn from_config(config: &AppConfig) -> Arc<dyn PriceProvider> {
+    let providers = config
+        .provider_order()
+        .into_iter()
+        .map(|kind| kind.provider(config))
+        .collect();
+    Arc::new(ProviderChain::new(providers))
+}
+
+/// An upstream source of token prices.
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// Name the provider is reported under, e.g. `birdeye`.
+    fn name(&self) -> &'static str;
+
+    /// URL [`PriceProvider::fetch_history`] calls for close-only or OHLC series.
+    fn history_endpoint(&self, ohlc: bool) -> String;
+
+    /// Fetches the price series of a token over `[from_date, to_date]`.
+    ///
+    /// With `ohlc`, candles carry real open/high/low data; otherwise they are built from
+    /// a single price each (see [`Candle::from_price`]).
+    async fn fetch_history(
+        &self,
+        token_address: &str,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        ohlc: bool,
+    ) -> Result<ProviderSeries, ProviderError>;
+
+    /// Fetches the latest price of a token.
+    async fn fetch_current_price(&self, token_address: &str) -> Result<f64, ProviderError>;
+
+    /// Call counters per underlying provider, for providers that track them.
+    async fn health(&self) -> Vec<ProviderHealth> {
+        Vec::new()
+    }
+}
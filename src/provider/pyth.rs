@@ -0,0 +1,204 @@
+//! Pyth provider: history from the Benchmarks API, latest prices from Hermes.
+
+use super::feed_registry::{FeedRegistry, PythFeed};
+use super::{PriceProvider, ProviderError, ProviderSeries};
+use crate::config::AppConfig;
+use crate::math::{Candle, Interval};
+use crate::utils::address_type::{self, AddressType};
+use crate::utils::deadline::with_deadline;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Raw structure of the Benchmarks TradingView `history` response: parallel arrays, one
+/// entry per candle. `s` is `ok`, `no_data` or `error`.
+#[derive(Debug, Deserialize)]
+struct BenchmarksHistoryResponse {
+    s: String,
+    #[serde(default)]
+    t: Vec<i64>,
+    #[serde(default)]
+    o: Vec<f64>,
+    #[serde(default)]
+    h: Vec<f64>,
+    #[serde(default)]
+    l: Vec<f64>,
+    #[serde(default)]
+    c: Vec<f64>,
+    #[serde(default)]
+    errmsg: Option<String>,
+}
+
+/// Raw structure of the Hermes `updates/price/latest` response with `parsed=true`.
+#[derive(Debug, Deserialize)]
+struct HermesLatestResponse {
+    parsed: Vec<HermesPriceUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HermesPriceUpdate {
+    price: HermesPrice,
+}
+
+/// A Pyth price as an integer mantissa and a base-10 exponent.
+#[derive(Debug, Deserialize)]
+struct HermesPrice {
+    price: String,
+    expo: i32,
+}
+
+impl HermesPrice {
+    fn value(&self) -> Result<f64, ProviderError> {
+        let mantissa: f64 = self.price.parse()?;
+        Ok(mantissa * 10f64.powi(self.expo))
+    }
+}
+
+/// Benchmarks `resolution` value for an interval.
+fn resolution(interval: Interval) -> &'static str {
+    match interval {
+        Interval::FiveMinutes => "5",
+        Interval::FifteenMinutes => "15",
+        Interval::OneHour => "60",
+        Interval::OneDay => "1D",
+    }
+}
+
+/// Fetches prices for tokens with a Pyth feed in the [`FeedRegistry`].
+///
+/// Benchmarks serves full OHLC candles, so range-based estimators can be served too.
+pub struct PythProvider {
+    client: reqwest::Client,
+    benchmarks_url: String,
+    hermes_url: String,
+    registry: FeedRegistry,
+}
+
+impl PythProvider {
+    /// Builds a provider from the Pyth URLs and feed registry in `config`.
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            benchmarks_url: config.pyth_benchmarks_url.trim_end_matches('/').to_string(),
+            hermes_url: config.pyth_hermes_url.trim_end_matches('/').to_string(),
+            registry: FeedRegistry::from_config(config),
+        }
+    }
+
+    fn feed(&self, token_address: &str) -> Result<&PythFeed, ProviderError> {
+        if address_type::current() == AddressType::Pair {
+            return Err("Pyth feeds are per token; pairs are not supported.".into());
+        }
+        self.registry
+            .get(token_address)
+            .ok_or_else(|| format!("No Pyth price feed is registered for {}.", token_address).into())
+    }
+}
+
+#[async_trait]
+impl PriceProvider for PythProvider {
+    fn name(&self) -> &'static str {
+        "pyth"
+    }
+
+    fn history_endpoint(&self, _ohlc: bool) -> String {
+        format!("{}/v1/shims/tradingview/history", self.benchmarks_url)
+    }
+
+    async fn fetch_history(
+        &self,
+        token_address: &str,
+        interval: Interval,
+        from_date: DateTime<Utc>,
+        to_date: DateTime<Utc>,
+        ohlc: bool,
+    ) -> Result<ProviderSeries, ProviderError> {
+        let feed = self.feed(token_address)?;
+        let request = self.client.get(self.history_endpoint(ohlc)).query(&[
+            ("symbol", feed.symbol.clone()),
+            ("resolution", resolution(interval).to_string()),
+            ("from", from_date.timestamp().to_string()),
+            ("to", to_date.timestamp().to_string()),
+        ]);
+        let response = with_deadline(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<BenchmarksHistoryResponse>()
+            .await?;
+
+        let candles = match response.s.as_str() {
+            "ok" => response
+                .t
+                .iter()
+                .zip(&response.c)
+                .enumerate()
+                .map(|(i, (&unix_time, &close))| {
+                    let price = |values: &[f64]| values.get(i).copied().unwrap_or(close);
+                    if ohlc {
+                        Candle {
+                            unix_time,
+                            open: price(&response.o),
+                            high: price(&response.h),
+                            low: price(&response.l),
+                            close,
+                        }
+                    } else {
+                        Candle::from_price(unix_time, close)
+                    }
+                })
+                .collect(),
+            "no_data" => Vec::new(),
+            _ => {
+                return Err(format!(
+                    "Pyth Benchmarks error: {}",
+                    response.errmsg.unwrap_or_else(|| response.s.clone())
+                )
+                .into())
+            }
+        };
+
+        Ok(ProviderSeries {
+            provider: self.name(),
+            candles,
+        })
+    }
+
+    async fn fetch_current_price(&self, token_address: &str) -> Result<f64, ProviderError> {
+        let feed = self.feed(token_address)?;
+        let url = format!("{}/v2/updates/price/latest", self.hermes_url);
+        let request = self
+            .client
+            .get(url)
+            .query(&[("ids[]", feed.feed_id.as_str()), ("parsed", "true")]);
+        let response = with_deadline(request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<HermesLatestResponse>()
+            .await?;
+
+        response
+            .parsed
+            .first()
+            .ok_or_else(|| -> ProviderError {
+                format!("Hermes returned no price for {}.", feed.symbol).into()
+            })?
+            .price
+            .value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hermes_price_applies_exponent() {
+        let price = HermesPrice {
+            price: "14512345678".to_string(),
+            expo: -8,
+        };
+        assert!((price.value().unwrap() - 145.12345678).abs() < 1e-9);
+    }
+}
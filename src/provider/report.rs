@@ -0,0 +1,72 @@
+//! Reporting which providers a response's price data came from, and from which environment.
+
+use super::ProviderEnvironment;
+use crate::state::AppState;
+use axum::{
+    extract::{Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+/// Response header listing the providers that served the request's price data.
+pub const PROVIDER_HEADER: &str = "x-price-provider";
+
+/// Response header labeling responses served from sandbox providers.
+pub const ENVIRONMENT_HEADER: &str = "x-provider-environment";
+
+tokio::task_local! {
+    /// Providers whose data the request being handled on this task has used.
+    static USED: RefCell<BTreeSet<&'static str>>;
+}
+
+/// Notes that the current request's response is built from `provider`'s data.
+///
+/// Does nothing outside a request, e.g. in the background refresh task.
+pub fn record(provider: &'static str) {
+    let _ = USED.try_with(|used| used.borrow_mut().insert(provider));
+}
+
+/// Providers recorded so far for the current request, sorted; empty outside a request.
+pub fn recorded() -> Vec<&'static str> {
+    USED.try_with(|used| used.borrow().iter().copied().collect())
+        .unwrap_or_default()
+}
+
+/// Middleware adding `X-Price-Provider` with the providers the handler recorded, comma
+/// separated, so callers can tell when a fallback provider answered.
+pub async fn report_price_provider(request: Request, next: Next) -> Response {
+    let (mut response, used) = USED
+        .scope(RefCell::new(BTreeSet::new()), async {
+            let response = next.run(request).await;
+            (response, USED.with(|used| used.take()))
+        })
+        .await;
+
+    if !used.is_empty() {
+        let providers = used.into_iter().collect::<Vec<_>>().join(",");
+        if let Ok(value) = HeaderValue::from_str(&providers) {
+            response.headers_mut().insert(PROVIDER_HEADER, value);
+        }
+    }
+    response
+}
+
+/// Middleware adding `X-Provider-Environment: sandbox` to every response when providers point
+/// at sandbox endpoints, so test data can't pass for production data.
+pub async fn label_provider_environment(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let environment = state.config.provider_environment;
+    if environment != ProviderEnvironment::Production {
+        response
+            .headers_mut()
+            .insert(ENVIRONMENT_HEADER, HeaderValue::from_static(environment.as_str()));
+    }
+    response
+}
@@ -0,0 +1,110 @@
+//! Backtests of volatility-based alert rules against a historical price series.
+
+use crate::math::close_to_close::CloseToClose;
+use crate::math::rolling::rolling_volatility;
+use crate::math::{sorted_valid_candles, Candle};
+use crate::utils::timestamp_format;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Flags when short-window volatility exceeds `ratio` times long-window volatility,
+/// e.g. "7-day vol above 2× 90-day vol".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolRatioRule {
+    /// Returns per short volatility window.
+    pub short_window: usize,
+    /// Returns per long volatility window.
+    pub long_window: usize,
+    pub ratio: f64,
+}
+
+/// A candle on which the rule started firing, with the move that followed.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalHit {
+    #[serde(serialize_with = "timestamp_format::serialize")]
+    pub unix_time: i64,
+    /// Annualized short-window volatility, in percent.
+    pub short_volatility: f64,
+    /// Annualized long-window volatility, in percent.
+    pub long_volatility: f64,
+    /// Simple return over the following `forward` candles, `None` when the series ends first.
+    pub forward_return: Option<f64>,
+}
+
+/// Evaluates `rule` on every candle with a full long window and reports the candles where
+/// it turned on (was off on the previous candle), so a lasting spike counts once.
+///
+/// Volatility is close-to-close; `forward` is the number of candles the subsequent move
+/// is measured over.
+pub fn backtest_vol_ratio(
+    candles: &[Candle],
+    rule: &VolRatioRule,
+    forward: usize,
+    periods_per_year: f64,
+) -> Vec<SignalHit> {
+    let candles = sorted_valid_candles(candles);
+    let short: HashMap<i64, f64> =
+        rolling_volatility(&candles, rule.short_window, &CloseToClose, periods_per_year)
+            .into_iter()
+            .map(|point| (point.unix_time, point.volatility))
+            .collect();
+    let closes: HashMap<i64, usize> = candles
+        .iter()
+        .enumerate()
+        .map(|(i, candle)| (candle.unix_time, i))
+        .collect();
+
+    let mut hits = Vec::new();
+    let mut firing = false;
+    for long in rolling_volatility(&candles, rule.long_window, &CloseToClose, periods_per_year) {
+        let Some(&short_volatility) = short.get(&long.unix_time) else {
+            continue;
+        };
+        let fires = short_volatility > rule.ratio * long.volatility;
+        if fires && !firing {
+            let index = closes[&long.unix_time];
+            hits.push(SignalHit {
+                unix_time: long.unix_time,
+                short_volatility,
+                long_volatility: long.volatility,
+                forward_return: candles
+                    .get(index + forward)
+                    .map(|later| later.close / candles[index].close - 1.0),
+            });
+        }
+        firing = fires;
+    }
+
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtest_reports_onsets_with_forward_moves() {
+        let prices = [100.0, 101.0, 100.0, 101.0, 100.0, 101.0, 120.0, 100.0, 101.0, 100.0];
+        let candles: Vec<Candle> = prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| Candle::from_price(i as i64, price))
+            .collect();
+        let rule = VolRatioRule {
+            short_window: 2,
+            long_window: 4,
+            ratio: 1.3,
+        };
+
+        let hits = backtest_vol_ratio(&candles, &rule, 2, 365.0);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].unix_time, 7);
+        assert!(hits[0].short_volatility > 1.3 * hits[0].long_volatility);
+        assert_eq!(hits[0].forward_return, Some(0.0));
+
+        let late = backtest_vol_ratio(&candles, &rule, 3, 365.0);
+        assert_eq!(late[0].forward_return, None);
+    }
+}
@@ -0,0 +1,97 @@
+//! Peak-to-trough drawdowns of a price series.
+
+use crate::math::{sorted_valid_candles, Candle};
+use crate::utils::timestamp_format;
+use serde::{Deserialize, Serialize};
+
+/// Drawdown statistics of a price series. Drawdowns are fractions below the running peak.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownSummary {
+    /// Largest peak-to-trough decline.
+    pub max_drawdown: f64,
+    /// Timestamp of the peak preceding the largest decline.
+    #[serde(serialize_with = "timestamp_format::serialize")]
+    pub peak_time: i64,
+    /// Timestamp of the largest decline's trough.
+    #[serde(serialize_with = "timestamp_format::serialize")]
+    pub trough_time: i64,
+    /// Timestamp the price first regained the peak after the trough, if it has.
+    #[serde(serialize_with = "timestamp_format::serialize_option")]
+    pub recovery_time: Option<i64>,
+    /// Seconds from the peak to recovery, or to the last candle if not yet recovered.
+    pub duration_secs: i64,
+    /// Decline of the latest close from the running peak.
+    pub current_drawdown: f64,
+}
+
+/// Computes drawdown statistics from closing prices.
+///
+/// Returns `None` for fewer than two valid candles.
+pub fn drawdown(candles: &[Candle]) -> Option<DrawdownSummary> {
+    let candles = sorted_valid_candles(candles);
+    if candles.len() < 2 {
+        return None;
+    }
+
+    let mut peak = candles[0];
+    // (decline, peak, trough) of the largest drawdown so far
+    let mut worst = (0.0, candles[0], candles[0]);
+    for candle in &candles {
+        if candle.close > peak.close {
+            peak = *candle;
+        }
+        let decline = 1.0 - candle.close / peak.close;
+        if decline > worst.0 {
+            worst = (decline, peak, *candle);
+        }
+    }
+    let (max_drawdown, worst_peak, trough) = worst;
+
+    let recovery_time = candles
+        .iter()
+        .find(|candle| candle.unix_time > trough.unix_time && candle.close >= worst_peak.close)
+        .map(|candle| candle.unix_time);
+    let last = candles[candles.len() - 1];
+
+    Some(DrawdownSummary {
+        max_drawdown,
+        peak_time: worst_peak.unix_time,
+        trough_time: trough.unix_time,
+        recovery_time,
+        duration_secs: recovery_time.unwrap_or(last.unix_time) - worst_peak.unix_time,
+        current_drawdown: 1.0 - last.close / peak.close,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle::from_price(i as i64, close))
+            .collect()
+    }
+
+    #[test]
+    fn test_drawdown_finds_largest_decline_and_recovery() {
+        let summary = drawdown(&candles(&[100.0, 120.0, 90.0, 110.0, 125.0, 100.0])).unwrap();
+
+        assert!((summary.max_drawdown - 0.25).abs() < 1e-12);
+        assert_eq!((summary.peak_time, summary.trough_time), (1, 2));
+        assert_eq!(summary.recovery_time, Some(4));
+        assert_eq!(summary.duration_secs, 3);
+        assert!((summary.current_drawdown - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unrecovered_drawdown_runs_to_last_candle() {
+        let summary = drawdown(&candles(&[100.0, 80.0, 90.0])).unwrap();
+        assert_eq!(summary.recovery_time, None);
+        assert_eq!(summary.duration_secs, 2);
+        assert!(drawdown(&candles(&[100.0])).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+//! Risk metrics built on the return series from [`crate::math`].
+
+pub mod backtest;
+pub mod drawdown;
+pub mod ratios;
+pub mod stress;
+pub mod value_at_risk;
@@ -0,0 +1,60 @@
+//! Risk-adjusted return ratios.
+
+use crate::math::stats::{mean, variance};
+
+/// Per-period returns in excess of an annual risk-free rate spread evenly over the year.
+fn excess_returns(returns: &[f64], annual_risk_free_rate: f64, periods_per_year: f64) -> Vec<f64> {
+    let per_period_rate = annual_risk_free_rate / periods_per_year;
+    returns.iter().map(|r| r - per_period_rate).collect()
+}
+
+/// Annualized Sharpe ratio of per-period simple returns.
+///
+/// Returns `None` for fewer than two returns or when the returns don't vary.
+pub fn sharpe_ratio(returns: &[f64], annual_risk_free_rate: f64, periods_per_year: f64) -> Option<f64> {
+    let excess = excess_returns(returns, annual_risk_free_rate, periods_per_year);
+    let deviation = variance(&excess)?.sqrt();
+    if deviation == 0.0 {
+        return None;
+    }
+    Some(mean(&excess)? / deviation * periods_per_year.sqrt())
+}
+
+/// Annualized Sortino ratio of per-period simple returns.
+///
+/// Only returns below the risk-free rate count towards the (downside) deviation. Returns
+/// `None` for fewer than two returns or when no period underperformed the risk-free rate.
+pub fn sortino_ratio(returns: &[f64], annual_risk_free_rate: f64, periods_per_year: f64) -> Option<f64> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let excess = excess_returns(returns, annual_risk_free_rate, periods_per_year);
+    let downside_deviation = mean(&excess.iter().map(|r| r.min(0.0).powi(2)).collect::<Vec<_>>())?.sqrt();
+    if downside_deviation == 0.0 {
+        return None;
+    }
+    Some(mean(&excess)? / downside_deviation * periods_per_year.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharpe_and_sortino() {
+        let returns = [0.02, -0.01, 0.03, -0.02, 0.01];
+        let sharpe = sharpe_ratio(&returns, 0.0, 1.0).unwrap();
+        let sortino = sortino_ratio(&returns, 0.0, 1.0).unwrap();
+
+        // mean 0.006, sample std 0.02074, downside deviation sqrt(0.0005 / 5)
+        assert!((sharpe - 0.006 / 0.000_43f64.sqrt()).abs() < 1e-9);
+        assert!((sortino - 0.006 / 0.0001f64.sqrt()).abs() < 1e-9);
+        assert!(sortino_ratio(&[0.01, 0.02], 0.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_risk_free_rate_lowers_sharpe() {
+        let returns = [0.02, -0.01, 0.03, -0.02, 0.01];
+        assert!(sharpe_ratio(&returns, 0.5, 10.0).unwrap() < sharpe_ratio(&returns, 0.0, 10.0).unwrap());
+    }
+}
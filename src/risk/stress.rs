@@ -0,0 +1,88 @@
+//! Stress scenarios applied to a historical log-return series.
+
+use crate::math::stats::mean;
+use serde::{Deserialize, Serialize};
+
+/// A hypothetical shock applied to the most recent part of a return series.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Shock {
+    /// One extra day with the given simple return, e.g. `-0.3` for a 30% drop.
+    Jump { change: f64 },
+    /// Deviations from the mean return of the last `days` returns scaled by `multiplier`,
+    /// e.g. `2.0` for doubled daily volatility.
+    VolMultiplier { multiplier: f64, days: usize },
+}
+
+impl Shock {
+    /// Checks the shock's parameters, returning a message naming the bad field.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Shock::Jump { change } if !(change.is_finite() && change > -1.0) => {
+                Err("jump change must be greater than -1.".to_string())
+            }
+            Shock::VolMultiplier { multiplier, .. }
+                if !(multiplier.is_finite() && multiplier >= 0.0) =>
+            {
+                Err("volMultiplier multiplier must be a non-negative number.".to_string())
+            }
+            Shock::VolMultiplier { days: 0, .. } => {
+                Err("volMultiplier days must be greater than 0.".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Applies `shocks` in order to chronological per-period `log_returns`.
+///
+/// Volatility shocks scale around the mean of the unshocked series so a later jump doesn't
+/// shift the centre; a window longer than the series covers all of it.
+pub fn apply_shocks(log_returns: &[f64], shocks: &[Shock]) -> Vec<f64> {
+    let centre = mean(log_returns).unwrap_or(0.0);
+    let mut stressed = log_returns.to_vec();
+
+    for shock in shocks {
+        match *shock {
+            Shock::Jump { change } => stressed.push(change.ln_1p()),
+            Shock::VolMultiplier { multiplier, days } => {
+                let start = stressed.len().saturating_sub(days);
+                for r in &mut stressed[start..] {
+                    *r = centre + (*r - centre) * multiplier;
+                }
+            }
+        }
+    }
+
+    stressed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shocks_apply_in_order_to_the_tail() {
+        let returns = [0.01, -0.01, 0.02, 0.0];
+        let stressed = apply_shocks(
+            &returns,
+            &[
+                Shock::VolMultiplier { multiplier: 2.0, days: 2 },
+                Shock::Jump { change: -0.3 },
+            ],
+        );
+
+        assert_eq!(stressed.len(), 5);
+        assert_eq!(&stressed[..2], &returns[..2]);
+        assert!((stressed[2] - (0.005 + 0.015 * 2.0)).abs() < 1e-12);
+        assert!((stressed[3] + 0.005).abs() < 1e-12);
+        assert!((stressed[4] - 0.7_f64.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_shock_validation() {
+        assert!(Shock::Jump { change: -1.0 }.validate().is_err());
+        assert!(Shock::VolMultiplier { multiplier: 2.0, days: 0 }.validate().is_err());
+        assert!(Shock::VolMultiplier { multiplier: 0.5, days: 14 }.validate().is_ok());
+    }
+}
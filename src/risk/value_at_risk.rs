@@ -0,0 +1,93 @@
+//! Value-at-Risk and Conditional Value-at-Risk (expected shortfall).
+//!
+//! Both are expressed as a loss fraction of the position's value over the horizon
+//! (0.05 = a 5% loss), so a positive number is a loss.
+
+use crate::math::normal;
+use crate::math::stats::{mean, quantile, variance};
+use serde::{Deserialize, Serialize};
+
+/// VaR and CVaR at one confidence level and horizon.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskEstimate {
+    /// Loss not exceeded with probability `confidence`.
+    pub var: f64,
+    /// Expected loss in the tail beyond `var`.
+    pub cvar: f64,
+}
+
+/// Parametric (variance-covariance) VaR assuming normally distributed log returns.
+///
+/// The per-period mean and standard deviation of `log_returns` are scaled to `horizon`
+/// periods with the square-root-of-time rule. Returns `None` for fewer than two returns
+/// or a confidence outside `(0, 1)`.
+pub fn parametric(log_returns: &[f64], confidence: f64, horizon: usize) -> Option<RiskEstimate> {
+    let z = normal::inverse_cdf(1.0 - confidence)?;
+    let horizon = horizon as f64;
+    let drift = mean(log_returns)? * horizon;
+    let deviation = variance(log_returns)?.sqrt() * horizon.sqrt();
+
+    // Worst-case log return at the quantile, and the expected log return below it
+    let tail_log_return = drift + z * deviation;
+    let expected_tail_log_return = drift - deviation * normal::pdf(z) / (1.0 - confidence);
+
+    Some(RiskEstimate {
+        var: -tail_log_return.exp_m1(),
+        cvar: -expected_tail_log_return.exp_m1(),
+    })
+}
+
+/// Historical-simulation VaR over overlapping `horizon`-period returns.
+///
+/// Returns `None` when fewer than two horizon returns can be formed or the confidence
+/// is outside `(0, 1)`.
+pub fn historical(log_returns: &[f64], confidence: f64, horizon: usize) -> Option<RiskEstimate> {
+    if !(confidence > 0.0 && confidence < 1.0) || horizon == 0 {
+        return None;
+    }
+
+    let losses: Vec<f64> = log_returns
+        .windows(horizon)
+        .map(|window| -window.iter().sum::<f64>().exp_m1())
+        .collect();
+    if losses.len() < 2 {
+        return None;
+    }
+
+    let var = quantile(&losses, confidence)?;
+    let tail: Vec<f64> = losses.iter().copied().filter(|loss| *loss >= var).collect();
+
+    Some(RiskEstimate {
+        var,
+        cvar: mean(&tail)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_historical_var_picks_tail_loss() {
+        let returns: Vec<f64> = (1..=100).map(|i| (i as f64 - 50.0) / 1000.0).collect();
+        let estimate = historical(&returns, 0.95, 1).unwrap();
+
+        // The 95th percentile loss interpolates between log returns of -0.044 and -0.045
+        assert!((estimate.var - (-(-0.04405f64).exp_m1())).abs() < 1e-4);
+        assert!(estimate.cvar > estimate.var);
+    }
+
+    #[test]
+    fn test_parametric_var_grows_with_horizon_and_confidence() {
+        let returns = [0.01, -0.02, 0.015, -0.005, 0.0, 0.02, -0.03];
+        let one_day = parametric(&returns, 0.95, 1).unwrap();
+        let ten_day = parametric(&returns, 0.95, 10).unwrap();
+        let stricter = parametric(&returns, 0.99, 1).unwrap();
+
+        assert!(ten_day.var > one_day.var);
+        assert!(stricter.var > one_day.var);
+        assert!(one_day.cvar > one_day.var);
+        assert!(parametric(&returns, 1.0, 1).is_none());
+    }
+}
@@ -0,0 +1,438 @@
+//! # Admin Handlers
+//!
+//! Export and import of the service's mutable state, so one environment (e.g. staging)
+//! can be seeded from another, and management of calculation presets. The watchlist of
+//! tracked tokens and the presets are the only state the service keeps; everything else
+//! comes from configuration. Also checks cached volatility against a fresh computation and
+//! adds or removes tracked tokens at runtime, and reports the volatility cache's statistics.
+
+use crate::background::volatility_cache::{CacheStats, CACHED_WINDOW_DAYS, RecomputedVolatility};
+use crate::errors::api_error::ApiError;
+use crate::extractors::admin_auth::AdminAuth;
+use crate::extractors::json_extractor::ApiJson;
+use crate::math::Interval;
+use crate::presets::{self, Preset};
+use crate::state::AppState;
+use crate::utils::{base58, timestamp_format};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{error, info, instrument, warn};
+
+/// Format version of [`StateSnapshot`], bumped whenever its shape changes.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// Portable snapshot of the service's state.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Tokens kept refreshed by the background task.
+    pub watchlist: Vec<WatchedToken>,
+    /// Calculation presets keyed by name. Absent from snapshots taken before presets existed.
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// A tracked token in a [`StateSnapshot`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedToken {
+    pub token_address: String,
+    /// Whether the token is kept however long it goes unrequested.
+    pub pinned: bool,
+}
+
+/// Result of importing a snapshot.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResponse {
+    /// Tokens newly added to the watchlist.
+    pub imported: Vec<String>,
+    /// Tokens that were already tracked.
+    pub skipped: Vec<String>,
+    /// Tokens whose initial volatility fetch failed; they are not tracked.
+    pub failed: Vec<String>,
+    /// Presets defined or replaced by the import.
+    pub presets: Vec<String>,
+}
+
+/// Token to start tracking.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackTokenRequest {
+    pub token_address: String,
+}
+
+/// A tracked token and its freshly cached volatility.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackTokenResponse {
+    pub token_address: String,
+    /// 90-day volatility.
+    pub volatility: f64,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub last_updated: DateTime<Utc>,
+}
+
+/// Volatility values of a token, by where they came from.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityValues {
+    /// 90-day volatility.
+    pub volatility: f64,
+    /// Volatility by window length in days; `null` where the history is too short.
+    pub windows: BTreeMap<u32, Option<f64>>,
+    /// Provider that served the series the values were computed from.
+    pub provider: String,
+}
+
+/// Cached values of a token next to ones recomputed from a fresh provider fetch.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyResponse {
+    pub token_address: String,
+    pub cached: VolatilityValues,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub cached_at: DateTime<Utc>,
+    pub fresh: VolatilityValues,
+    /// Candles in the fresh series.
+    pub data_points: usize,
+    /// Fresh minus cached 90-day volatility, in percentage points.
+    pub delta: f64,
+    /// `delta` relative to the cached value, in percent; `null` when the cached value is 0.
+    pub relative_delta: Option<f64>,
+    /// Fresh minus cached volatility by window; `null` where either is missing.
+    pub window_deltas: BTreeMap<u32, Option<f64>>,
+}
+
+/// Recomputes a tracked token's volatility from a fresh provider fetch and compares it
+/// against the cached value. The cache is left untouched.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `400 Bad Request` for requests the cache cannot answer (another chain or
+///   `addressType=pair`), or when the fresh series is too short.
+/// - Returns `404 Not Found` when the token is not tracked.
+/// - Returns `500 Internal Server Error` for unexpected provider failures.
+#[instrument(skip(state))]
+pub async fn verify_token(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(token_address): Path<String>,
+) -> Result<Json<VerifyResponse>, ApiError> {
+    let cache = &state.volatility_cache;
+    if !cache.covers(state.config.volatility_estimator, Interval::OneDay) {
+        return Err(ApiError::InvalidQuery(
+            "The volatility cache holds Solana token values only.".to_string(),
+        ));
+    }
+    let (Some(volatility), Some(cached_at), Some(provider)) = (
+        cache.peek_volatility(&token_address).await,
+        cache.last_updated(&token_address).await,
+        cache.provider_of(&token_address).await,
+    ) else {
+        return Err(ApiError::NotFound(format!(
+            "Token '{}' is not tracked.",
+            token_address
+        )));
+    };
+    let mut cached_windows = BTreeMap::new();
+    for days in CACHED_WINDOW_DAYS {
+        cached_windows.insert(days, cache.get_window_volatility(&token_address, days).await);
+    }
+    let cached = VolatilityValues {
+        volatility,
+        windows: cached_windows,
+        provider: provider.to_string(),
+    };
+
+    let RecomputedVolatility {
+        volatility,
+        windows,
+        provider,
+        data_points,
+    } = cache
+        .recompute(&token_address)
+        .await
+        .map_err(|e| {
+            error!(token_address = %token_address, error = %e, "Fresh fetch for verification failed");
+            ApiError::InternalServerError
+        })?
+        .ok_or(ApiError::NotEnoughData)?;
+    let fresh = VolatilityValues {
+        volatility,
+        windows: CACHED_WINDOW_DAYS.into_iter().zip(windows).collect(),
+        provider: provider.to_string(),
+    };
+
+    let delta = fresh.volatility - cached.volatility;
+    let relative_delta = (cached.volatility != 0.0).then(|| delta / cached.volatility * 100.0);
+    let window_deltas = CACHED_WINDOW_DAYS
+        .into_iter()
+        .map(|days| {
+            let delta = match (fresh.windows[&days], cached.windows[&days]) {
+                (Some(fresh), Some(cached)) => Some(fresh - cached),
+                _ => None,
+            };
+            (days, delta)
+        })
+        .collect();
+
+    if delta != 0.0 {
+        warn!(token_address = %token_address, delta = %delta, "Cached volatility differs from a fresh computation");
+    } else {
+        info!(token_address = %token_address, "Cached volatility matches a fresh computation");
+    }
+
+    Ok(Json(VerifyResponse {
+        token_address,
+        cached,
+        cached_at,
+        fresh,
+        data_points,
+        delta,
+        relative_delta,
+        window_deltas,
+    }))
+}
+
+/// Adds a token to the set the background task keeps refreshed, computing its volatility
+/// right away.
+///
+/// Responds `201 Created` for a newly tracked token and `200 OK` when it already was.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `400 Bad Request` for a malformed address, requests the cache cannot answer
+///   (another chain or `addressType=pair`), or when the token's history is too short.
+/// - Returns `500 Internal Server Error` for unexpected provider failures.
+#[instrument(skip(state))]
+pub async fn track_token(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<TrackTokenRequest>,
+) -> Result<(StatusCode, Json<TrackTokenResponse>), ApiError> {
+    let cache = &state.volatility_cache;
+    if !cache.covers(state.config.volatility_estimator, Interval::OneDay) {
+        return Err(ApiError::InvalidQuery(
+            "The volatility cache holds Solana token values only.".to_string(),
+        ));
+    }
+    let token_address = request.token_address;
+    base58::validate_pubkey(&token_address).map_err(ApiError::InvalidBody)?;
+
+    let tracked = cache.last_updated(&token_address).await.is_some();
+    // Recompute stale values too, so the response never reports a value the cache won't serve
+    if cache.get_volatility(&token_address).await.is_none() {
+        cache.add_token(token_address.clone()).await.map_err(|e| {
+            error!(token_address = %token_address, error = %e, "Failed to track token");
+            ApiError::InternalServerError
+        })?;
+    }
+    cache.pin_token(&token_address).await;
+    let (Some(volatility), Some(last_updated)) = (
+        cache.get_volatility(&token_address).await,
+        cache.last_updated(&token_address).await,
+    ) else {
+        return Err(ApiError::NotEnoughData);
+    };
+    info!(token_address = %token_address, already_tracked = %tracked, "Tracking token.");
+
+    let status = if tracked { StatusCode::OK } else { StatusCode::CREATED };
+    Ok((
+        status,
+        Json(TrackTokenResponse {
+            token_address,
+            volatility,
+            last_updated,
+        }),
+    ))
+}
+
+/// Stops tracking a token and drops its cached values.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `404 Not Found` when the token is not tracked.
+#[instrument(skip(state))]
+pub async fn untrack_token(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(token_address): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    if !state.volatility_cache.remove_token(&token_address).await {
+        return Err(ApiError::NotFound(format!(
+            "Token '{}' is not tracked.",
+            token_address
+        )));
+    }
+    info!(token_address = %token_address, "Stopped tracking token.");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Reports the volatility cache's entry count, hit and miss counts, staleness and
+/// background refresh statistics.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+#[instrument(skip(state))]
+pub async fn get_cache_stats(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Result<Json<CacheStats>, ApiError> {
+    Ok(Json(state.volatility_cache.stats().await))
+}
+
+/// Exports the current state as a snapshot.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+#[instrument(skip(state))]
+pub async fn export_state(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+) -> Result<Json<StateSnapshot>, ApiError> {
+    let mut watchlist = Vec::new();
+    for token_address in state.volatility_cache.tracked_tokens().await {
+        let pinned = state.volatility_cache.is_pinned(&token_address).await;
+        watchlist.push(WatchedToken { token_address, pinned });
+    }
+    let presets = state.presets.all().await;
+    info!(tokens = %watchlist.len(), presets = %presets.len(), "Exported service state.");
+
+    Ok(Json(StateSnapshot {
+        version: SNAPSHOT_VERSION,
+        exported_at: Utc::now(),
+        watchlist,
+        presets,
+    }))
+}
+
+/// Imports a snapshot, adding its watchlist tokens to this instance's cache and pinning
+/// those pinned in the snapshot.
+///
+/// Import is additive: tokens already tracked here are left untouched. Presets replace any
+/// defined here under the same name. Nothing is imported unless every address and preset
+/// is valid.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `400 Bad Request` for an invalid body, unsupported snapshot version, invalid
+///   token address or invalid preset.
+#[instrument(skip(state, snapshot))]
+pub async fn import_state(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    ApiJson(snapshot): ApiJson<StateSnapshot>,
+) -> Result<Json<ImportResponse>, ApiError> {
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(ApiError::InvalidBody(format!(
+            "Unsupported snapshot version {} (expected {}).",
+            snapshot.version, SNAPSHOT_VERSION
+        )));
+    }
+    for token in &snapshot.watchlist {
+        base58::validate_pubkey(&token.token_address).map_err(ApiError::InvalidBody)?;
+    }
+    for (name, preset) in &snapshot.presets {
+        presets::validate_name(name)
+            .and_then(|()| preset.validate())
+            .map_err(|msg| ApiError::InvalidBody(format!("Preset '{}': {}", name, msg)))?;
+    }
+
+    let tracked = state.volatility_cache.tracked_tokens().await;
+    let mut response = ImportResponse {
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        failed: Vec::new(),
+        presets: snapshot.presets.keys().cloned().collect(),
+    };
+    for (name, preset) in snapshot.presets {
+        state.presets.upsert(name, preset).await;
+    }
+
+    for WatchedToken { token_address, pinned } in snapshot.watchlist {
+        if tracked.contains(&token_address) || response.imported.contains(&token_address) {
+            response.skipped.push(token_address);
+            continue;
+        }
+        // Stringify the error so the handler future stays `Send` across the pin
+        let added = state
+            .volatility_cache
+            .add_token(token_address.clone())
+            .await
+            .map_err(|e| e.to_string());
+        match added {
+            Ok(()) => {
+                if pinned {
+                    state.volatility_cache.pin_token(&token_address).await;
+                }
+                response.imported.push(token_address);
+            }
+            Err(e) => {
+                error!(token_address = %token_address, error = %e, "Failed to import token");
+                response.failed.push(token_address);
+            }
+        }
+    }
+
+    info!(
+        imported = %response.imported.len(),
+        skipped = %response.skipped.len(),
+        failed = %response.failed.len(),
+        presets = %response.presets.len(),
+        "Imported service state."
+    );
+    Ok(Json(response))
+}
+
+/// Defines or replaces the calculation preset `name`.
+///
+/// Responds `201 Created` for a new preset and `200 OK` when one was replaced.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `400 Bad Request` for an invalid name or preset.
+#[instrument(skip(state))]
+pub async fn put_preset(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    ApiJson(preset): ApiJson<Preset>,
+) -> Result<(StatusCode, Json<Preset>), ApiError> {
+    presets::validate_name(&name).map_err(ApiError::InvalidQuery)?;
+    preset.validate().map_err(ApiError::InvalidBody)?;
+
+    let replaced = state.presets.upsert(name.clone(), preset.clone()).await;
+    info!(preset = %name, replaced = %replaced, "Defined calculation preset.");
+
+    let status = if replaced { StatusCode::OK } else { StatusCode::CREATED };
+    Ok((status, Json(preset)))
+}
+
+/// Removes the calculation preset `name`.
+///
+/// # Errors
+/// - Returns `401 Unauthorized` without a valid admin key.
+/// - Returns `400 Bad Request` when no such preset is defined.
+#[instrument(skip(state))]
+pub async fn delete_preset(
+    _admin: AdminAuth,
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    if !state.presets.remove(&name).await {
+        return Err(ApiError::InvalidQuery(format!("Unknown preset '{}'.", name)));
+    }
+    info!(preset = %name, "Removed calculation preset.");
+
+    Ok(StatusCode::NO_CONTENT)
+}
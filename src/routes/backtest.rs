@@ -0,0 +1,159 @@
+//! # Backtest Handler
+//!
+//! Replays a volatility alert rule ("flag when 7-day vol exceeds 2× 90-day vol") over a
+//! token's daily price history and reports when it fired and how the price moved after,
+//! so users can validate a rule before enabling it as an alert.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::stats::mean;
+use crate::math::Interval;
+use crate::risk::backtest::{backtest_vol_ratio, SignalHit, VolRatioRule};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::trailing_window;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+fn default_short_window_days() -> u32 {
+    7
+}
+
+fn default_long_window_days() -> u32 {
+    90
+}
+
+fn default_ratio() -> f64 {
+    2.0
+}
+
+fn default_lookback_days() -> u32 {
+    365
+}
+
+fn default_forward_days() -> u32 {
+    7
+}
+
+/// Query parameters for the backtest request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestQuery {
+    pub token_address: String,
+    #[serde(default = "default_short_window_days")]
+    pub short_window_days: u32,
+    #[serde(default = "default_long_window_days")]
+    pub long_window_days: u32,
+    /// The rule fires when short-window vol exceeds this multiple of long-window vol.
+    #[serde(default = "default_ratio")]
+    pub ratio: f64,
+    /// How far back the rule is replayed.
+    #[serde(default = "default_lookback_days")]
+    pub lookback_days: u32,
+    /// Days after each hit the subsequent move is measured over.
+    #[serde(default = "default_forward_days")]
+    pub forward_days: u32,
+}
+
+/// Response structure for the backtest endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BacktestResponse {
+    pub token_address: String,
+    pub rule: VolRatioRule,
+    pub lookback_days: u32,
+    pub forward_days: u32,
+    pub hits: Vec<SignalHit>,
+    /// Mean absolute forward return after hits, `None` without a completed hit.
+    pub mean_abs_forward_return: Option<f64>,
+    /// Mean absolute forward return over every day of the look-back, for comparison.
+    pub baseline_mean_abs_forward_return: Option<f64>,
+}
+
+/// Axum handler backtesting a short-versus-long volatility ratio rule for a token.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_backtest(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<BacktestQuery>,
+) -> Result<Json<BacktestResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        short_window_days = %query.short_window_days,
+        long_window_days = %query.long_window_days,
+        ratio = %query.ratio,
+        "Received backtest request."
+    );
+
+    if query.short_window_days < 2 {
+        return Err(ApiError::InvalidQuery(
+            "shortWindowDays must be at least 2.".to_string(),
+        ));
+    }
+    if query.long_window_days <= query.short_window_days {
+        return Err(ApiError::InvalidQuery(
+            "longWindowDays must be greater than shortWindowDays.".to_string(),
+        ));
+    }
+    if !(query.ratio.is_finite() && query.ratio > 0.0) {
+        return Err(ApiError::InvalidQuery(
+            "ratio must be a positive number.".to_string(),
+        ));
+    }
+    if query.forward_days == 0 {
+        return Err(ApiError::InvalidQuery(
+            "forwardDays must be greater than 0.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(
+        query.lookback_days.saturating_add(query.long_window_days),
+        state.config.max_date_range_days,
+    )?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let rule = VolRatioRule {
+        short_window: query.short_window_days as usize,
+        long_window: query.long_window_days as usize,
+        ratio: query.ratio,
+    };
+    let forward = query.forward_days as usize;
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let hits = backtest_vol_ratio(&candles, &rule, forward, periods_per_year);
+
+    let hit_moves: Vec<f64> = hits
+        .iter()
+        .filter_map(|hit| hit.forward_return.map(f64::abs))
+        .collect();
+    let mut closes: Vec<_> = candles.iter().map(|candle| (candle.unix_time, candle.close)).collect();
+    closes.sort_by_key(|(unix_time, _)| *unix_time);
+    let baseline_moves: Vec<f64> = closes
+        .iter()
+        .zip(closes.iter().skip(forward))
+        .filter(|((_, start), _)| *start > 0.0)
+        .map(|((_, start), (_, end))| (end / start - 1.0).abs())
+        .collect();
+
+    Ok(Json(BacktestResponse {
+        mean_abs_forward_return: mean(&hit_moves),
+        baseline_mean_abs_forward_return: mean(&baseline_moves),
+        hits,
+        rule,
+        token_address: query.token_address,
+        lookback_days: query.lookback_days,
+        forward_days: query.forward_days,
+    }))
+}
@@ -0,0 +1,88 @@
+//! # Beta Handler
+//!
+//! Computes a token's beta versus a benchmark token (SOL unless configured otherwise),
+//! reusing the price-fetch, alignment and return pipeline of the correlation endpoint.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::alignment::align_closes;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::stats::{beta, correlation};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the beta request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BetaQuery {
+    pub token_address: String,
+    /// Benchmark token address. Defaults to `BENCHMARK_TOKEN_ADDRESS`.
+    pub benchmark: Option<String>,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Response structure for the beta endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BetaResponse {
+    pub token_address: String,
+    pub benchmark: String,
+    pub days: u32,
+    pub interval: Interval,
+    /// Number of aligned return pairs used.
+    pub data_points: usize,
+    pub beta: f64,
+    /// Correlation with the benchmark, useful to judge how meaningful the beta is.
+    pub correlation: Option<f64>,
+}
+
+/// Axum handler returning a token's beta to the benchmark over a trailing window.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or when the aligned series
+///   are too short (or the benchmark is flat).
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_beta(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<BetaQuery>,
+) -> Result<Json<BetaResponse>, ApiError> {
+    let benchmark = query
+        .benchmark
+        .clone()
+        .unwrap_or_else(|| state.config.benchmark_token_address.clone());
+
+    info!(
+        token_address = %query.token_address,
+        benchmark = %benchmark,
+        days = %query.days,
+        "Received beta request."
+    );
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let (asset_series, benchmark_series) = tokio::try_join!(
+        fetch_series(&state, &CloseToClose, query.interval, from_date, to_date, &query.token_address),
+        fetch_series(&state, &CloseToClose, query.interval, from_date, to_date, &benchmark),
+    )?;
+
+    let returns = align_closes(&[asset_series, benchmark_series], query.interval.seconds()).log_returns();
+    let token_beta = beta(&returns[0], &returns[1]).ok_or(ApiError::NotEnoughData)?;
+
+    Ok(Json(BetaResponse {
+        data_points: returns[0].len(),
+        beta: token_beta,
+        correlation: correlation(&returns[0], &returns[1]),
+        token_address: query.token_address,
+        benchmark,
+        days: query.days,
+        interval: query.interval,
+    }))
+}
@@ -0,0 +1,79 @@
+//! # Pairwise Correlation Handler
+//!
+//! Computes the Pearson correlation of two tokens' log returns over a trailing window,
+//! after aligning both series on common timestamps.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::alignment::align_closes;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::stats::correlation;
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the correlation request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationQuery {
+    pub token_a: String,
+    pub token_b: String,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Response structure for the correlation endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationResponse {
+    pub token_a: String,
+    pub token_b: String,
+    pub days: u32,
+    pub interval: Interval,
+    /// Number of aligned return pairs the coefficient was computed from.
+    pub data_points: usize,
+    pub correlation: f64,
+}
+
+/// Axum handler returning the Pearson correlation of two tokens' log returns.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or when fewer than two
+///   aligned returns are available (or either series is flat).
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_correlation(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<CorrelationQuery>,
+) -> Result<Json<CorrelationResponse>, ApiError> {
+    info!(
+        token_a = %query.token_a,
+        token_b = %query.token_b,
+        days = %query.days,
+        "Received correlation request."
+    );
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let (series_a, series_b) = tokio::try_join!(
+        fetch_series(&state, &CloseToClose, query.interval, from_date, to_date, &query.token_a),
+        fetch_series(&state, &CloseToClose, query.interval, from_date, to_date, &query.token_b),
+    )?;
+
+    let returns = align_closes(&[series_a, series_b], query.interval.seconds()).log_returns();
+    let coefficient = correlation(&returns[0], &returns[1]).ok_or(ApiError::NotEnoughData)?;
+
+    Ok(Json(CorrelationResponse {
+        data_points: returns[0].len(),
+        correlation: coefficient,
+        token_a: query.token_a,
+        token_b: query.token_b,
+        days: query.days,
+        interval: query.interval,
+    }))
+}
@@ -0,0 +1,100 @@
+//! # Correlation Matrix Handler
+//!
+//! Computes pairwise correlation and covariance matrices of log returns for a basket of
+//! tokens, from one concurrent batch of price fetches aligned on common timestamps.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::json_extractor::ApiJson;
+use crate::math::alignment::align_closes;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::stats::{correlation, covariance};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Request body for the correlation matrix endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationMatrixRequest {
+    pub token_addresses: Vec<String>,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Response structure for the correlation matrix endpoint.
+///
+/// Row and column `i` of both matrices refer to `token_addresses[i]`. Entries are `null`
+/// where a coefficient is undefined (e.g. a token with a flat price series).
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrelationMatrixResponse {
+    pub token_addresses: Vec<String>,
+    pub days: u32,
+    pub interval: Interval,
+    /// Number of aligned returns per token.
+    pub data_points: usize,
+    pub correlation: Vec<Vec<Option<f64>>>,
+    /// Sample covariance of per-interval log returns.
+    pub covariance: Vec<Vec<Option<f64>>>,
+}
+
+/// Axum handler returning the correlation and covariance matrices of a token basket.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an invalid body, fewer than two or more than
+///   `MAX_MATRIX_TOKENS` tokens, or too few aligned returns.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn post_correlation_matrix(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<CorrelationMatrixRequest>,
+) -> Result<Json<CorrelationMatrixResponse>, ApiError> {
+    info!(
+        tokens = %request.token_addresses.len(),
+        days = %request.days,
+        "Received correlation matrix request."
+    );
+
+    let token_count = request.token_addresses.len();
+    if token_count < 2 || token_count > state.config.max_matrix_tokens {
+        return Err(ApiError::InvalidBody(format!(
+            "tokenAddresses must contain between 2 and {} tokens.",
+            state.config.max_matrix_tokens
+        )));
+    }
+
+    let (from_date, to_date) = trailing_window(request.days, state.config.max_date_range_days)?;
+    let series = try_join_all(request.token_addresses.iter().map(|token_address| {
+        fetch_series(&state, &CloseToClose, request.interval, from_date, to_date, token_address)
+    }))
+    .await?;
+
+    let returns = align_closes(&series, request.interval.seconds()).log_returns();
+    let data_points = returns.first().map_or(0, Vec::len);
+    if data_points < 2 {
+        return Err(ApiError::NotEnoughData);
+    }
+
+    let matrix = |statistic: fn(&[f64], &[f64]) -> Option<f64>| -> Vec<Vec<Option<f64>>> {
+        returns
+            .iter()
+            .map(|row| returns.iter().map(|column| statistic(row, column)).collect())
+            .collect()
+    };
+
+    Ok(Json(CorrelationMatrixResponse {
+        correlation: matrix(correlation),
+        covariance: matrix(covariance),
+        data_points,
+        token_addresses: request.token_addresses,
+        days: request.days,
+        interval: request.interval,
+    }))
+}
@@ -0,0 +1,45 @@
+//! # Dashboard Handler
+//!
+//! Serves a self-contained HTML ops view embedded in the binary. Tracked tokens and
+//! their cached volatilities are injected server-side; the page fetches price
+//! sparklines from `/prices` and cache health from `/status` itself.
+
+use crate::state::AppState;
+use axum::{extract::State, response::Html};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::instrument;
+
+/// Dashboard page, compiled into the binary so it needs no static file serving.
+const DASHBOARD_HTML: &str = include_str!("../../assets/dashboard.html");
+/// Placeholder in the page replaced with the tracked tokens JSON.
+const TRACKED_TOKENS_PLACEHOLDER: &str = "__TRACKED_TOKENS__";
+
+/// A tracked token as rendered by the dashboard.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DashboardToken {
+    token_address: String,
+    volatility: Option<f64>,
+    last_updated: Option<DateTime<Utc>>,
+}
+
+/// Axum handler serving the dashboard page.
+#[instrument(skip(state))]
+pub async fn get_dashboard(State(state): State<AppState>) -> Html<String> {
+    let mut tokens = Vec::new();
+    for token_address in state.volatility_cache.tracked_tokens().await {
+        tokens.push(DashboardToken {
+            volatility: state.volatility_cache.get_volatility(&token_address).await,
+            last_updated: state.volatility_cache.last_updated(&token_address).await,
+            token_address,
+        });
+    }
+
+    // Escape `</` so token data can never close the surrounding <script> element
+    let tokens_json = serde_json::to_string(&tokens)
+        .unwrap_or_else(|_| "[]".to_string())
+        .replace("</", "<\\/");
+
+    Html(DASHBOARD_HTML.replace(TRACKED_TOKENS_PLACEHOLDER, &tokens_json))
+}
@@ -0,0 +1,27 @@
+//! # Deprecations Handler
+//!
+//! Reports the deprecated routes and parameters and how often each was used since startup,
+//! so we know when it is safe to remove them.
+
+use crate::deprecations::DeprecationUsage;
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Response structure for the deprecations endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecationsResponse {
+    /// Deprecated features, soonest sunset first.
+    pub deprecations: Vec<DeprecationUsage>,
+}
+
+/// Axum handler listing deprecated features and their use.
+#[instrument(skip(state))]
+pub async fn get_deprecations(State(state): State<AppState>) -> Json<DeprecationsResponse> {
+    let deprecations = state.deprecations.report().await;
+    info!(deprecations = %deprecations.len(), "Listing deprecated features.");
+
+    Json(DeprecationsResponse { deprecations })
+}
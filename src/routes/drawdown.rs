@@ -0,0 +1,77 @@
+//! # Drawdown Handler
+//!
+//! Reports the maximum drawdown, its duration and the current drawdown from peak of a
+//! token's closing prices over a trailing window.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::Interval;
+use crate::risk::drawdown::{drawdown, DrawdownSummary};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the drawdown request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownQuery {
+    pub token_address: String,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Response structure for the drawdown endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownResponse {
+    pub token_address: String,
+    pub days: u32,
+    pub interval: Interval,
+    /// Number of candles used.
+    pub data_points: usize,
+    #[serde(flatten)]
+    pub drawdown: DrawdownSummary,
+}
+
+/// Axum handler returning drawdown statistics for a token.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or too little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_drawdown(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<DrawdownQuery>,
+) -> Result<Json<DrawdownResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        days = %query.days,
+        interval = %query.interval,
+        "Received drawdown request."
+    );
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    Ok(Json(DrawdownResponse {
+        drawdown: drawdown(&candles).ok_or(ApiError::NotEnoughData)?,
+        data_points: candles.len(),
+        token_address: query.token_address,
+        days: query.days,
+        interval: query.interval,
+    }))
+}
@@ -0,0 +1,205 @@
+//! # Query Explain Handler
+//!
+//! Describes what `GET /historicalVolatility` would do for a query — validation, window and
+//! method resolution, cache lookups and the provider call it would make — without fetching
+//! anything or touching cache state. Intended for support and debugging.
+
+use crate::background::price_cache::SeriesKey;
+use crate::extractors::query_extractor::HistoricalVolatilityQuery;
+use crate::math::{EstimatorKind, Interval};
+use crate::routes::historical_volatility::cached_window;
+use crate::state::AppState;
+use crate::utils::date_window::validate_range;
+use crate::utils::{address_type, chain, timestamp_format};
+use axum::{
+    extract::State,
+    http::Uri,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Outcome of validating the query string.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationTrace {
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// How the query's parameters resolve to a calculation.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionTrace {
+    pub token_address: String,
+    /// Preset the parameters were filled from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    pub estimator: EstimatorKind,
+    pub interval: Interval,
+    pub annualization_days: u32,
+    /// `cachedRollingWindow` when served from the background cache, `requestedRange` otherwise.
+    pub window: String,
+    pub window_days: Option<i64>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub from_date: DateTime<Utc>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub to_date: DateTime<Utc>,
+}
+
+/// Cache lookup the handler would perform.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheTrace {
+    /// `volatilityCache` or `priceCache`.
+    pub cache: String,
+    pub key: String,
+    pub hit: bool,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime_option")]
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+/// Provider request the handler would make on a cache miss.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderTrace {
+    pub provider: String,
+    pub endpoint: String,
+    pub would_call: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplainResponse {
+    pub validation: ValidationTrace,
+    pub resolution: Option<ResolutionTrace>,
+    pub cache: Option<CacheTrace>,
+    pub provider: Option<ProviderTrace>,
+}
+
+/// Explain endpoint for `GET /historicalVolatility`.
+///
+/// Always returns `200 OK`; invalid queries are reported in `validation` instead of
+/// as an error response, so support can paste any failing URL in.
+#[instrument(skip(state))]
+pub async fn explain_historical_volatility(
+    State(state): State<AppState>,
+    uri: Uri,
+) -> Json<ExplainResponse> {
+    info!("Received historical volatility explain request.");
+
+    let query = HistoricalVolatilityQuery::from_uri(&uri, &state.presets, state.config.max_date_range_days)
+        .await
+        .and_then(|query| {
+            validate_range(query.from_date, query.to_date, state.config.max_date_range_days)
+                .map_err(|err| err.to_string())?;
+            Ok(query)
+        });
+    let query = match query {
+        Ok(query) => query,
+        Err(err) => {
+            return Json(ExplainResponse {
+                validation: ValidationTrace {
+                    valid: false,
+                    error: Some(err),
+                },
+                resolution: None,
+                cache: None,
+                provider: None,
+            })
+        }
+    };
+
+    let annualization_days = query
+        .annualization_days
+        .unwrap_or(state.config.annualization_days);
+    let (resolution, cache, requires_ohlc) = if let Some(window_days) = cached_window(&state, &query) {
+        let last_updated = state
+            .volatility_cache
+            .last_updated(&query.token_address)
+            .await;
+        let window_days = window_days as i64;
+        let to_date = Utc::now();
+        (
+            ResolutionTrace {
+                token_address: query.token_address.clone(),
+                preset: query.preset.clone(),
+                estimator: query.estimator,
+                interval: query.interval,
+                annualization_days,
+                window: "cachedRollingWindow".to_string(),
+                window_days: Some(window_days),
+                from_date: to_date - chrono::Duration::days(window_days),
+                to_date,
+            },
+            CacheTrace {
+                cache: "volatilityCache".to_string(),
+                key: query.token_address.clone(),
+                hit: last_updated.is_some(),
+                last_updated,
+            },
+            state
+                .config
+                .volatility_estimator
+                .estimator()
+                .requires_ohlc(),
+        )
+    } else {
+        let requires_ohlc = query.estimator.estimator().requires_ohlc();
+        let key = SeriesKey {
+            chain: chain::current(),
+            address_type: address_type::current(),
+            token_address: query.token_address.clone(),
+            interval: query.interval,
+            ohlc: requires_ohlc,
+        };
+        let hit = state
+            .price_cache
+            .get(&key, query.from_date, query.to_date)
+            .await
+            .is_some();
+        (
+            ResolutionTrace {
+                token_address: query.token_address.clone(),
+                preset: query.preset.clone(),
+                estimator: query.estimator,
+                interval: query.interval,
+                annualization_days,
+                window: "requestedRange".to_string(),
+                window_days: None,
+                from_date: query.from_date,
+                to_date: query.to_date,
+            },
+            CacheTrace {
+                cache: "priceCache".to_string(),
+                key: format!(
+                    "{}:{}:{}:{}:{}",
+                    key.chain,
+                    key.address_type,
+                    key.token_address,
+                    key.interval,
+                    if key.ohlc { "ohlcv" } else { "close" }
+                ),
+                hit,
+                last_updated: None,
+            },
+            requires_ohlc,
+        )
+    };
+
+
+    Json(ExplainResponse {
+        validation: ValidationTrace {
+            valid: true,
+            error: None,
+        },
+        provider: Some(ProviderTrace {
+            provider: state.provider.name().to_string(),
+            endpoint: state.provider.history_endpoint(requires_ohlc),
+            would_call: !cache.hit || query.force_refresh,
+        }),
+        resolution: Some(resolution),
+        cache: Some(cache),
+    })
+}
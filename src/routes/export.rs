@@ -0,0 +1,256 @@
+//! # Parquet Export Handlers
+//!
+//! Bulk downloads of a token's price and rolling volatility series as Parquet files, for
+//! data-science consumers loading them straight into DuckDB, Spark or pandas rather than
+//! paging through JSON.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::rolling::rolling_volatility;
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::routes::prices::PricesQuery;
+use crate::state::AppState;
+use crate::utils::custom_date_serde;
+use crate::utils::date_window::validate_range;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray, UInt32Array};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use axum::{
+    extract::State,
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+const DEFAULT_EXPORT_WINDOW_DAYS: u32 = 30;
+
+fn default_export_window_days() -> u32 {
+    DEFAULT_EXPORT_WINDOW_DAYS
+}
+
+/// Query parameters for the volatility series export.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilitySeriesExportQuery {
+    #[serde(with = "custom_date_serde")]
+    pub from_date: DateTime<Utc>,
+    #[serde(with = "custom_date_serde")]
+    pub to_date: DateTime<Utc>,
+    pub token_address: String,
+    /// Daily returns per rolling volatility window.
+    #[serde(default = "default_export_window_days")]
+    pub window_days: u32,
+}
+
+/// A Parquet file download.
+pub struct ParquetFile {
+    pub file_name: String,
+    pub bytes: Vec<u8>,
+}
+
+impl IntoResponse for ParquetFile {
+    fn into_response(self) -> Response {
+        (
+            [
+                (CONTENT_TYPE, PARQUET_CONTENT_TYPE.to_string()),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", self.file_name),
+                ),
+            ],
+            self.bytes,
+        )
+            .into_response()
+    }
+}
+
+/// Axum handler returning a token's price series for a date range as a Parquet file with
+/// `token_address`, `time` (UTC milliseconds) and `price` columns, one row per candle close.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or date range.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures or when the file
+///   can't be written.
+#[instrument(skip(state))]
+pub async fn export_prices(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<PricesQuery>,
+) -> Result<ParquetFile, ApiError> {
+    info!(
+        from_date = %query.from_date,
+        to_date = %query.to_date,
+        token_address = %query.token_address,
+        interval = %query.interval,
+        "Received price export request."
+    );
+
+    validate_range(query.from_date, query.to_date, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        query.from_date,
+        query.to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let schema = Schema::new(vec![
+        Field::new("token_address", DataType::Utf8, false),
+        Field::new("time", utc_millis(), false),
+        Field::new("price", DataType::Float64, false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![query.token_address.as_str(); candles.len()])),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(candles.iter().map(|candle| candle.unix_time * 1000))
+                .with_timezone("UTC"),
+        ),
+        Arc::new(Float64Array::from_iter_values(candles.iter().map(|candle| candle.close))),
+    ];
+
+    Ok(ParquetFile {
+        file_name: format!("{}-prices-{}.parquet", query.token_address, query.interval),
+        bytes: write_parquet(schema, columns)?,
+    })
+}
+
+/// Axum handler returning a token's rolling close-to-close volatility for a date range as a
+/// Parquet file with `token_address`, `window_days`, `time` (UTC milliseconds) and
+/// `volatility` columns, one row per daily window ending in the range.
+///
+/// The prices fetched reach `windowDays` before `fromDate`, so the series covers the whole
+/// range.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or date range, a `windowDays`
+///   below 2, or when no window fits in the range.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures or when the file
+///   can't be written.
+#[instrument(skip(state))]
+pub async fn export_volatility_series(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<VolatilitySeriesExportQuery>,
+) -> Result<ParquetFile, ApiError> {
+    info!(
+        from_date = %query.from_date,
+        to_date = %query.to_date,
+        token_address = %query.token_address,
+        window_days = %query.window_days,
+        "Received volatility series export request."
+    );
+
+    if query.window_days < 2 {
+        return Err(ApiError::InvalidQuery(
+            "windowDays must be at least 2.".to_string(),
+        ));
+    }
+    validate_range(query.from_date, query.to_date, state.config.max_date_range_days)?;
+    let fetch_from = query
+        .from_date
+        .checked_sub_signed(Duration::days(query.window_days as i64))
+        .ok_or_else(|| ApiError::InvalidQuery(format!("windowDays {} is out of range.", query.window_days)))?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        fetch_from,
+        query.to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let series: Vec<_> = rolling_volatility(&candles, query.window_days as usize, &CloseToClose, periods_per_year)
+        .into_iter()
+        .filter(|point| point.unix_time >= query.from_date.timestamp())
+        .collect();
+    if series.is_empty() {
+        return Err(ApiError::NotEnoughData);
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("token_address", DataType::Utf8, false),
+        Field::new("window_days", DataType::UInt32, false),
+        Field::new("time", utc_millis(), false),
+        Field::new("volatility", DataType::Float64, false),
+    ]);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from(vec![query.token_address.as_str(); series.len()])),
+        Arc::new(UInt32Array::from(vec![query.window_days; series.len()])),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(series.iter().map(|point| point.unix_time * 1000))
+                .with_timezone("UTC"),
+        ),
+        Arc::new(Float64Array::from_iter_values(series.iter().map(|point| point.volatility))),
+    ];
+
+    Ok(ParquetFile {
+        file_name: format!("{}-volatility-{}d.parquet", query.token_address, query.window_days),
+        bytes: write_parquet(schema, columns)?,
+    })
+}
+
+fn utc_millis() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+/// Encodes `columns` as a Snappy-compressed Parquet file.
+fn write_parquet(schema: Schema, columns: Vec<ArrayRef>) -> Result<Vec<u8>, ApiError> {
+    let encode = || -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let batch = RecordBatch::try_new(Arc::new(schema), columns)?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), Some(properties))?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(bytes)
+    };
+    encode().map_err(|e| {
+        error!(error = %e, "Failed to write Parquet file");
+        ApiError::InternalServerError
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_schema::ArrowError;
+    use axum::body::Bytes;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_written_file_reads_back() {
+        let schema = Schema::new(vec![
+            Field::new("time", utc_millis(), false),
+            Field::new("price", DataType::Float64, false),
+        ]);
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(TimestampMillisecondArray::from(vec![1_700_000_000_000, 1_700_086_400_000]).with_timezone("UTC")),
+            Arc::new(Float64Array::from(vec![100.0, 101.5])),
+        ];
+        let bytes = write_parquet(schema, columns).unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes))
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.collect::<Result<_, ArrowError>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.schema().field(0).data_type(), &utc_millis());
+        let prices = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(prices.values(), &[100.0, 101.5]);
+    }
+}
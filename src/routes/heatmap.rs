@@ -0,0 +1,191 @@
+//! # Volatility Heatmap Handler
+//!
+//! Returns a matrix of volatility by token and trailing window for a watchlist in one
+//! response. Tracked tokens are answered entirely from the multi-window values the
+//! background task caches; any other token is computed from a single fetch of its
+//! longest requested window.
+
+use crate::background::volatility_cache::CACHED_WINDOW_DAYS;
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::rolling::trailing_volatility;
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::trailing_window;
+use crate::utils::timestamp_format;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use futures::future::try_join_all;
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the heatmap request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapQuery {
+    /// Comma-separated token addresses; defaults to every tracked token.
+    pub token_addresses: Option<String>,
+    /// Comma-separated window lengths in days; defaults to every cached window.
+    pub windows: Option<String>,
+}
+
+/// One token's row of the heatmap.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapRow {
+    pub token_address: String,
+    /// Annualized volatility in percent per window, in the order of `windows`. `null` where
+    /// the token's history is too short to cover the window.
+    pub volatility: Vec<Option<f64>>,
+    /// Whether the row was read from the volatility cache rather than computed on demand.
+    pub cached: bool,
+    /// When the cached values were last refreshed, for cached rows.
+    #[serde(
+        default,
+        serialize_with = "timestamp_format::serialize_datetime_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+/// Response structure for the heatmap endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HeatmapResponse {
+    /// Column headers: window lengths in days, ascending.
+    pub windows: Vec<u32>,
+    pub rows: Vec<HeatmapRow>,
+}
+
+/// Axum handler returning volatility by token and window.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters, windows other than
+///   `CACHED_WINDOW_DAYS`, or more than `MAX_MATRIX_TOKENS` tokens.
+/// - Returns `500 Internal Server Error` for unexpected provider failures.
+#[instrument(skip(state))]
+pub async fn get_heatmap(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<HeatmapQuery>,
+) -> Result<Json<HeatmapResponse>, ApiError> {
+    let windows = parse_windows(query.windows.as_deref())?;
+    let token_addresses = match query.token_addresses.as_deref() {
+        Some(list) => split_list(list),
+        None => state.volatility_cache.tracked_tokens().await,
+    };
+    if query.token_addresses.is_some() && token_addresses.is_empty() {
+        return Err(ApiError::InvalidQuery(
+            "tokenAddresses must not be empty.".to_string(),
+        ));
+    }
+    if token_addresses.len() > state.config.max_matrix_tokens {
+        return Err(ApiError::InvalidQuery(format!(
+            "tokenAddresses must contain at most {} tokens.",
+            state.config.max_matrix_tokens
+        )));
+    }
+
+    info!(
+        tokens = %token_addresses.len(),
+        windows = ?windows,
+        "Received heatmap request."
+    );
+
+    let rows = try_join_all(
+        token_addresses
+            .into_iter()
+            .map(|token_address| heatmap_row(&state, token_address, &windows)),
+    )
+    .await?;
+
+    Ok(Json(HeatmapResponse { windows, rows }))
+}
+
+/// Reads a token's row from the cache when it is tracked, computing it on demand otherwise.
+async fn heatmap_row(
+    state: &AppState,
+    token_address: String,
+    windows: &[u32],
+) -> Result<HeatmapRow, ApiError> {
+    let cache = &state.volatility_cache;
+    let estimator_kind = state.config.volatility_estimator;
+    if cache.covers(estimator_kind, Interval::OneDay) {
+        if let (Some(_), Some(last_updated)) = (
+            cache.get_volatility(&token_address).await,
+            cache.last_updated(&token_address).await,
+        ) {
+            let mut volatility = Vec::with_capacity(windows.len());
+            for &days in windows {
+                volatility.push(cache.get_window_volatility(&token_address, days).await);
+            }
+            return Ok(HeatmapRow {
+                token_address,
+                volatility,
+                cached: true,
+                last_updated: Some(last_updated),
+            });
+        }
+    }
+
+    let estimator = estimator_kind.estimator();
+    let longest = windows.iter().copied().max().unwrap_or_default();
+    let (from_date, to_date) = trailing_window(longest, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        state,
+        estimator.as_ref(),
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &token_address,
+    )
+    .await?;
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    Ok(HeatmapRow {
+        volatility: windows
+            .iter()
+            .map(|&days| trailing_volatility(&candles, days as usize, estimator.as_ref(), periods_per_year))
+            .collect(),
+        token_address,
+        cached: false,
+        last_updated: None,
+    })
+}
+
+/// Parses the requested windows, sorted and deduplicated; all cached windows when unset.
+fn parse_windows(list: Option<&str>) -> Result<Vec<u32>, ApiError> {
+    let Some(list) = list else {
+        return Ok(CACHED_WINDOW_DAYS.to_vec());
+    };
+
+    let invalid = || {
+        let allowed: Vec<String> = CACHED_WINDOW_DAYS.iter().map(u32::to_string).collect();
+        ApiError::InvalidQuery(format!("windows must be among {}.", allowed.join(", ")))
+    };
+    let mut windows = split_list(list)
+        .iter()
+        .map(|window| {
+            window
+                .parse::<u32>()
+                .ok()
+                .filter(|days| CACHED_WINDOW_DAYS.contains(days))
+                .ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<u32>, ApiError>>()?;
+    if windows.is_empty() {
+        return Err(invalid());
+    }
+    windows.sort_unstable();
+    windows.dedup();
+    Ok(windows)
+}
+
+/// Splits a comma-separated list, dropping blank entries.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
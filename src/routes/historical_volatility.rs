@@ -1,21 +1,25 @@
 //! # Volatility Calculation Handler
 //!
 //! This module provides a single Axum handler for calculating historical volatility
-//! based on token prices fetched from the Birdeye API.
+//! based on token prices fetched from the configured [`PriceProvider`](crate::provider::PriceProvider).
 //!
 //! It is intended to be used **internally** in the backend, not as a standalone library.
-//! It also contains data models and internal helpers necessary for this specific functionality.
-
-use crate::config::AppConfig;
-use crate::extractors::query_extractor::HistoricalVolatilityQuery;
+//! It also contains the cached series fetch the other analytics handlers share.
+
+use crate::background::price_cache::SeriesKey;
+use crate::background::volatility_cache::CACHED_WINDOW_DAYS;
+#[cfg(feature = "admin")]
+use crate::extractors::admin_auth::AdminAuth;
+use crate::extractors::query_extractor::{DateRange, HistoricalVolatilityQuery};
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::stats::{return_stats, ReturnStats};
+use crate::math::{Candle, Interval, VolatilityEstimator};
+use crate::provider::birdeye::HistoricalPricePoint;
+use crate::provider::report;
+use crate::utils::{address_type, chain, deadline, timestamp_format};
 use crate::{errors::api_error::ApiError, state::AppState};
-use axum::{
-    extract::State,
-    http::{HeaderMap, HeaderValue},
-    Json,
-};
-use chrono::{DateTime, Utc};
-use reqwest::header::ACCEPT;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument, error};
 
@@ -28,68 +32,42 @@ use tracing::{info, instrument, error};
 #[serde(rename_all = "camelCase")]
 pub struct HistoricalVolatilityResponse {
     pub historical_volatility: f64,
-}
-
-/// Raw structure of the response returned by the Birdeye API.
-#[derive(Debug, Deserialize)]
-pub struct BirdeyeHistoricalPriceResponse {
-    pub data: Option<HistoricalPriceData>,
-    pub success: bool,
-    pub message: Option<String>,
-}
-
-/// Nested `data` field inside the Birdeye response.
-#[derive(Debug, Deserialize)]
-pub struct HistoricalPriceData {
-    pub items: Vec<HistoricalPricePoint>,
-}
-
-/// Represents a single historical price point.
-#[derive(Debug, Deserialize, Clone)]
-pub struct HistoricalPricePoint {
-    #[serde(rename = "unixTime")]
-    pub unix_time: i64,
-    pub value: f64,
-}
-
-/// Internal representation of Birdeye response, abstracting success and failure.
-#[derive(Debug)]
-pub enum BirdeyeResponse {
-    Success(HistoricalPriceData),
-    Failure(String),
-}
-
-//
-// ----------- Conversions -----------
-//
-
-impl From<BirdeyeHistoricalPriceResponse> for BirdeyeResponse {
-    fn from(raw: BirdeyeHistoricalPriceResponse) -> Self {
-        if raw.success {
-            if let Some(data) = raw.data {
-                BirdeyeResponse::Success(data)
-            } else {
-                BirdeyeResponse::Failure("Missing data in successful Birdeye response.".to_string())
-            }
-        } else {
-            let message = raw.message.unwrap_or_else(|| "Unknown error".to_string());
-            BirdeyeResponse::Failure(message)
-        }
-    }
+    /// Days per year the volatility was annualized with.
+    pub annualization_days: u32,
+    /// Candles the volatility was computed from.
+    pub data_points: usize,
+    /// Length of the window the candles span, rounded up to whole days.
+    pub window_days: u32,
+    /// When the underlying prices were fetched from the provider.
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub last_updated: DateTime<Utc>,
+    pub interval: Interval,
+    /// Providers that served the prices, comma separated, as in `X-Price-Provider`.
+    pub provider: String,
+    /// Return distribution of the underlying series, when `extendedStats=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_stats: Option<ReturnStats>,
+    /// Closes of the underlying series, when `includePrices=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prices: Option<Vec<HistoricalPricePoint>>,
 }
 
 //
 // ----------- Handlers and Logic -----------
 //
 
-/// Axum handler that fetches historical prices from Birdeye and calculates volatility.
+/// Axum handler that fetches historical prices from the price provider and calculates volatility.
 ///
 /// # Errors
-/// - Returns `400 Bad Request` for invalid user input (wrong address or wrong date format).
+/// - Returns `400 Bad Request` for invalid user input (wrong address, wrong date format, or a
+///   date range that is reversed, ends in the future or exceeds `MAX_DATE_RANGE_DAYS`).
+/// - Returns `401 Unauthorized` for `forceRefresh=true` without a valid admin key when
+///   `FORCE_REFRESH_REQUIRES_ADMIN` is set.
 /// - Returns `500 Internal Server Error` for unexpected Birdeye failures or internal issues.
-#[instrument(ret, err, skip(state))]
+#[instrument(ret, err, skip_all, fields(query = ?query))]
 pub async fn get_historical_volatility(
     State(state): State<AppState>,
+    #[cfg(feature = "admin")] admin: Result<AdminAuth, ApiError>,
     query: HistoricalVolatilityQuery,
 ) -> Result<Json<HistoricalVolatilityResponse>, ApiError> {
     // Log the incoming request parameters
@@ -100,21 +78,92 @@ pub async fn get_historical_volatility(
         "Received historical volatility request."
     );
 
-    // Check if we have cached volatility data for this token
-    if let Some(volatility) = state.volatility_cache.get_volatility(&query.token_address).await {
-        info!(
-            token_address = %query.token_address,
-            volatility = %volatility,
-            "Returning cached volatility data"
-        );
-        
-        return Ok(Json(HistoricalVolatilityResponse {
-            historical_volatility: volatility,
-        }));
+    if query.force_refresh {
+        if state.config.force_refresh_requires_admin {
+            #[cfg(feature = "admin")]
+            admin?;
+            #[cfg(not(feature = "admin"))]
+            return Err(ApiError::Unauthorized(
+                "forceRefresh requires an admin key, and this build has no admin support.".to_string(),
+            ));
+        }
+        info!(token_address = %query.token_address, "Bypassing caches for forced refresh");
+        let ohlc = query.estimator.estimator().requires_ohlc();
+        state
+            .price_cache
+            .invalidate(&series_key(&query.token_address, query.interval, ohlc))
+            .await;
     }
 
-    // If not in cache, add it to the cache and calculate volatility
-    if let Err(e) = state.volatility_cache.add_token(query.token_address.clone()).await {
+    let annualization_days = query
+        .annualization_days
+        .unwrap_or(state.config.annualization_days);
+    let series = if query.extended_stats || query.include_prices {
+        Some(get_calculation_series(&state, &query).await?)
+    } else {
+        None
+    };
+    let extended_stats = match &series {
+        Some(candles) if query.extended_stats => Some(extended_stats(candles)?),
+        _ => None,
+    };
+    let prices = series.filter(|_| query.include_prices).map(|candles| {
+        candles
+            .iter()
+            .map(|candle| HistoricalPricePoint {
+                unix_time: candle.unix_time,
+                value: candle.close,
+            })
+            .collect()
+    });
+
+    // The cache only holds trailing daily values for its configured estimator; anything else
+    // is computed on demand
+    let cached = match cached_window(&state, &query) {
+        Some(window_days) => {
+            get_cached_volatility(&state, &query, window_days, annualization_days).await?
+        }
+        None => None,
+    };
+    let response = match cached {
+        Some(response) => response,
+        None => get_uncached_volatility(&state, &query, annualization_days).await?,
+    };
+
+    Ok(Json(HistoricalVolatilityResponse {
+        extended_stats,
+        prices,
+        ..response
+    }))
+}
+
+/// Serves the volatility over the cache's trailing `window_days` window, adding the token to
+/// the cache on first request and refreshing it when forced.
+///
+/// `None` when a `days` window isn't among the token's cached windows, leaving it to be
+/// computed on demand.
+async fn get_cached_volatility(
+    state: &AppState,
+    query: &HistoricalVolatilityQuery,
+    window_days: u32,
+    annualization_days: u32,
+) -> Result<Option<HistoricalVolatilityResponse>, ApiError> {
+    let volatility_cache = &state.volatility_cache;
+    let refreshed = if query.force_refresh {
+        volatility_cache
+            .force_refresh_token(query.token_address.clone())
+            .await
+            .map_err(|e| e.to_string())
+    } else if volatility_cache.lookup(&query.token_address).await.is_some() {
+        info!(token_address = %query.token_address, "Returning cached volatility data");
+        Ok(())
+    } else {
+        volatility_cache
+            .add_token(query.token_address.clone())
+            .await
+            .map_err(|e| e.to_string())
+    };
+    if let Err(e) = refreshed {
         error!(
             token_address = %query.token_address,
             error = %e,
@@ -123,122 +172,242 @@ pub async fn get_historical_volatility(
         return Err(ApiError::InternalServerError);
     }
 
-    // Get the newly calculated volatility from the cache
-    let volatility = state.volatility_cache.get_volatility(&query.token_address).await
-        .ok_or(ApiError::NotEnoughData)?;
+    let data_points = volatility_cache
+        .data_points(&query.token_address)
+        .await
+        .unwrap_or_default();
+    let (volatility, data_points) = match query.range {
+        DateRange::Trailing(days) => {
+            match volatility_cache
+                .get_window_volatility(&query.token_address, days)
+                .await
+            {
+                // The window's value is computed from its last `days` returns
+                Some(volatility) => (volatility, data_points.min(days as usize + 1)),
+                None => return Ok(None),
+            }
+        }
+        _ => (
+            volatility_cache
+                .get_volatility(&query.token_address)
+                .await
+                .ok_or(ApiError::NotEnoughData)?,
+            data_points,
+        ),
+    };
+    if let Some(provider) = volatility_cache.provider_of(&query.token_address).await {
+        report::record(provider);
+    }
 
-    Ok(Json(HistoricalVolatilityResponse {
-        historical_volatility: volatility,
+    Ok(Some(HistoricalVolatilityResponse {
+        historical_volatility: rescale_annualization(
+            volatility,
+            state.config.annualization_days,
+            annualization_days,
+        ),
+        annualization_days,
+        data_points,
+        window_days,
+        last_updated: volatility_cache
+            .last_updated(&query.token_address)
+            .await
+            .unwrap_or_else(Utc::now),
+        interval: query.interval,
+        provider: report::recorded().join(","),
+        extended_stats: None,
+        prices: None,
     }))
 }
-/// Fetches historical token prices from the Birdeye public API.
-///
-/// # Notes
-/// - Injects configuration (base URL, API key) from `AppConfig`.
-#[allow(dead_code)]
-async fn make_birdeye_request(
-    config: &AppConfig,
-    from_date: DateTime<Utc>,
-    to_date: DateTime<Utc>,
-    token_address: &str,
-) -> Result<BirdeyeHistoricalPriceResponse, reqwest::Error> {
-    // Convert DateTime objects to Unix timestamps for the API request
-    let from_timestamp = from_date.timestamp();
-    let to_timestamp = to_date.timestamp();
-
-    // Construct the query string with required parameters:
-    // - address: The token address to fetch prices for
-    // - address_type: Set to "token" to indicate we're querying a token
-    // - type: Set to "1D" to get daily price data
-    // - time_from: Start timestamp
-    // - time_to: End timestamp
-    let query = format!(
-        "address={}&address_type=token&type=1D&time_from={}&time_to={}",
-        token_address, from_timestamp, to_timestamp
-    );
-    let request_url = format!("{}?{}", config.birdeye_base_url, query);
-
-    // Set up HTTP client with required headers
-    let client = reqwest::Client::new();
-    let mut headers = HeaderMap::new();
-    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-    headers.insert(
-        "X-API-KEY",
-        HeaderValue::from_str(&config.birdeye_api_key).expect("Invalid API key format"),
-    );
-    headers.insert("x-chain", HeaderValue::from_static("solana"));
-
-    // Make the HTTP request and parse the JSON response
-    let response = client
-        .get(request_url)
-        .headers(headers)
-        .send()
-        .await?
-        .json::<BirdeyeHistoricalPriceResponse>()
-        .await?;
-
-    Ok(response)
-}
 
-/// Calculates the annualized volatility using the standard financial approach.
-///
-/// This function:
-/// 1. Computes the logarithmic daily returns
-/// 2. Calculates the standard deviation of these returns
-/// 3. Annualizes the result (multiplies by √365, as crypto markets trade 24/7/365)
+/// Days of the cache's trailing window when the volatility cache answers the query, `None`
+/// when the volatility has to be computed over the requested range.
 ///
-/// # Requirements
-/// - At least two price points.
-/// - Price points must be ordered chronologically.
-///
-/// # Example
-/// For standard financial volatility, we:
-/// 1. Calculate log returns: ln(P₁/P₀), ln(P₂/P₁), etc.
-/// 2. Find the standard deviation of these returns
-/// 3. Annualize by multiplying by √365 (for crypto markets)
-///
-/// instead of 252 days used for traditional stock markets
-pub fn calculate_volatility(prices: Vec<HistoricalPricePoint>) -> Option<f64> {
-    // Need at least 2 price points to calculate volatility
-    if prices.len() < 2 {
+/// A given `fromDate`/`toDate` is only served from the cache when it is the cached window
+/// itself, spanning the token's headline window and ending within
+/// `VOLATILITY_CACHE_MAX_AGE_SECS` of now, and a `days` look-back only when it is one of
+/// [`CACHED_WINDOW_DAYS`].
+pub(crate) fn cached_window(state: &AppState, query: &HistoricalVolatilityQuery) -> Option<u32> {
+    if !state.volatility_cache.covers(query.estimator, query.interval) {
         return None;
     }
+    let window_days = state.volatility_cache.window_days(&query.token_address);
+    match query.range {
+        DateRange::Default => Some(window_days),
+        DateRange::Trailing(days) => CACHED_WINDOW_DAYS.contains(&days).then_some(days),
+        DateRange::Explicit => {
+            let max_age = Duration::seconds(state.config.volatility_cache_max_age_secs as i64);
+            let is_cached_window = query.to_date - query.from_date == Duration::days(window_days as i64)
+                && Utc::now() - query.to_date <= max_age;
+            is_cached_window.then_some(window_days)
+        }
+    }
+}
 
-    // Sort the prices by time (oldest first) to ensure chronological order
-    let mut sorted_prices = prices;
-    sorted_prices.sort_by_key(|point| point.unix_time);
-
-    // Calculate the logarithmic daily returns
-    let log_returns: Vec<f64> = sorted_prices.windows(2).map(|window| {
-        let [previous, current] = window else {
-            unreachable!("prices.windows(2) always yields exactly two items");
-        };
-        // Log return formula: ln(P₁/P₀)
-        (current.value / previous.value).ln()
-    }).collect();
-
-    // We need at least one return to calculate standard deviation
-    if log_returns.is_empty() {
-        return None;
+/// Series behind the requested volatility: the cache's trailing daily window when the cache
+/// answers the query, the requested range otherwise.
+async fn get_calculation_series(
+    state: &AppState,
+    query: &HistoricalVolatilityQuery,
+) -> Result<Vec<Candle>, ApiError> {
+    let (from_date, to_date) = match cached_window(state, query) {
+        Some(window_days) => {
+            let to_date = Utc::now();
+            (to_date - Duration::days(window_days as i64), to_date)
+        }
+        None => (query.from_date, query.to_date),
+    };
+
+    let estimator = query.estimator.estimator();
+    fetch_series(
+        state,
+        estimator.as_ref(),
+        query.interval,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await
+}
+
+/// Return distribution of the log returns of `candles`.
+fn extended_stats(candles: &[Candle]) -> Result<ReturnStats, ApiError> {
+    let returns: Vec<f64> = return_series(candles, ReturnKind::Log)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    return_stats(&returns).ok_or(ApiError::NotEnoughData)
+}
+
+/// Converts a volatility annualized over `from_days` to one annualized over `to_days`.
+///
+/// Annualized volatility scales with √(periods per year), so cached values can be
+/// re-expressed under another convention without recomputing them.
+fn rescale_annualization(volatility: f64, from_days: u32, to_days: u32) -> f64 {
+    volatility * (to_days as f64 / from_days as f64).sqrt()
+}
+
+/// Computes volatility on demand for the query's date range with the requested estimator.
+async fn get_uncached_volatility(
+    state: &AppState,
+    query: &HistoricalVolatilityQuery,
+    annualization_days: u32,
+) -> Result<HistoricalVolatilityResponse, ApiError> {
+    let estimator = query.estimator.estimator();
+
+    let candles = fetch_series(
+        state,
+        estimator.as_ref(),
+        query.interval,
+        query.from_date,
+        query.to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let volatility = estimator
+        .annualized_volatility(
+            &candles,
+            query.interval.periods_per_year(annualization_days as f64),
+        )
+        .ok_or(ApiError::NotEnoughData)?;
+
+    let key = series_key(&query.token_address, query.interval, estimator.requires_ohlc());
+    let range_secs = (query.to_date - query.from_date).num_seconds().max(0) as u64;
+
+    Ok(HistoricalVolatilityResponse {
+        historical_volatility: volatility,
+        annualization_days,
+        data_points: candles.len(),
+        window_days: range_secs.div_ceil(86_400) as u32,
+        last_updated: state
+            .price_cache
+            .fetched_at(&key)
+            .await
+            .unwrap_or_else(Utc::now),
+        interval: query.interval,
+        provider: report::recorded().join(","),
+        extended_stats: None,
+        prices: None,
+    })
+}
+
+/// Price cache key of a token's series on the current request's chain and address type.
+fn series_key(token_address: &str, interval: Interval, ohlc: bool) -> SeriesKey {
+    SeriesKey {
+        chain: chain::current(),
+        address_type: address_type::current(),
+        token_address: token_address.to_string(),
+        interval,
+        ohlc,
     }
+}
 
-    // Calculate the mean of log returns
-    let mean = log_returns.iter().sum::<f64>() / log_returns.len() as f64;
+/// Fetches the price series an estimator needs (OHLC candles for range-based estimators,
+/// closes otherwise), serving from the price cache when possible and logging provider
+/// failures, mapping them to `ApiError` for use in handlers.
+///
+/// When the cache only partially covers the range, just the missing gaps are fetched.
+pub(crate) async fn fetch_series(
+    state: &AppState,
+    estimator: &dyn VolatilityEstimator,
+    interval: Interval,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+    token_address: &str,
+) -> Result<Vec<Candle>, ApiError> {
+    chain::current()
+        .validate_address(token_address)
+        .map_err(ApiError::InvalidQuery)?;
+    let key = series_key(token_address, interval, estimator.requires_ohlc());
+    let fetched = state
+        .price_cache
+        .fetch_through(state.provider.as_ref(), &state.monitor, &key, from_date, to_date)
+        .await
+        .map_err(|e| provider_error(state, token_address, e))?;
+    fetched.providers.into_iter().for_each(report::record);
 
-    // Calculate the variance (average of squared differences from the mean)
-    let variance = log_returns.iter()
-        .map(|&return_value| (return_value - mean).powi(2))
-        .sum::<f64>() / log_returns.len() as f64;
+    Ok(fetched.candles)
+}
 
-    // The daily volatility is the square root of the variance
-    let daily_volatility = variance.sqrt();
+/// Like [`fetch_series`], but straight from the provider without reading or filling the
+/// price cache, for series streamed a chunk at a time that shouldn't pile up in memory.
+pub(crate) async fn fetch_series_uncached(
+    state: &AppState,
+    estimator: &dyn VolatilityEstimator,
+    interval: Interval,
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+    token_address: &str,
+) -> Result<Vec<Candle>, ApiError> {
+    chain::current()
+        .validate_address(token_address)
+        .map_err(ApiError::InvalidQuery)?;
+    let result = state
+        .provider
+        .fetch_history(token_address, interval, from_date, to_date, estimator.requires_ohlc())
+        .await;
+    state
+        .monitor
+        .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+        .await;
+    let fetched = result.map_err(|e| provider_error(state, token_address, e))?;
+    report::record(fetched.provider);
+
+    Ok(fetched.candles)
+}
 
-    // Annualize the volatility using 365 days for crypto markets (which trade 24/7/365)
-    // instead of 252 days used for traditional stock markets
-    let annualized_volatility = daily_volatility * (365.0_f64).sqrt();
-    
-    // Convert to percentage for easier interpretation
-    Some(annualized_volatility * 100.0)
+/// Logs a failed provider request, mapping it to the error the handler returns.
+fn provider_error(state: &AppState, token_address: &str, e: impl std::fmt::Display) -> ApiError {
+    if deadline::expired() {
+        return ApiError::DeadlineExceeded;
+    }
+    error!(
+        token_address = %token_address,
+        error = %e,
+        provider = state.provider.name(),
+        "Price provider request failed"
+    );
+    ApiError::InternalServerError
 }
 
 //
@@ -248,148 +417,91 @@ pub fn calculate_volatility(prices: Vec<HistoricalPricePoint>) -> Option<f64> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Duration, Utc};
-    use dotenvy::dotenv;
-    use once_cell::sync::Lazy;
-
-    static INIT: Lazy<()> = Lazy::new(|| {
-        dotenv().ok();
-    });
-
-    fn test_config() -> AppConfig {
-        AppConfig {
-            birdeye_api_key: std::env::var("BIRDEYE_API_KEY")
-                .unwrap_or_else(|_| "dummy".to_string()),
-            birdeye_base_url: std::env::var("BIRDEYE_BASE_URL").unwrap_or_else(|_| {
-                "https://public-api.birdeye.so/token_price/history".to_string()
-            }),
-            app_server_port: 8080,
-        }
+    use crate::background::volatility_cache::VolatilityCache;
+    use crate::config::AppConfig;
+    use crate::math::close_to_close::CloseToClose;
+    use crate::provider::{PriceProvider, ProviderError, ProviderSeries};
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves a fixed daily series and counts how often it is asked for one.
+    #[derive(Default)]
+    struct StubProvider {
+        calls: AtomicUsize,
     }
 
-    fn from_and_to_dates(days: i64) -> (DateTime<Utc>, DateTime<Utc>) {
-        let to = Utc::now().date_naive() - Duration::days(1);
-        let from = to - Duration::days(days - 1);
-        (
-            from.and_hms_opt(0, 0, 0).unwrap().and_utc(),
-            to.and_hms_opt(0, 0, 0).unwrap().and_utc(),
-        )
-    }
+    #[async_trait]
+    impl PriceProvider for StubProvider {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
 
-    #[test]
-    fn test_calculate_volatility_with_three_prices() {
-        let prices = vec![
-            HistoricalPricePoint {
-                unix_time: 1,
-                value: 100.0,
-            },
-            HistoricalPricePoint {
-                unix_time: 2,
-                value: 105.0,
-            },
-            HistoricalPricePoint {
-                unix_time: 3,
-                value: 95.0,
-            },
-        ];
-        let result = calculate_volatility(prices).expect("Should calculate volatility");
-        
-        // With log returns: ln(105/100) ≈ 0.049, ln(95/105) ≈ -0.101
-        // Mean of log returns: (0.049 + (-0.101))/2 = -0.026
-        // Variance: ((0.049-(-0.026))² + (-0.101-(-0.026))²)/2 ≈ 0.0057
-        // Daily volatility: √0.0057 ≈ 0.075
-        // Annualized: 0.075 * √365 ≈ 4.24
-        // As percentage: 4.24 * 100 = 424%
-        assert!((result - 424.2).abs() < 1.0); // Allow some floating point error
-    }
+        fn history_endpoint(&self, _ohlc: bool) -> String {
+            String::new()
+        }
 
-    #[test]
-    fn test_calculate_volatility_with_two_prices() {
-        let prices = vec![
-            HistoricalPricePoint {
-                unix_time: 1,
-                value: 200.0,
-            },
-            HistoricalPricePoint {
-                unix_time: 2,
-                value: 180.0,
-            },
-        ];
-        let result = calculate_volatility(prices).expect("Should calculate volatility");
-        
-        // With log returns: ln(180/200) ≈ -0.105
-        // Mean of log returns: -0.105 (only one value)
-        // Variance: 0 (only one value, so no deviation from mean)
-        // Daily volatility: 0
-        // Annualized: 0 (note: this is an edge case with only 2 points)
-        // As percentage: 0
-        
-        // For single return case, the variance calculation will produce 0
-        // This is an edge case in volatility calculation
-        assert!(result.abs() < 1e-6);
-    }
+        async fn fetch_history(
+            &self,
+            _token_address: &str,
+            _interval: Interval,
+            from_date: DateTime<Utc>,
+            _to_date: DateTime<Utc>,
+            _ohlc: bool,
+        ) -> Result<ProviderSeries, ProviderError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let start = from_date.timestamp();
+            Ok(ProviderSeries {
+                provider: self.name(),
+                candles: (0..3)
+                    .map(|day| Candle::from_price(start + day * 86_400, 100.0 + day as f64))
+                    .collect(),
+            })
+        }
 
-    #[test]
-    fn test_calculate_volatility_with_more_realistic_data() {
-        let prices = vec![
-            HistoricalPricePoint { unix_time: 1, value: 100.0 },
-            HistoricalPricePoint { unix_time: 2, value: 102.0 },
-            HistoricalPricePoint { unix_time: 3, value: 99.0 },
-            HistoricalPricePoint { unix_time: 4, value: 101.0 },
-            HistoricalPricePoint { unix_time: 5, value: 103.0 },
-            HistoricalPricePoint { unix_time: 6, value: 102.5 },
-            HistoricalPricePoint { unix_time: 7, value: 103.5 },
-        ];
-        
-        let result = calculate_volatility(prices).expect("Should calculate volatility");
-        
-        // This is a more realistic volatility test with several data points
-        // For crypto with ~1-2% daily moves, annualized volatility using 365 days
-        // would typically be higher than stock markets, often between 20-80%
-        assert!(result > 15.0 && result < 85.0);
+        async fn fetch_current_price(&self, _token_address: &str) -> Result<f64, ProviderError> {
+            Ok(102.0)
+        }
     }
 
     #[test]
-    fn test_calculate_volatility_with_unsorted_data() {
-        // Test with unsorted time data to ensure the function sorts correctly
-        let prices = vec![
-            HistoricalPricePoint { unix_time: 3, value: 95.0 },   // Note: out of order
-            HistoricalPricePoint { unix_time: 1, value: 100.0 },  // Note: out of order
-            HistoricalPricePoint { unix_time: 2, value: 105.0 },  // Note: out of order
-        ];
-        
-        let result = calculate_volatility(prices).expect("Should calculate volatility");
-        
-        // Same expected result as test_calculate_volatility_with_three_prices
-        assert!((result - 424.2).abs() < 1.0);
+    fn test_rescale_annualization_from_crypto_to_tradfi_convention() {
+        let rescaled = rescale_annualization(100.0, 365, 252);
+        assert!((rescaled - 100.0 * (252.0_f64 / 365.0).sqrt()).abs() < 1e-9);
+        assert_eq!(rescale_annualization(42.0, 365, 365), 42.0);
     }
 
-    #[test]
-    fn test_calculate_volatility_with_insufficient_prices() {
-        let prices = vec![HistoricalPricePoint {
-            unix_time: 1,
-            value: 100.0,
-        }];
-        assert!(calculate_volatility(prices).is_none());
+    #[tokio::test]
+    async fn test_fetch_series_reads_through_the_price_cache() {
+        let provider = Arc::new(StubProvider::default());
+        let config = AppConfig::default();
+        let volatility_cache = VolatilityCache::with_provider(config.clone(), provider.clone());
+        let state = AppState::new(config, volatility_cache);
+
+        let to_date = Utc::now();
+        let from_date = to_date - chrono::Duration::days(2);
+        for _ in 0..2 {
+            let candles = fetch_series(&state, &CloseToClose, Interval::OneDay, from_date, to_date, "token")
+                .await
+                .expect("Stub provider should not fail");
+            assert_eq!(candles.len(), 3);
+        }
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
     }
 
     #[tokio::test]
-    #[ignore = "Expensive - real HTTP call"]
-    async fn test_make_birdeye_request_real() {
-        let _ = *INIT;
-        let config = test_config();
-        let (from_date, to_date) = from_and_to_dates(10);
-
-        let response = make_birdeye_request(
-            &config,
-            from_date,
-            to_date,
-            "So11111111111111111111111111111111111111112",
-        )
-        .await
-        .expect("Birdeye request should succeed");
-
-        let data = response.data.expect("Expected data field present");
-        assert_eq!(data.items.len(), 10);
+    async fn test_fetch_series_reuses_the_volatility_cache_series() {
+        let provider = Arc::new(StubProvider::default());
+        let config = AppConfig::default();
+        let volatility_cache = VolatilityCache::with_provider(config.clone(), provider.clone());
+        volatility_cache.add_token("token".to_string()).await.expect("Stub provider should not fail");
+        let state = AppState::new(config, volatility_cache);
+
+        let to_date = Utc::now() - chrono::Duration::days(1);
+        let from_date = to_date - chrono::Duration::days(30);
+        fetch_series(&state, &CloseToClose, Interval::OneDay, from_date, to_date, "token")
+            .await
+            .expect("Stub provider should not fail");
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
     }
 }
@@ -0,0 +1,125 @@
+//! # Historical Volatility Rank Handler
+//!
+//! Places a token's current N-day realized volatility within the range of its rolling
+//! N-day volatilities over a trailing look-back, analogous to IV rank / IV percentile.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::rolling::rolling_volatility;
+use crate::math::stats::{min_max_rank, percentile_of};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::trailing_window;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+const DEFAULT_HV_WINDOW_DAYS: u32 = 30;
+const DEFAULT_LOOKBACK_DAYS: u32 = 365;
+
+fn default_hv_window_days() -> u32 {
+    DEFAULT_HV_WINDOW_DAYS
+}
+
+fn default_lookback_days() -> u32 {
+    DEFAULT_LOOKBACK_DAYS
+}
+
+/// Query parameters for the HV rank request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HvRankQuery {
+    pub token_address: String,
+    /// Number of daily returns in each volatility window.
+    #[serde(default = "default_hv_window_days")]
+    pub window_days: u32,
+    /// How far back rolling volatilities are compared against.
+    #[serde(default = "default_lookback_days")]
+    pub lookback_days: u32,
+}
+
+/// Response structure for the HV rank endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HvRankResponse {
+    pub token_address: String,
+    pub window_days: u32,
+    pub lookback_days: u32,
+    /// Annualized volatility of the most recent window, in percent.
+    pub current_volatility: f64,
+    pub min_volatility: f64,
+    pub max_volatility: f64,
+    /// Where the current value sits between the look-back min (0) and max (100).
+    /// `None` when every rolling volatility is identical.
+    pub hv_rank: Option<f64>,
+    /// Share of rolling volatilities at or below the current value, in percent.
+    pub hv_percentile: f64,
+    /// Number of rolling windows compared against.
+    pub data_points: usize,
+}
+
+/// Axum handler returning the current volatility's rank and percentile over the look-back.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or when too little history
+///   is available to form a single window.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_hv_rank(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<HvRankQuery>,
+) -> Result<Json<HvRankResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        window_days = %query.window_days,
+        lookback_days = %query.lookback_days,
+        "Received HV rank request."
+    );
+
+    if query.window_days < 2 {
+        return Err(ApiError::InvalidQuery(
+            "windowDays must be at least 2.".to_string(),
+        ));
+    }
+    if query.lookback_days == 0 {
+        return Err(ApiError::InvalidQuery(
+            "lookbackDays must be greater than 0.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(
+        query.lookback_days.saturating_add(query.window_days),
+        state.config.max_date_range_days,
+    )?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let volatilities: Vec<f64> = rolling_volatility(&candles, query.window_days as usize, &CloseToClose, periods_per_year)
+        .into_iter()
+        .map(|point| point.volatility)
+        .collect();
+
+    let current_volatility = *volatilities.last().ok_or(ApiError::NotEnoughData)?;
+
+    Ok(Json(HvRankResponse {
+        current_volatility,
+        min_volatility: volatilities.iter().copied().fold(f64::INFINITY, f64::min),
+        max_volatility: volatilities.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        hv_rank: min_max_rank(&volatilities, current_volatility),
+        hv_percentile: percentile_of(&volatilities, current_volatility).unwrap_or_default(),
+        data_points: volatilities.len(),
+        token_address: query.token_address,
+        window_days: query.window_days,
+        lookback_days: query.lookback_days,
+    }))
+}
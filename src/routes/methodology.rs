@@ -0,0 +1,60 @@
+//! # Methodology Change Log Handler
+//!
+//! Reports the methodology version of the configured defaults and of each preset, with the
+//! log of preset changes, so downstream systems can tell a methodology change from a market
+//! move when numbers shift.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::presets::{Methodology, PresetChange};
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, instrument};
+
+/// Query parameters for the change log request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogQuery {
+    /// Only report changes of this preset.
+    pub preset: Option<String>,
+}
+
+/// Response structure for the change log endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLogResponse {
+    /// Methodology version of requests without a preset.
+    pub methodology_version: String,
+    pub defaults: Methodology,
+    /// Current methodology version of each defined preset, keyed by name.
+    pub presets: BTreeMap<String, String>,
+    /// Preset changes since startup, oldest first.
+    pub changes: Vec<PresetChange>,
+}
+
+/// Axum handler returning the methodology versions and the preset change log.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters.
+#[instrument(skip(state))]
+pub async fn get_change_log(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ChangeLogQuery>,
+) -> Result<Json<ChangeLogResponse>, ApiError> {
+    info!(preset = ?query.preset, "Received methodology change log request.");
+
+    let mut presets = BTreeMap::new();
+    for name in state.presets.all().await.into_keys() {
+        let version = state.presets.version(Some(&name)).await;
+        presets.insert(name, version);
+    }
+
+    Ok(Json(ChangeLogResponse {
+        methodology_version: state.presets.version(None).await,
+        defaults: state.presets.defaults().clone(),
+        presets,
+        changes: state.presets.changes(query.preset.as_deref()).await,
+    }))
+}
@@ -1,34 +1,190 @@
+use crate::background::clock_skew::annotate_clock_skew;
+use crate::cors::{self, RouteGroup};
+use crate::deprecations::mark_deprecations;
+use crate::presets::report_methodology_version;
+use crate::provider::report::{label_provider_environment, report_price_provider};
 use crate::state::AppState;
+use crate::symbols::resolve_symbols;
+use crate::utils::address_type::scope_address_type;
+use crate::utils::chain::scope_chain;
+use crate::utils::csv_format::render_csv;
+use crate::utils::deadline::enforce_deadline;
+use crate::utils::msgpack_format::render_msgpack;
+use crate::utils::number_format::render_numbers_as_strings;
+use crate::utils::timestamp_format::scope_timestamp_format;
 use axum::{
     http::{Response, StatusCode},
-    routing::get,
+    middleware,
+    routing::{get, post},
     Router,
 };
+#[cfg(feature = "admin")]
+use axum::routing::{delete, put};
+#[cfg(feature = "admin")]
+use admin::{
+    delete_preset, export_state, get_cache_stats, import_state, put_preset, track_token,
+    untrack_token, verify_token,
+};
+use backtest::get_backtest;
+use beta::get_beta;
+use correlation::get_correlation;
+use correlation_matrix::post_correlation_matrix;
+#[cfg(feature = "dashboard")]
+use dashboard::get_dashboard;
+use deprecations::get_deprecations;
+use drawdown::get_drawdown;
+use explain::explain_historical_volatility;
+#[cfg(feature = "parquet")]
+use export::{export_prices, export_volatility_series};
 use health_check::health_check;
+use heatmap::get_heatmap;
 use historical_volatility::get_historical_volatility;
+use hv_rank::get_hv_rank;
+use methodology::get_change_log;
+use option_price::get_option_price;
+use presets::get_presets;
+use prices::get_prices;
+use return_distribution::get_return_distribution;
+use returns::get_returns;
+use risk_adjusted_returns::get_risk_adjusted_returns;
+#[cfg(feature = "simulation")]
+use simulate::post_simulate;
+use status::get_status;
+use stress_test::post_stress_test;
+use token_metadata::get_token_metadata;
+use token_search::search_tokens;
+use tracked_tokens::get_tracked_tokens;
+use updates::get_updates;
+use value_at_risk::get_value_at_risk;
+use volatility_history::get_volatility_history;
+use vol_of_vol::get_vol_of_vol;
+use vol_regime::get_vol_regime;
 use tower_http::{
     catch_panic::CatchPanicLayer,
     trace::{DefaultOnRequest, TraceLayer},
 };
 use tracing::Level;
 
+#[cfg(feature = "admin")]
+pub mod admin;
+pub mod backtest;
+pub mod beta;
+pub mod correlation;
+pub mod correlation_matrix;
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+pub mod deprecations;
+pub mod drawdown;
+pub mod explain;
+#[cfg(feature = "parquet")]
+pub mod export;
 pub mod health_check;
+pub mod heatmap;
 pub mod historical_volatility;
+pub mod hv_rank;
+pub mod methodology;
+pub mod option_price;
+pub mod presets;
+pub mod prices;
+pub mod return_distribution;
+pub mod returns;
+pub mod risk_adjusted_returns;
+#[cfg(feature = "simulation")]
+pub mod simulate;
+pub mod status;
+pub mod stress_test;
+pub mod token_metadata;
+pub mod token_search;
+pub mod tracked_tokens;
+pub mod updates;
+pub mod value_at_risk;
+pub mod volatility_history;
+pub mod vol_of_vol;
+pub mod vol_regime;
 
 pub fn register_routes(state: AppState) -> Router {
-    // TODO (Pen): I'll need to think about the CORS.
-    // let cors = CorsLayer::new()
-    // .allow_methods(Any)
-    // .allow_origin(Any)
-    // .allow_headers(Any);
-
-    Router::new()
-        .route("/historicalVolatility", get(get_historical_volatility))
+    let router = Router::new()
+        .route(
+            "/historicalVolatility",
+            get(get_historical_volatility).layer(middleware::from_fn(render_csv)),
+        )
+        .route("/historicalVolatility/explain", get(explain_historical_volatility))
+        .route("/presets", get(get_presets))
+        .route("/methodology/changelog", get(get_change_log))
+        .route("/deprecations", get(get_deprecations))
+        .route("/returns", get(get_returns))
+        .route("/prices", get(get_prices).layer(middleware::from_fn(render_csv)))
+        .route("/correlation", get(get_correlation))
+        .route("/beta", get(get_beta))
+        .route("/hvRank", get(get_hv_rank))
+        .route("/volOfVol", get(get_vol_of_vol))
+        .route("/volRegime", get(get_vol_regime))
+        .route("/backtest", get(get_backtest))
+        .route("/correlationMatrix", post(post_correlation_matrix))
+        .route("/heatmap", get(get_heatmap))
+        .route("/updates", get(get_updates))
+        .route("/volatilityHistory", get(get_volatility_history))
+        .route("/drawdown", get(get_drawdown))
+        .route("/returnDistribution", get(get_return_distribution))
+        .route("/riskAdjustedReturns", get(get_risk_adjusted_returns))
+        .route("/var", get(get_value_at_risk))
+        .route("/optionPrice", get(get_option_price))
+        .route("/simulate/stress", post(post_stress_test))
+        .route("/tokens", get(get_tracked_tokens))
+        .route("/tokens/search", get(search_tokens))
+        .route("/token/{address}", get(get_token_metadata))
         .route("/healthCheck", get(health_check))
-        .with_state(state)
+        .route("/status", get(get_status));
+
+    // Optional subsystems, see the `[features]` table in Cargo.toml
+    #[cfg(feature = "simulation")]
+    let router = router.route("/simulate", post(post_simulate));
+    #[cfg(feature = "dashboard")]
+    let router = router.route("/dashboard", get(get_dashboard));
+    #[cfg(feature = "parquet")]
+    let router = router
+        .route("/export/prices.parquet", get(export_prices))
+        .route("/export/volatilitySeries.parquet", get(export_volatility_series));
+    let router = with_cors(router, RouteGroup::Public, &state.config.cors_public_origins);
+
+    #[cfg(feature = "admin")]
+    let router = router.merge(with_cors(
+        Router::new()
+            .route("/admin/cacheStats", get(get_cache_stats))
+            .route("/admin/export", get(export_state))
+            .route("/admin/import", post(import_state))
+            .route("/admin/presets/{name}", put(put_preset).delete(delete_preset))
+            .route("/admin/tokens", post(track_token))
+            .route("/admin/tokens/{address}", delete(untrack_token))
+            .route("/admin/verify/{token}", get(verify_token)),
+        RouteGroup::Admin,
+        &state.config.cors_admin_origins,
+    ));
+
+    router
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(state.clone(), resolve_symbols))
+        .layer(middleware::from_fn_with_state(state.clone(), mark_deprecations))
+        .layer(middleware::from_fn(report_price_provider))
+        .layer(middleware::from_fn_with_state(state.clone(), label_provider_environment))
+        .layer(middleware::from_fn_with_state(state.clone(), report_methodology_version))
+        .layer(middleware::from_fn_with_state(state, annotate_clock_skew))
+        .layer(middleware::from_fn(scope_timestamp_format))
+        .layer(middleware::from_fn(render_numbers_as_strings))
+        .layer(middleware::from_fn(render_msgpack))
+        .layer(middleware::from_fn(scope_chain))
+        .layer(middleware::from_fn(scope_address_type))
+        .layer(middleware::from_fn(enforce_deadline))
         .layer(CatchPanicLayer::custom(|_err| panic_handler()))
         .layer(TraceLayer::new_for_http().on_request(DefaultOnRequest::new().level(Level::INFO)))
-    // .layer(cors)
+}
+
+/// Applies the CORS policy of `group` to the routes of `router`, if it allows any origin.
+fn with_cors(router: Router<AppState>, group: RouteGroup, origins: &[String]) -> Router<AppState> {
+    match cors::layer(group, origins) {
+        Some(layer) => router.layer(layer),
+        None => router,
+    }
 }
 
 fn panic_handler() -> Response<String> {
@@ -0,0 +1,130 @@
+//! # Option Price Handler
+//!
+//! Prices a European option on a token with Black-Scholes, using the token's historical
+//! volatility (from the configured estimator, over the trailing window) as sigma and its
+//! latest close as spot.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::black_scholes::{price_european, OptionQuote, OptionType};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::custom_date_serde;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the option price request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionPriceQuery {
+    pub token_address: String,
+    pub strike: f64,
+    /// Expiry date; the option expires at 00:00 UTC that day.
+    #[serde(with = "custom_date_serde")]
+    pub expiry: DateTime<Utc>,
+    pub option_type: OptionType,
+    /// Annualized risk-free rate as a decimal. Defaults to `RISK_FREE_RATE`.
+    pub risk_free_rate: Option<f64>,
+    /// Days of history the volatility is computed over.
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+}
+
+/// Response structure for the option price endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionPriceResponse {
+    pub token_address: String,
+    pub option_type: OptionType,
+    pub strike: f64,
+    #[serde(with = "custom_date_serde")]
+    pub expiry: DateTime<Utc>,
+    /// Latest close used as the underlying price.
+    pub spot: f64,
+    /// Annualized historical volatility used as sigma, in percent.
+    pub historical_volatility: f64,
+    /// Time to expiry in years.
+    pub years_to_expiry: f64,
+    #[serde(flatten)]
+    pub quote: OptionQuote,
+}
+
+/// Axum handler returning the Black-Scholes value of a European option on a token.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters, a non-positive strike, an
+///   expiry that is not in the future, or too little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_option_price(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<OptionPriceQuery>,
+) -> Result<Json<OptionPriceResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        strike = %query.strike,
+        expiry = %query.expiry,
+        option_type = ?query.option_type,
+        "Received option price request."
+    );
+
+    if query.strike <= 0.0 {
+        return Err(ApiError::InvalidQuery(
+            "strike must be greater than 0.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    if query.expiry <= to_date {
+        return Err(ApiError::InvalidQuery(
+            "expiry must be in the future.".to_string(),
+        ));
+    }
+
+    let estimator = state.config.volatility_estimator.estimator();
+    let candles = fetch_series(
+        &state,
+        estimator.as_ref(),
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let spot = candles
+        .iter()
+        .max_by_key(|candle| candle.unix_time)
+        .map(|candle| candle.close)
+        .ok_or(ApiError::NotEnoughData)?;
+    let annualization_days = state.config.annualization_days as f64;
+    let historical_volatility = estimator
+        .annualized_volatility(&candles, Interval::OneDay.periods_per_year(annualization_days))
+        .ok_or(ApiError::NotEnoughData)?;
+
+    let years_to_expiry = (query.expiry - to_date).num_seconds() as f64 / (annualization_days * 86_400.0);
+    let quote = price_european(
+        query.option_type,
+        spot,
+        query.strike,
+        years_to_expiry,
+        historical_volatility / 100.0,
+        query.risk_free_rate.unwrap_or(state.config.risk_free_rate),
+    )
+    .ok_or(ApiError::NotEnoughData)?;
+
+    Ok(Json(OptionPriceResponse {
+        token_address: query.token_address,
+        option_type: query.option_type,
+        strike: query.strike,
+        expiry: query.expiry,
+        spot,
+        historical_volatility,
+        years_to_expiry,
+        quote,
+    }))
+}
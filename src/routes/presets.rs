@@ -0,0 +1,28 @@
+//! # Presets Handler
+//!
+//! Lists the calculation presets accepted by `preset=` on `/historicalVolatility`, so teams
+//! can see which methodology a name stands for.
+
+use crate::presets::Preset;
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, instrument};
+
+/// Response structure for the presets endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PresetsResponse {
+    /// Presets keyed by name.
+    pub presets: BTreeMap<String, Preset>,
+}
+
+/// Axum handler listing the defined presets.
+#[instrument(skip(state))]
+pub async fn get_presets(State(state): State<AppState>) -> Json<PresetsResponse> {
+    let presets = state.presets.all().await;
+    info!(presets = %presets.len(), "Listing calculation presets.");
+
+    Json(PresetsResponse { presets })
+}
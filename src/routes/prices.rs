@@ -0,0 +1,163 @@
+//! # Price History Handler
+//!
+//! Proxies the Birdeye price series the service fetches (through the shared price cache),
+//! so frontends can overlay prices on volatility charts without their own Birdeye key.
+//!
+//! Requests accepting `application/x-ndjson` get the series streamed as one JSON line per
+//! price, fetched a chunk of candles at a time straight from the provider, so long ranges
+//! start arriving before the whole range is fetched and never sit in memory at once, in
+//! the handler or in the price cache.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::{Candle, Interval};
+use crate::provider::birdeye::HistoricalPricePoint;
+use crate::routes::historical_volatility::{fetch_series, fetch_series_uncached};
+use crate::state::AppState;
+use crate::utils::custom_date_serde;
+use crate::utils::date_window::validate_range;
+use crate::utils::ndjson;
+use crate::utils::request_scope::RequestScope;
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Candles fetched per chunk of a streamed price series.
+const STREAM_CHUNK_CANDLES: i64 = 500;
+
+/// Query parameters for the price history request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PricesQuery {
+    #[serde(with = "custom_date_serde")]
+    pub from_date: DateTime<Utc>,
+    #[serde(with = "custom_date_serde")]
+    pub to_date: DateTime<Utc>,
+    pub token_address: String,
+    #[serde(default)]
+    pub interval: Interval,
+}
+
+/// Response structure for the price history endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PricesResponse {
+    pub token_address: String,
+    pub interval: Interval,
+    pub prices: Vec<HistoricalPricePoint>,
+}
+
+/// Axum handler returning the cached Birdeye price series for a token and date range, as
+/// a [`PricesResponse`] or, for requests accepting `application/x-ndjson`, a stream of
+/// price lines.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or date range.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures. Once a stream has
+///   started, failures end it with a line holding the error body instead.
+#[instrument(skip(state, headers))]
+pub async fn get_prices(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ApiQuery(query): ApiQuery<PricesQuery>,
+) -> Result<Response, ApiError> {
+    info!(
+        from_date = %query.from_date,
+        to_date = %query.to_date,
+        token_address = %query.token_address,
+        interval = %query.interval,
+        "Received price history request."
+    );
+
+    validate_range(query.from_date, query.to_date, state.config.max_date_range_days)?;
+    if ndjson::accepts_ndjson(&headers) {
+        return stream_prices(state, query).await;
+    }
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        query.from_date,
+        query.to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    Ok(Json(PricesResponse {
+        prices: candles
+            .iter()
+            .map(|candle| HistoricalPricePoint {
+                unix_time: candle.unix_time,
+                value: candle.close,
+            })
+            .collect(),
+        token_address: query.token_address,
+        interval: query.interval,
+    })
+    .into_response())
+}
+
+/// Streams the price series a chunk of [`STREAM_CHUNK_CANDLES`] at a time.
+///
+/// Chunks bypass the price cache, which would otherwise end up holding the whole range.
+/// The first chunk is fetched before answering, so a request that fails outright still gets
+/// an error status.
+async fn stream_prices(state: AppState, query: PricesQuery) -> Result<Response, ApiError> {
+    let span = Duration::seconds(query.interval.seconds() * STREAM_CHUNK_CANDLES);
+    let first_to = (query.from_date + span).min(query.to_date);
+    let first = fetch_series_uncached(
+        &state,
+        &CloseToClose,
+        query.interval,
+        query.from_date,
+        first_to,
+        &query.token_address,
+    )
+    .await?;
+    let first = ndjson::lines(first.iter().map(price_point));
+
+    // Each later chunk starts where the previous one ended, skipping the candle both hold
+    let scope = RequestScope::capture();
+    let rest = stream::unfold(Some(first_to), move |next_from| {
+        let state = state.clone();
+        let token_address = query.token_address.clone();
+        async move {
+            let from = next_from.filter(|from| *from < query.to_date)?;
+            let to = (from + span).min(query.to_date);
+            let chunk = scope
+                .run(async {
+                    match fetch_series_uncached(&state, &CloseToClose, query.interval, from, to, &token_address).await {
+                        Ok(candles) => Ok(ndjson::lines(
+                            candles
+                                .iter()
+                                .filter(|candle| candle.unix_time > from.timestamp())
+                                .map(price_point),
+                        )),
+                        Err(err) => Err(ndjson::error_line(err).await),
+                    }
+                })
+                .await;
+            Some(match chunk {
+                Ok(lines) => (lines, Some(to)),
+                Err(line) => (line, None),
+            })
+        }
+    });
+
+    Ok(ndjson::response(stream::iter([first]).chain(rest)))
+}
+
+fn price_point(candle: &Candle) -> HistoricalPricePoint {
+    HistoricalPricePoint {
+        unix_time: candle.unix_time,
+        value: candle.close,
+    }
+}
@@ -0,0 +1,115 @@
+//! # Return Distribution Handler
+//!
+//! Buckets a token's log returns over a trailing window into a histogram and fits a normal
+//! distribution to them, so frontends can chart the distribution against the bell curve.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::stats::{histogram, mean, variance, HistogramBin};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Largest number of bins accepted by `/returnDistribution`.
+pub const MAX_BINS: usize = 100;
+
+fn default_bins() -> usize {
+    20
+}
+
+/// Query parameters for the return distribution request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnDistributionQuery {
+    pub token_address: String,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+    /// Number of equal-width bins between the smallest and largest return.
+    #[serde(default = "default_bins")]
+    pub bins: usize,
+}
+
+/// Normal distribution fitted to the returns by their sample mean and standard deviation.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FittedNormal {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// Response structure for the return distribution endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnDistributionResponse {
+    pub token_address: String,
+    pub days: u32,
+    pub interval: Interval,
+    /// Number of log returns bucketed.
+    pub data_points: usize,
+    pub histogram: Vec<HistogramBin>,
+    pub normal: FittedNormal,
+}
+
+/// Axum handler returning the histogram of a token's log returns.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters, a `bins` outside
+///   `1..=MAX_BINS`, or fewer than two returns.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_return_distribution(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ReturnDistributionQuery>,
+) -> Result<Json<ReturnDistributionResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        days = %query.days,
+        interval = %query.interval,
+        bins = %query.bins,
+        "Received return distribution request."
+    );
+
+    if query.bins == 0 || query.bins > MAX_BINS {
+        return Err(ApiError::InvalidQuery(format!(
+            "bins must be between 1 and {}.",
+            MAX_BINS
+        )));
+    }
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let returns: Vec<f64> = return_series(&candles, ReturnKind::Log)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    let normal = FittedNormal {
+        mean: mean(&returns).ok_or(ApiError::NotEnoughData)?,
+        std_dev: variance(&returns).ok_or(ApiError::NotEnoughData)?.sqrt(),
+    };
+
+    Ok(Json(ReturnDistributionResponse {
+        histogram: histogram(&returns, query.bins).ok_or(ApiError::NotEnoughData)?,
+        data_points: returns.len(),
+        normal,
+        token_address: query.token_address,
+        days: query.days,
+        interval: query.interval,
+    }))
+}
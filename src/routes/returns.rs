@@ -0,0 +1,81 @@
+//! # Returns Handler
+//!
+//! Exposes the return series the volatility calculations are built on, so clients can
+//! run their own models on top of the same data pipeline.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind, ReturnPoint};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::custom_date_serde;
+use crate::utils::date_window::validate_range;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the returns request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnsQuery {
+    #[serde(with = "custom_date_serde")]
+    pub from_date: DateTime<Utc>,
+    #[serde(with = "custom_date_serde")]
+    pub to_date: DateTime<Utc>,
+    pub token_address: String,
+    #[serde(default)]
+    pub interval: Interval,
+    /// `log` (default) or `simple` returns.
+    #[serde(default)]
+    pub return_type: ReturnKind,
+}
+
+/// Response structure for the returns endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReturnsResponse {
+    pub token_address: String,
+    pub interval: Interval,
+    pub return_type: ReturnKind,
+    pub returns: Vec<ReturnPoint>,
+}
+
+/// Axum handler returning the time-stamped return series for a token and date range.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or date range.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_returns(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ReturnsQuery>,
+) -> Result<Json<ReturnsResponse>, ApiError> {
+    info!(
+        from_date = %query.from_date,
+        to_date = %query.to_date,
+        token_address = %query.token_address,
+        interval = %query.interval,
+        "Received returns request."
+    );
+
+    validate_range(query.from_date, query.to_date, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        query.from_date,
+        query.to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    Ok(Json(ReturnsResponse {
+        returns: return_series(&candles, query.return_type),
+        token_address: query.token_address,
+        interval: query.interval,
+        return_type: query.return_type,
+    }))
+}
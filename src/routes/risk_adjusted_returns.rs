@@ -0,0 +1,108 @@
+//! # Risk-Adjusted Returns Handler
+//!
+//! Reports annualized Sharpe and Sortino ratios of a token's simple returns over a
+//! trailing window, against the configured (or requested) risk-free rate.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::stats::{mean, variance};
+use crate::math::Interval;
+use crate::risk::ratios::{sharpe_ratio, sortino_ratio};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Query parameters for the risk-adjusted returns request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskAdjustedReturnsQuery {
+    pub token_address: String,
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default)]
+    pub interval: Interval,
+    /// Annualized risk-free rate as a decimal. Defaults to `RISK_FREE_RATE`.
+    pub risk_free_rate: Option<f64>,
+}
+
+/// Response structure for the risk-adjusted returns endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskAdjustedReturnsResponse {
+    pub token_address: String,
+    pub days: u32,
+    pub interval: Interval,
+    pub risk_free_rate: f64,
+    /// Number of returns used.
+    pub data_points: usize,
+    /// Mean per-period simple return times periods per year, as a decimal.
+    pub annualized_return: f64,
+    /// Annualized standard deviation of simple returns, as a decimal.
+    pub annualized_volatility: f64,
+    /// `None` when returns don't vary.
+    pub sharpe: Option<f64>,
+    /// `None` when no period underperformed the risk-free rate.
+    pub sortino: Option<f64>,
+}
+
+/// Axum handler returning Sharpe and Sortino ratios for a token.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or too little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_risk_adjusted_returns(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<RiskAdjustedReturnsQuery>,
+) -> Result<Json<RiskAdjustedReturnsResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        days = %query.days,
+        interval = %query.interval,
+        "Received risk-adjusted returns request."
+    );
+
+    let risk_free_rate = query.risk_free_rate.unwrap_or(state.config.risk_free_rate);
+    if !risk_free_rate.is_finite() {
+        return Err(ApiError::InvalidQuery(
+            "riskFreeRate must be a finite number.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        query.interval,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let returns: Vec<f64> = return_series(&candles, ReturnKind::Simple)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    let periods_per_year = query
+        .interval
+        .periods_per_year(state.config.annualization_days as f64);
+
+    Ok(Json(RiskAdjustedReturnsResponse {
+        annualized_return: mean(&returns).ok_or(ApiError::NotEnoughData)? * periods_per_year,
+        annualized_volatility: variance(&returns).ok_or(ApiError::NotEnoughData)?.sqrt()
+            * periods_per_year.sqrt(),
+        sharpe: sharpe_ratio(&returns, risk_free_rate, periods_per_year),
+        sortino: sortino_ratio(&returns, risk_free_rate, periods_per_year),
+        data_points: returns.len(),
+        risk_free_rate,
+        token_address: query.token_address,
+        days: query.days,
+        interval: query.interval,
+    }))
+}
@@ -0,0 +1,181 @@
+//! # Monte Carlo Simulation Handler
+//!
+//! Simulates future price paths for a token from its historical daily log returns and
+//! reports percentile bands of the terminal price. Simulations run on the blocking pool
+//! so large path counts don't stall the async runtime.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::json_extractor::ApiJson;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::simulation::{simulate_terminal_prices, SimulationMethod, SimulationParams};
+use crate::math::stats::{mean, quantile};
+use crate::math::{Interval, VolatilityEstimator};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+/// Upper bound on simulated paths per request.
+pub const MAX_PATHS: usize = 100_000;
+/// Upper bound on the simulated horizon.
+pub const MAX_HORIZON_DAYS: u32 = 365;
+/// Terminal-price percentiles reported in every response.
+const PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+fn default_horizon_days() -> u32 {
+    30
+}
+
+fn default_paths() -> usize {
+    1_000
+}
+
+/// Request body for the Monte Carlo simulation endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MonteCarloRequest {
+    pub token_address: String,
+    /// Days of history the drift, volatility and bootstrap samples are taken from.
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    #[serde(default = "default_horizon_days")]
+    pub horizon_days: u32,
+    #[serde(default = "default_paths")]
+    pub paths: usize,
+    #[serde(default)]
+    pub method: SimulationMethod,
+    /// RNG seed; a random one is chosen (and echoed back) when omitted.
+    pub seed: Option<u64>,
+}
+
+/// A terminal-price percentile.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PercentileBand {
+    pub percentile: f64,
+    pub price: f64,
+}
+
+/// Response structure for the Monte Carlo simulation endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MonteCarloResponse {
+    pub token_address: String,
+    pub method: SimulationMethod,
+    pub horizon_days: u32,
+    pub paths: usize,
+    /// Seed that reproduces this result.
+    pub seed: u64,
+    /// Latest close the paths start from.
+    pub start_price: f64,
+    /// Annualized historical volatility the simulation was seeded with, in percent.
+    pub historical_volatility: f64,
+    /// Mean daily log return of the history.
+    pub daily_drift: f64,
+    pub mean_terminal_price: f64,
+    pub percentiles: Vec<PercentileBand>,
+}
+
+/// Axum handler running a Monte Carlo simulation of a token's price.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an invalid body, out-of-range `paths` or `horizonDays`,
+///   or too little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn post_simulate(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<MonteCarloRequest>,
+) -> Result<Json<MonteCarloResponse>, ApiError> {
+    info!(
+        token_address = %request.token_address,
+        method = ?request.method,
+        paths = %request.paths,
+        horizon_days = %request.horizon_days,
+        "Received simulation request."
+    );
+
+    if request.paths == 0 || request.paths > MAX_PATHS {
+        return Err(ApiError::InvalidBody(format!(
+            "paths must be between 1 and {}.",
+            MAX_PATHS
+        )));
+    }
+    if request.horizon_days == 0 || request.horizon_days > MAX_HORIZON_DAYS {
+        return Err(ApiError::InvalidBody(format!(
+            "horizonDays must be between 1 and {}.",
+            MAX_HORIZON_DAYS
+        )));
+    }
+
+    let (from_date, to_date) = trailing_window(request.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &request.token_address,
+    )
+    .await?;
+
+    let start_price = candles
+        .iter()
+        .max_by_key(|candle| candle.unix_time)
+        .map(|candle| candle.close)
+        .ok_or(ApiError::NotEnoughData)?;
+    let log_returns: Vec<f64> = return_series(&candles, ReturnKind::Log)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    let daily_drift = mean(&log_returns).ok_or(ApiError::NotEnoughData)?;
+    let historical_volatility = CloseToClose
+        .annualized_volatility(
+            &candles,
+            Interval::OneDay.periods_per_year(state.config.annualization_days as f64),
+        )
+        .ok_or(ApiError::NotEnoughData)?;
+
+    let seed = request.seed.unwrap_or_else(rand::random);
+    let params = SimulationParams {
+        start_price,
+        log_returns,
+        horizon: request.horizon_days as usize,
+        paths: request.paths,
+        seed,
+    };
+    let method = request.method;
+    let terminal_prices = tokio::task::spawn_blocking(move || simulate_terminal_prices(&params, method))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Simulation task failed");
+            ApiError::InternalServerError
+        })?
+        .ok_or(ApiError::NotEnoughData)?;
+
+    let percentiles = PERCENTILES
+        .iter()
+        .filter_map(|&percentile| {
+            Some(PercentileBand {
+                percentile,
+                price: quantile(&terminal_prices, percentile / 100.0)?,
+            })
+        })
+        .collect();
+
+    Ok(Json(MonteCarloResponse {
+        historical_volatility,
+        mean_terminal_price: mean(&terminal_prices).ok_or(ApiError::NotEnoughData)?,
+        percentiles,
+        daily_drift,
+        start_price,
+        seed,
+        token_address: request.token_address,
+        method,
+        horizon_days: request.horizon_days,
+        paths: request.paths,
+    }))
+}
@@ -0,0 +1,59 @@
+use crate::background::health_monitor::HealthSnapshot;
+use crate::provider::chain::ProviderHealth;
+use crate::provider::ProviderEnvironment;
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Upstream error rate above which the service is reported as degraded.
+const DEGRADED_UPSTREAM_ERROR_RATE: f64 = 0.5;
+
+/// Overall state reported to the status page.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OverallStatus {
+    Operational,
+    Degraded,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusResponse {
+    pub status: OverallStatus,
+    /// `sandbox` when providers point at sandbox or testnet endpoints.
+    pub environment: ProviderEnvironment,
+    #[serde(flatten)]
+    pub health: HealthSnapshot,
+    /// Call counters of each configured provider, in failover order.
+    pub providers: Vec<ProviderHealth>,
+}
+
+/// Status endpoint.
+///
+/// Returns uptime, the upstream error rate and background refresh success rate over
+/// recent sliding windows, per-provider call counters, and the most recent incident
+/// markers, in a machine-readable form for the public status page.
+///
+/// The service is `degraded` when the last background cycle failed, more than half of the
+/// recent upstream calls errored, or the host clock is skewed against the provider's.
+#[instrument(skip(state))]
+pub async fn get_status(State(state): State<AppState>) -> Json<StatusResponse> {
+    info!("Received status request.");
+    let health = state.monitor.snapshot().await;
+
+    let degraded = health.last_cycle_succeeded == Some(false)
+        || health.upstream_error_rate > DEGRADED_UPSTREAM_ERROR_RATE
+        || health.clock_skewed;
+
+    Json(StatusResponse {
+        status: if degraded {
+            OverallStatus::Degraded
+        } else {
+            OverallStatus::Operational
+        },
+        environment: state.config.provider_environment,
+        health,
+        providers: state.provider.health().await,
+    })
+}
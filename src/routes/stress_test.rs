@@ -0,0 +1,154 @@
+//! # Stress Test Handler
+//!
+//! Applies hypothetical shocks (a sudden drop, a stretch of elevated volatility) to a
+//! token's historical daily returns and reports volatility and VaR before and after, so
+//! risk can see how pricing inputs would move under a scenario.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::json_extractor::ApiJson;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::stats::variance;
+use crate::math::{annualize_variance, Interval};
+use crate::risk::stress::{apply_shocks, Shock};
+use crate::risk::value_at_risk::{historical, parametric, RiskEstimate};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// Upper bound on shocks per scenario.
+pub const MAX_SHOCKS: usize = 20;
+
+fn default_confidence() -> f64 {
+    0.95
+}
+
+fn default_horizon_days() -> u32 {
+    1
+}
+
+/// Request body for the stress test endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StressTestRequest {
+    pub token_address: String,
+    /// Days of history the shocks are applied to.
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+    /// Shocks applied in order to the most recent returns.
+    pub shocks: Vec<Shock>,
+    /// VaR confidence level in `(0, 1)`.
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    #[serde(default = "default_horizon_days")]
+    pub horizon_days: u32,
+}
+
+/// Risk figures of one return series.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioMetrics {
+    /// Number of daily returns.
+    pub data_points: usize,
+    /// Annualized volatility, in percent.
+    pub historical_volatility: f64,
+    pub parametric_var: RiskEstimate,
+    /// `None` when the series holds too few horizon-length returns.
+    pub historical_var: Option<RiskEstimate>,
+}
+
+/// Response structure for the stress test endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StressTestResponse {
+    pub token_address: String,
+    pub days: u32,
+    pub confidence: f64,
+    pub horizon_days: u32,
+    pub shocks: Vec<Shock>,
+    pub baseline: ScenarioMetrics,
+    pub stressed: ScenarioMetrics,
+}
+
+/// Axum handler stress-testing a token's volatility and VaR under a shock scenario.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an invalid body, an invalid or missing shock, or too
+///   little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn post_stress_test(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<StressTestRequest>,
+) -> Result<Json<StressTestResponse>, ApiError> {
+    info!(
+        token_address = %request.token_address,
+        shocks = %request.shocks.len(),
+        "Received stress test request."
+    );
+
+    if request.shocks.is_empty() || request.shocks.len() > MAX_SHOCKS {
+        return Err(ApiError::InvalidBody(format!(
+            "shocks must contain between 1 and {} entries.",
+            MAX_SHOCKS
+        )));
+    }
+    for shock in &request.shocks {
+        shock.validate().map_err(ApiError::InvalidBody)?;
+    }
+    if !(request.confidence > 0.0 && request.confidence < 1.0) {
+        return Err(ApiError::InvalidBody(
+            "confidence must be between 0 and 1 (exclusive).".to_string(),
+        ));
+    }
+    if request.horizon_days == 0 {
+        return Err(ApiError::InvalidBody(
+            "horizonDays must be greater than 0.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(request.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &request.token_address,
+    )
+    .await?;
+
+    let log_returns: Vec<f64> = return_series(&candles, ReturnKind::Log)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    let stressed_returns = apply_shocks(&log_returns, &request.shocks);
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let metrics = |returns: &[f64]| -> Result<ScenarioMetrics, ApiError> {
+        let horizon = request.horizon_days as usize;
+        Ok(ScenarioMetrics {
+            data_points: returns.len(),
+            historical_volatility: annualize_variance(
+                variance(returns).ok_or(ApiError::NotEnoughData)?,
+                periods_per_year,
+            ),
+            parametric_var: parametric(returns, request.confidence, horizon)
+                .ok_or(ApiError::NotEnoughData)?,
+            historical_var: historical(returns, request.confidence, horizon),
+        })
+    };
+
+    Ok(Json(StressTestResponse {
+        baseline: metrics(&log_returns)?,
+        stressed: metrics(&stressed_returns)?,
+        token_address: request.token_address,
+        days: request.days,
+        confidence: request.confidence,
+        horizon_days: request.horizon_days,
+        shocks: request.shocks,
+    }))
+}
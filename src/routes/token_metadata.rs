@@ -0,0 +1,147 @@
+//! # Token Metadata Handler
+//!
+//! Returns a token's name, symbol, decimals and logo from Birdeye's token metadata (itself
+//! read from on-chain metadata accounts), cached so frontends can label volatility results
+//! without lookups of their own.
+
+use crate::background::metadata_cache::TokenMetadata;
+use crate::config::AppConfig;
+use crate::errors::api_error::ApiError;
+use crate::state::AppState;
+use crate::symbols::is_symbol;
+use crate::utils::chain::{self, Chain};
+use crate::utils::deadline::{self, with_deadline};
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue},
+    Json,
+};
+use reqwest::header::ACCEPT;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+/// Response structure for the token metadata endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadataResponse {
+    pub address: String,
+    pub chain: Chain,
+    #[serde(flatten)]
+    pub metadata: TokenMetadata,
+}
+
+/// Raw structure of the response returned by the Birdeye token metadata API.
+#[derive(Debug, Deserialize)]
+struct BirdeyeMetadataResponse {
+    data: Option<BirdeyeTokenMetadata>,
+    success: bool,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeTokenMetadata {
+    name: Option<String>,
+    symbol: Option<String>,
+    decimals: Option<u8>,
+    logo_uri: Option<String>,
+}
+
+/// Axum handler returning a token's metadata. `address` may also be a symbol.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an address that is malformed on the requested chain,
+///   or an unknown or ambiguous symbol.
+/// - Returns `404 Not Found` when Birdeye has no metadata for the token.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_token_metadata(
+    State(state): State<AppState>,
+    Path(address): Path<String>,
+) -> Result<Json<TokenMetadataResponse>, ApiError> {
+    info!(address = %address, "Received token metadata request.");
+
+    let chain = chain::current();
+    let address = if is_symbol(&address) {
+        state.symbols.resolve(&state, &address).await?
+    } else {
+        chain.validate_address(&address).map_err(ApiError::InvalidQuery)?;
+        address
+    };
+
+    if let Some(metadata) = state.metadata_cache.get(chain, &address).await {
+        return Ok(Json(TokenMetadataResponse {
+            address,
+            chain,
+            metadata,
+        }));
+    }
+
+    let result = make_birdeye_metadata_request(&state.config, &address).await;
+    state
+        .monitor
+        .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+        .await;
+    let metadata = result
+        .map_err(|e| {
+            if deadline::expired() {
+                return ApiError::DeadlineExceeded;
+            }
+            error!(address = %address, error = %e, "Birdeye token metadata request failed");
+            ApiError::InternalServerError
+        })?
+        .ok_or_else(|| {
+            ApiError::NotFound(format!("No metadata for token '{}' on {}.", address, chain))
+        })?;
+
+    state
+        .metadata_cache
+        .insert(chain, address.clone(), metadata.clone())
+        .await;
+
+    Ok(Json(TokenMetadataResponse {
+        address,
+        chain,
+        metadata,
+    }))
+}
+
+/// Fetches a token's metadata on the request's chain from Birdeye, `None` if it has none.
+///
+/// # Notes
+/// - Injects configuration (metadata URL, API key) from `AppConfig`.
+async fn make_birdeye_metadata_request(
+    config: &AppConfig,
+    address: &str,
+) -> Result<Option<TokenMetadata>, Box<dyn std::error::Error + Send + Sync>> {
+    let client = reqwest::Client::new();
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(
+        "X-API-KEY",
+        HeaderValue::from_str(&config.birdeye_api_key).expect("Invalid API key format"),
+    );
+    headers.insert("x-chain", HeaderValue::from_static(chain::current().as_str()));
+
+    let request = client
+        .get(&config.birdeye_token_metadata_url)
+        .query(&[("address", address)])
+        .headers(headers);
+    let response = with_deadline(request)
+        .send()
+        .await?
+        .json::<BirdeyeMetadataResponse>()
+        .await?;
+
+    if !response.success {
+        return Err(response
+            .message
+            .unwrap_or_else(|| "Unknown error".to_string())
+            .into());
+    }
+    Ok(response.data.map(|data| TokenMetadata {
+        name: data.name,
+        symbol: data.symbol,
+        decimals: data.decimals,
+        logo_uri: data.logo_uri,
+    }))
+}
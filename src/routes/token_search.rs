@@ -0,0 +1,192 @@
+//! # Token Search Handler
+//!
+//! Proxies Birdeye's token search for the UI's token picker and annotates each result
+//! with whether the service already tracks it, and its cached volatility if so.
+
+use crate::config::AppConfig;
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::state::AppState;
+use crate::utils::chain;
+use crate::utils::deadline::{self, with_deadline};
+use axum::{
+    extract::State,
+    http::{HeaderMap, HeaderValue},
+    Json,
+};
+use reqwest::header::ACCEPT;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+/// Largest `limit` accepted, matching Birdeye's own page size cap.
+pub const MAX_SEARCH_LIMIT: u32 = 20;
+
+fn default_limit() -> u32 {
+    10
+}
+
+/// Query parameters for the token search request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSearchQuery {
+    /// Name, symbol or address fragment to search for.
+    pub q: String,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+}
+
+/// A single search match.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSearchResult {
+    pub address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub logo_uri: Option<String>,
+    /// Whether the background cache keeps this token's volatility refreshed.
+    pub tracked: bool,
+    /// Cached 90-day volatility, for tracked tokens.
+    pub volatility: Option<f64>,
+}
+
+/// Response structure for the token search endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSearchResponse {
+    pub query: String,
+    pub results: Vec<TokenSearchResult>,
+}
+
+/// Raw structure of the response returned by the Birdeye search API.
+#[derive(Debug, Deserialize)]
+struct BirdeyeSearchResponse {
+    data: Option<BirdeyeSearchData>,
+    success: bool,
+    message: Option<String>,
+}
+
+/// Nested `data` field inside the Birdeye search response.
+#[derive(Debug, Deserialize)]
+struct BirdeyeSearchData {
+    items: Vec<BirdeyeSearchGroup>,
+}
+
+/// Matches of one result type (tokens, markets, ...).
+#[derive(Debug, Deserialize)]
+struct BirdeyeSearchGroup {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    result: Vec<BirdeyeSearchToken>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct BirdeyeSearchToken {
+    pub address: String,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub logo_uri: Option<String>,
+}
+
+/// Axum handler searching tokens by name, symbol or address.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an empty `q` or a `limit` outside `1..=20`.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn search_tokens(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<TokenSearchQuery>,
+) -> Result<Json<TokenSearchResponse>, ApiError> {
+    info!(q = %query.q, limit = %query.limit, "Received token search request.");
+
+    let keyword = query.q.trim();
+    if keyword.is_empty() {
+        return Err(ApiError::InvalidQuery("q cannot be empty.".to_string()));
+    }
+    if query.limit == 0 || query.limit > MAX_SEARCH_LIMIT {
+        return Err(ApiError::InvalidQuery(format!(
+            "limit must be between 1 and {}.",
+            MAX_SEARCH_LIMIT
+        )));
+    }
+
+    let result = make_birdeye_search_request(&state.config, keyword, query.limit).await;
+    state
+        .monitor
+        .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+        .await;
+    let tokens = result.map_err(|e| {
+        if deadline::expired() {
+            return ApiError::DeadlineExceeded;
+        }
+        error!(q = %keyword, error = %e, "Birdeye search request failed");
+        ApiError::InternalServerError
+    })?;
+
+    let mut results = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let volatility = state.volatility_cache.get_volatility(&token.address).await;
+        results.push(TokenSearchResult {
+            tracked: volatility.is_some(),
+            volatility,
+            address: token.address,
+            name: token.name,
+            symbol: token.symbol,
+            logo_uri: token.logo_uri,
+        });
+    }
+
+    Ok(Json(TokenSearchResponse {
+        query: keyword.to_string(),
+        results,
+    }))
+}
+
+/// Searches tokens on the request's chain through the Birdeye search API.
+///
+/// # Notes
+/// - Injects configuration (search URL, API key) from `AppConfig`.
+pub(crate) async fn make_birdeye_search_request(
+    config: &AppConfig,
+    keyword: &str,
+    limit: u32,
+) -> Result<Vec<BirdeyeSearchToken>, Box<dyn std::error::Error + Send + Sync>> {
+    let client = reqwest::Client::new();
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(
+        "X-API-KEY",
+        HeaderValue::from_str(&config.birdeye_api_key).expect("Invalid API key format"),
+    );
+    let chain = chain::current();
+    headers.insert("x-chain", HeaderValue::from_static(chain.as_str()));
+
+    let request = client
+        .get(&config.birdeye_search_url)
+        .query(&[
+            ("keyword", keyword),
+            ("target", "token"),
+            ("chain", chain.as_str()),
+            ("limit", &limit.to_string()),
+        ])
+        .headers(headers);
+    let response = with_deadline(request)
+        .send()
+        .await?
+        .json::<BirdeyeSearchResponse>()
+        .await?;
+
+    match response.data {
+        Some(data) if response.success => Ok(data
+            .items
+            .into_iter()
+            .filter(|group| group.kind == "token")
+            .flat_map(|group| group.result)
+            .collect()),
+        _ => Err(response
+            .message
+            .unwrap_or_else(|| "Unknown error".to_string())
+            .into()),
+    }
+}
@@ -0,0 +1,76 @@
+//! # Tracked Tokens Handler
+//!
+//! Lists the tokens the background task keeps refreshed together with their cached
+//! values, for dashboards and for checking that the refresh loop keeps them current.
+
+use crate::state::AppState;
+use crate::utils::timestamp_format;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+/// A tracked token and its cached volatility.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedToken {
+    pub token_address: String,
+    pub volatility: f64,
+    /// Length of the rolling window the volatility covers.
+    pub window_days: u32,
+    /// Candles the volatility was computed from.
+    pub data_points: usize,
+    /// Last background refresh; falling behind the refresh interval means the task is stuck
+    /// or the provider is failing.
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub last_updated: DateTime<Utc>,
+    pub provider: String,
+    /// Older than `VOLATILITY_CACHE_MAX_AGE_SECS`: requests recompute the value instead of
+    /// serving it.
+    pub stale: bool,
+    /// Requested often enough to be refreshed every cycle rather than every
+    /// `COLD_REFRESH_INTERVAL_SECS`.
+    pub hot: bool,
+}
+
+/// Response structure for the tracked tokens endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackedTokensResponse {
+    /// Sorted by address.
+    pub tokens: Vec<TrackedToken>,
+}
+
+/// Axum handler listing every token in the volatility cache.
+#[instrument(skip(state))]
+pub async fn get_tracked_tokens(State(state): State<AppState>) -> Json<TrackedTokensResponse> {
+    let cache = &state.volatility_cache;
+    let mut tokens = Vec::new();
+    for token_address in cache.tracked_tokens().await {
+        // Skip tokens removed while the list was being built
+        let (Some(volatility), Some(data_points), Some(last_updated), Some(provider)) = (
+            cache.peek_volatility(&token_address).await,
+            cache.data_points(&token_address).await,
+            cache.last_updated(&token_address).await,
+            cache.provider_of(&token_address).await,
+        ) else {
+            continue;
+        };
+        let stale = cache.get_volatility(&token_address).await.is_none();
+        let hot = cache.is_hot_token(&token_address).await;
+        let window_days = cache.window_days(&token_address);
+        tokens.push(TrackedToken {
+            token_address,
+            volatility,
+            window_days,
+            data_points,
+            last_updated,
+            provider: provider.to_string(),
+            stale,
+            hot,
+        });
+    }
+    info!(tokens = %tokens.len(), "Listed tracked tokens.");
+
+    Json(TrackedTokensResponse { tokens })
+}
@@ -0,0 +1,87 @@
+//! # Updates Handler
+//!
+//! Lets polling clients with large watchlists sync incrementally: only tokens whose cached
+//! volatility changed after `since` are returned, along with a cursor for the next poll.
+
+use crate::background::volatility_cache::CACHED_WINDOW_DAYS;
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::state::AppState;
+use crate::utils::timestamp_format;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::{info, instrument};
+
+/// Query parameters for the updates request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatesQuery {
+    /// Only changes strictly after this time are returned.
+    #[serde(
+        deserialize_with = "timestamp_format::deserialize_datetime",
+        serialize_with = "timestamp_format::serialize_datetime"
+    )]
+    pub since: DateTime<Utc>,
+}
+
+/// A tracked token whose cached values changed.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenUpdate {
+    pub token_address: String,
+    /// Cached 90-day volatility.
+    pub volatility: f64,
+    /// Cached volatility by window length in days; `null` where the history is too short.
+    pub windows: BTreeMap<u32, Option<f64>>,
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Response structure for the updates endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatesResponse {
+    /// Pass as `since` on the next poll.
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub as_of: DateTime<Utc>,
+    pub updates: Vec<TokenUpdate>,
+}
+
+/// Axum handler returning the tracked tokens whose cached values changed after `since`.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a missing or malformed `since`.
+#[instrument(skip(state))]
+pub async fn get_updates(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<UpdatesQuery>,
+) -> Result<Json<UpdatesResponse>, ApiError> {
+    info!(since = %query.since, "Received updates request.");
+
+    // Taken before reading so a change landing mid-read is returned again next poll, not lost
+    let as_of = Utc::now();
+    let cache = &state.volatility_cache;
+    let mut updates = Vec::new();
+    for token_address in cache.changed_since(query.since).await {
+        let (Some(volatility), Some(changed_at)) = (
+            cache.get_volatility(&token_address).await,
+            cache.changed_at(&token_address).await,
+        ) else {
+            continue;
+        };
+        let mut windows = BTreeMap::new();
+        for days in CACHED_WINDOW_DAYS {
+            windows.insert(days, cache.get_window_volatility(&token_address, days).await);
+        }
+        updates.push(TokenUpdate {
+            token_address,
+            volatility,
+            windows,
+            changed_at,
+        });
+    }
+
+    Ok(Json(UpdatesResponse { as_of, updates }))
+}
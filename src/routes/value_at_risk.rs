@@ -0,0 +1,111 @@
+//! # Value-at-Risk Handler
+//!
+//! Reports parametric and historical-simulation VaR and CVaR of holding a token, from its
+//! daily log returns over a trailing window.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::returns::{return_series, ReturnKind};
+use crate::math::Interval;
+use crate::risk::value_at_risk::{historical, parametric, RiskEstimate};
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+fn default_confidence() -> f64 {
+    0.95
+}
+
+fn default_horizon_days() -> u32 {
+    1
+}
+
+/// Query parameters for the VaR request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueAtRiskQuery {
+    pub token_address: String,
+    /// Confidence level in `(0, 1)`, e.g. 0.95 or 0.99.
+    #[serde(default = "default_confidence")]
+    pub confidence: f64,
+    #[serde(default = "default_horizon_days")]
+    pub horizon_days: u32,
+    /// Days of return history to estimate from.
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+}
+
+/// Response structure for the VaR endpoint. Losses are fractions of position value.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueAtRiskResponse {
+    pub token_address: String,
+    pub confidence: f64,
+    pub horizon_days: u32,
+    pub days: u32,
+    /// Number of daily returns used.
+    pub data_points: usize,
+    pub parametric: RiskEstimate,
+    /// `None` when the window holds too few horizon-length returns.
+    pub historical: Option<RiskEstimate>,
+}
+
+/// Axum handler returning VaR and CVaR of a token position.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or too little price history.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_value_at_risk(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ValueAtRiskQuery>,
+) -> Result<Json<ValueAtRiskResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        confidence = %query.confidence,
+        horizon_days = %query.horizon_days,
+        "Received VaR request."
+    );
+
+    if !(query.confidence > 0.0 && query.confidence < 1.0) {
+        return Err(ApiError::InvalidQuery(
+            "confidence must be between 0 and 1 (exclusive).".to_string(),
+        ));
+    }
+    if query.horizon_days == 0 {
+        return Err(ApiError::InvalidQuery(
+            "horizonDays must be greater than 0.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let log_returns: Vec<f64> = return_series(&candles, ReturnKind::Log)
+        .into_iter()
+        .map(|point| point.value)
+        .collect();
+    let horizon = query.horizon_days as usize;
+
+    Ok(Json(ValueAtRiskResponse {
+        parametric: parametric(&log_returns, query.confidence, horizon).ok_or(ApiError::NotEnoughData)?,
+        historical: historical(&log_returns, query.confidence, horizon),
+        data_points: log_returns.len(),
+        token_address: query.token_address,
+        confidence: query.confidence,
+        horizon_days: query.horizon_days,
+        days: query.days,
+    }))
+}
@@ -0,0 +1,101 @@
+//! # Vol-of-Vol Handler
+//!
+//! Measures how much a token's short-window volatility itself moves: the standard
+//! deviation of its rolling close-to-close volatilities over a trailing look-back.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::rolling::{rolling_volatility, VolatilityPoint};
+use crate::math::stats::{mean, variance};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::{default_window_days, trailing_window};
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+fn default_vol_window_days() -> u32 {
+    10
+}
+
+/// Query parameters for the vol-of-vol request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolOfVolQuery {
+    pub token_address: String,
+    /// Daily returns per rolling volatility window.
+    #[serde(default = "default_vol_window_days")]
+    pub window_days: u32,
+    /// Look-back the rolling volatilities are taken over.
+    #[serde(default = "default_window_days")]
+    pub days: u32,
+}
+
+/// Response structure for the vol-of-vol endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolOfVolResponse {
+    pub token_address: String,
+    pub window_days: u32,
+    pub days: u32,
+    /// Sample standard deviation of the rolling volatilities, in volatility points.
+    pub vol_of_vol: f64,
+    /// Mean of the rolling volatilities, in percent.
+    pub mean_volatility: f64,
+    /// Most recent rolling volatility, in percent.
+    pub current_volatility: f64,
+    /// Rolling annualized volatilities, oldest first.
+    pub series: Vec<VolatilityPoint>,
+}
+
+/// Axum handler returning the volatility of a token's rolling volatility.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or when fewer than two
+///   rolling windows fit in the look-back.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_vol_of_vol(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<VolOfVolQuery>,
+) -> Result<Json<VolOfVolResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        window_days = %query.window_days,
+        days = %query.days,
+        "Received vol-of-vol request."
+    );
+
+    if query.window_days < 2 {
+        return Err(ApiError::InvalidQuery(
+            "windowDays must be at least 2.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(query.days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let series = rolling_volatility(&candles, query.window_days as usize, &CloseToClose, periods_per_year);
+    let volatilities: Vec<f64> = series.iter().map(|point| point.volatility).collect();
+
+    Ok(Json(VolOfVolResponse {
+        vol_of_vol: variance(&volatilities).ok_or(ApiError::NotEnoughData)?.sqrt(),
+        mean_volatility: mean(&volatilities).ok_or(ApiError::NotEnoughData)?,
+        current_volatility: *volatilities.last().ok_or(ApiError::NotEnoughData)?,
+        series,
+        token_address: query.token_address,
+        window_days: query.window_days,
+        days: query.days,
+    }))
+}
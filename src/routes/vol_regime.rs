@@ -0,0 +1,132 @@
+//! # Volatility Regime Handler
+//!
+//! Classifies a token's current volatility environment as low, normal, high or extreme by
+//! the z-score of its short-window volatility against the distribution of rolling
+//! short-window volatilities over a long look-back.
+
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::math::close_to_close::CloseToClose;
+use crate::math::regime::{classify, RegimeThresholds, VolRegime};
+use crate::math::rolling::rolling_volatility;
+use crate::math::stats::{mean, variance};
+use crate::math::Interval;
+use crate::routes::historical_volatility::fetch_series;
+use crate::state::AppState;
+use crate::utils::date_window::trailing_window;
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument};
+
+const DEFAULT_REGIME_WINDOW_DAYS: u32 = 10;
+const DEFAULT_LOOKBACK_DAYS: u32 = 365;
+
+fn default_regime_window_days() -> u32 {
+    DEFAULT_REGIME_WINDOW_DAYS
+}
+
+fn default_lookback_days() -> u32 {
+    DEFAULT_LOOKBACK_DAYS
+}
+
+/// Query parameters for the volatility regime request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolRegimeQuery {
+    pub token_address: String,
+    /// Number of daily returns in the short volatility window.
+    #[serde(default = "default_regime_window_days")]
+    pub window_days: u32,
+    /// Look-back the rolling short-window volatilities are distributed over.
+    #[serde(default = "default_lookback_days")]
+    pub lookback_days: u32,
+}
+
+/// Response structure for the volatility regime endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolRegimeResponse {
+    pub token_address: String,
+    pub window_days: u32,
+    pub lookback_days: u32,
+    pub regime: VolRegime,
+    /// Standard deviations the current volatility sits above the look-back mean.
+    pub z_score: f64,
+    /// Annualized volatility of the most recent window, in percent.
+    pub current_volatility: f64,
+    pub mean_volatility: f64,
+    pub std_dev_volatility: f64,
+    pub thresholds: RegimeThresholds,
+    /// Number of rolling windows in the distribution.
+    pub data_points: usize,
+}
+
+/// Axum handler returning the current volatility regime of a token.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for invalid query parameters or when the look-back holds
+///   fewer than two distinct rolling volatilities.
+/// - Returns `500 Internal Server Error` for unexpected Birdeye failures.
+#[instrument(skip(state))]
+pub async fn get_vol_regime(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<VolRegimeQuery>,
+) -> Result<Json<VolRegimeResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        window_days = %query.window_days,
+        lookback_days = %query.lookback_days,
+        "Received volatility regime request."
+    );
+
+    if query.window_days < 2 {
+        return Err(ApiError::InvalidQuery(
+            "windowDays must be at least 2.".to_string(),
+        ));
+    }
+    if query.lookback_days <= query.window_days {
+        return Err(ApiError::InvalidQuery(
+            "lookbackDays must be greater than windowDays.".to_string(),
+        ));
+    }
+
+    let (from_date, to_date) = trailing_window(query.lookback_days, state.config.max_date_range_days)?;
+    let candles = fetch_series(
+        &state,
+        &CloseToClose,
+        Interval::OneDay,
+        from_date,
+        to_date,
+        &query.token_address,
+    )
+    .await?;
+
+    let periods_per_year = Interval::OneDay.periods_per_year(state.config.annualization_days as f64);
+    let volatilities: Vec<f64> = rolling_volatility(&candles, query.window_days as usize, &CloseToClose, periods_per_year)
+        .into_iter()
+        .map(|point| point.volatility)
+        .collect();
+
+    let current_volatility = *volatilities.last().ok_or(ApiError::NotEnoughData)?;
+    let mean_volatility = mean(&volatilities).ok_or(ApiError::NotEnoughData)?;
+    let std_dev_volatility = variance(&volatilities).ok_or(ApiError::NotEnoughData)?.sqrt();
+    if std_dev_volatility == 0.0 {
+        return Err(ApiError::NotEnoughData);
+    }
+
+    let z_score = (current_volatility - mean_volatility) / std_dev_volatility;
+    let thresholds = state.config.vol_regime_thresholds();
+
+    Ok(Json(VolRegimeResponse {
+        regime: classify(z_score, &thresholds),
+        z_score,
+        current_volatility,
+        mean_volatility,
+        std_dev_volatility,
+        thresholds,
+        data_points: volatilities.len(),
+        token_address: query.token_address,
+        window_days: query.window_days,
+        lookback_days: query.lookback_days,
+    }))
+}
@@ -0,0 +1,112 @@
+//! # Volatility History Handler
+//!
+//! Returns the values the volatility cache computed for a token over the last days, as
+//! recorded by its history store, so users can see how realized volatility trended.
+
+use crate::background::volatility_cache::CACHED_WINDOW_DAYS;
+use crate::errors::api_error::ApiError;
+use crate::extractors::query_extractor::ApiQuery;
+use crate::state::AppState;
+use crate::utils::timestamp_format;
+use axum::{extract::State, Json};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+const DEFAULT_HISTORY_DAYS: u32 = 30;
+
+fn default_history_days() -> u32 {
+    DEFAULT_HISTORY_DAYS
+}
+
+/// Query parameters for the volatility history request.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityHistoryQuery {
+    pub token_address: String,
+    /// How many days back to return computed values for.
+    #[serde(default = "default_history_days")]
+    pub days: u32,
+    /// Window the values were computed over, one of the cached window lengths. The token's
+    /// headline window by default.
+    #[serde(default)]
+    pub window_days: Option<u32>,
+}
+
+/// One previously computed value.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityHistoryPoint {
+    #[serde(serialize_with = "timestamp_format::serialize_datetime")]
+    pub computed_at: DateTime<Utc>,
+    pub volatility: f64,
+}
+
+/// Response structure for the volatility history endpoint.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VolatilityHistoryResponse {
+    pub token_address: String,
+    pub window_days: u32,
+    pub days: u32,
+    /// Computed values, oldest first; empty for tokens the cache hasn't computed in that time.
+    pub points: Vec<VolatilityHistoryPoint>,
+}
+
+/// Axum handler returning the volatility values computed for a token over the last `days`.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a zero `days`, one beyond `MAX_DATE_RANGE_DAYS`, or a
+///   `windowDays` that isn't cached.
+/// - Returns `500 Internal Server Error` when the history store can't be read.
+#[instrument(skip(state))]
+pub async fn get_volatility_history(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<VolatilityHistoryQuery>,
+) -> Result<Json<VolatilityHistoryResponse>, ApiError> {
+    info!(
+        token_address = %query.token_address,
+        days = %query.days,
+        "Received volatility history request."
+    );
+
+    if query.days == 0 {
+        return Err(ApiError::InvalidQuery("days must be greater than 0.".to_string()));
+    }
+    if query.days > state.config.max_date_range_days {
+        return Err(ApiError::DateRangeTooLong(state.config.max_date_range_days));
+    }
+    let cache = &state.volatility_cache;
+    let window_days = query
+        .window_days
+        .unwrap_or_else(|| cache.window_days(&query.token_address));
+    if !CACHED_WINDOW_DAYS.contains(&window_days) {
+        return Err(ApiError::InvalidQuery(format!(
+            "windowDays must be one of {:?}.",
+            CACHED_WINDOW_DAYS
+        )));
+    }
+
+    let since = Utc::now() - Duration::days(query.days as i64);
+    let points = cache
+        .history()
+        .points(&query.token_address, window_days, since)
+        .await
+        .map_err(|e| {
+            error!(store = cache.history().name(), error = %e, "Failed to read volatility history");
+            ApiError::InternalServerError
+        })?;
+
+    Ok(Json(VolatilityHistoryResponse {
+        points: points
+            .into_iter()
+            .map(|point| VolatilityHistoryPoint {
+                computed_at: point.computed_at,
+                volatility: point.volatility,
+            })
+            .collect(),
+        token_address: query.token_address,
+        window_days,
+        days: query.days,
+    }))
+}
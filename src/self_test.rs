@@ -0,0 +1,319 @@
+//! Built-in end-to-end smoke test, run by starting the binary with `--self-test`.
+//!
+//! Serves the API on a local port against a mock Birdeye serving deterministic synthetic
+//! prices, calls every route once and reports each result, so a deploy pipeline can check
+//! a freshly built binary without a provider key or network access.
+
+use crate::{
+    background::volatility_cache::VolatilityCache, config::AppConfig, math::Interval,
+    provider::ProviderKind, routes::register_routes, state::AppState,
+};
+use axum::{extract::Query, http::StatusCode, routing::get, Json, Router};
+use chrono::{Duration, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Command-line flag running the self-test instead of the server.
+pub const SELF_TEST_FLAG: &str = "--self-test";
+
+const TOKEN: &str = "So11111111111111111111111111111111111111112";
+const OTHER_TOKEN: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const ADMIN_KEY: &str = "self-test-admin-key";
+
+/// One request of the self-test and the status it must answer with.
+struct Check {
+    method: Method,
+    path: String,
+    body: Option<Value>,
+    expected: StatusCode,
+}
+
+impl Check {
+    fn get(path: impl Into<String>) -> Self {
+        Self {
+            method: Method::GET,
+            path: path.into(),
+            body: None,
+            expected: StatusCode::OK,
+        }
+    }
+
+    fn post(path: impl Into<String>, body: Value) -> Self {
+        Self {
+            method: Method::POST,
+            body: Some(body),
+            ..Self::get(path)
+        }
+    }
+
+    #[cfg(feature = "admin")]
+    fn put(path: impl Into<String>, body: Value) -> Self {
+        Self {
+            method: Method::PUT,
+            expected: StatusCode::CREATED,
+            ..Self::post(path, body)
+        }
+    }
+
+    #[cfg(feature = "admin")]
+    fn delete(path: impl Into<String>) -> Self {
+        Self {
+            method: Method::DELETE,
+            expected: StatusCode::NO_CONTENT,
+            ..Self::get(path)
+        }
+    }
+}
+
+/// Runs the self-test with `config`, its provider settings replaced by the mock's.
+///
+/// Prints one `PASS` or `FAIL` line per route and returns the number of failed checks.
+pub async fn run(config: AppConfig) -> usize {
+    let mock = match serve(mock_birdeye()).await {
+        Ok(address) => address,
+        Err(e) => {
+            println!("FAIL could not start the mock provider: {}", e);
+            return 1;
+        }
+    };
+    let config = AppConfig {
+        birdeye_api_key: "self-test".to_string(),
+        birdeye_base_url: format!("{}/history", mock),
+        birdeye_ohlcv_url: format!("{}/history", mock),
+        birdeye_pair_ohlcv_url: format!("{}/history", mock),
+        birdeye_price_url: format!("{}/price", mock),
+        birdeye_search_url: format!("{}/search", mock),
+        birdeye_token_metadata_url: format!("{}/metadata", mock),
+        providers: Some(vec![ProviderKind::Birdeye]),
+        fallback_provider: None,
+        admin_api_key: Some(ADMIN_KEY.to_string()),
+        ..config
+    };
+
+    let volatility_cache = VolatilityCache::new(config.clone());
+    if let Err(e) = volatility_cache.add_token(TOKEN.to_string()).await {
+        println!("FAIL could not cache {}: {}", TOKEN, e);
+        return 1;
+    }
+    let api = match serve(register_routes(AppState::new(config, volatility_cache))).await {
+        Ok(address) => address,
+        Err(e) => {
+            println!("FAIL could not start the API: {}", e);
+            return 1;
+        }
+    };
+
+    let client = reqwest::Client::new();
+    let checks = checks();
+    let mut failures = 0;
+    for check in &checks {
+        let label = format!("{} {}", check.method, check.path);
+        match send(&client, &api, check).await {
+            Ok(()) => println!("PASS {}", label),
+            Err(e) => {
+                failures += 1;
+                println!("FAIL {}: {}", label, e);
+            }
+        }
+    }
+    println!(
+        "Self-test finished: {} of {} checks passed.",
+        checks.len() - failures,
+        checks.len()
+    );
+    failures
+}
+
+async fn send(client: &reqwest::Client, api: &str, check: &Check) -> Result<(), String> {
+    let mut request = client
+        .request(check.method.clone(), format!("{}{}", api, check.path))
+        .header("x-admin-key", ADMIN_KEY);
+    if let Some(body) = &check.body {
+        request = request.json(body);
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status();
+    if status.as_u16() == check.expected.as_u16() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(format!("expected {}, got {}: {}", check.expected, status, body))
+}
+
+/// Every route of the API, with the ones behind disabled features left out.
+fn checks() -> Vec<Check> {
+    let today = Utc::now().date_naive();
+    let from = (today - Duration::days(60)).format("%Y-%m-%d");
+    let to = (today - Duration::days(1)).format("%Y-%m-%d");
+    let expiry = (today + Duration::days(30)).format("%Y-%m-%d");
+    let range = format!("tokenAddress={}&fromDate={}&toDate={}", TOKEN, from, to);
+
+    #[allow(unused_mut)]
+    let mut checks = vec![
+        Check::get("/healthCheck"),
+        Check::get(format!("/historicalVolatility?{}", range)),
+        Check::get(format!("/historicalVolatility/explain?{}", range)),
+        Check::get("/presets"),
+        Check::get("/methodology/changelog"),
+        Check::get("/deprecations"),
+        Check::get(format!("/returns?{}", range)),
+        Check::get(format!("/prices?{}", range)),
+        Check::get(format!("/correlation?tokenA={}&tokenB={}", TOKEN, OTHER_TOKEN)),
+        Check::get(format!("/beta?tokenAddress={}&benchmark={}", TOKEN, OTHER_TOKEN)),
+        Check::get(format!("/hvRank?tokenAddress={}", TOKEN)),
+        Check::get(format!("/volOfVol?tokenAddress={}", TOKEN)),
+        Check::get(format!("/volRegime?tokenAddress={}", TOKEN)),
+        Check::get(format!("/backtest?tokenAddress={}", TOKEN)),
+        Check::post(
+            "/correlationMatrix",
+            json!({ "tokenAddresses": [TOKEN, OTHER_TOKEN] }),
+        ),
+        Check::get("/heatmap"),
+        Check::get("/updates?since=2000-01-01"),
+        Check::get(format!("/volatilityHistory?tokenAddress={}", TOKEN)),
+        Check::get(format!("/drawdown?tokenAddress={}", TOKEN)),
+        Check::get(format!("/returnDistribution?tokenAddress={}", TOKEN)),
+        Check::get(format!("/riskAdjustedReturns?tokenAddress={}", TOKEN)),
+        Check::get(format!("/var?tokenAddress={}", TOKEN)),
+        Check::get(format!(
+            "/optionPrice?tokenAddress={}&strike=100&expiry={}&optionType=call",
+            TOKEN, expiry
+        )),
+        Check::post(
+            "/simulate/stress",
+            json!({ "tokenAddress": TOKEN, "shocks": [{ "type": "jump", "change": -0.3 }] }),
+        ),
+        Check::get("/tokens"),
+        Check::get("/tokens/search?q=SOL"),
+        Check::get(format!("/token/{}", TOKEN)),
+        Check::get("/status"),
+    ];
+    #[cfg(feature = "simulation")]
+    checks.push(Check::post(
+        "/simulate",
+        json!({ "tokenAddress": TOKEN, "paths": 100, "seed": 7 }),
+    ));
+    #[cfg(feature = "dashboard")]
+    checks.push(Check::get("/dashboard"));
+    #[cfg(feature = "parquet")]
+    checks.extend([
+        Check::get(format!("/export/prices.parquet?{}", range)),
+        Check::get(format!("/export/volatilitySeries.parquet?{}", range)),
+    ]);
+    #[cfg(feature = "admin")]
+    checks.extend([
+        Check::get("/admin/cacheStats"),
+        Check::get("/admin/export"),
+        Check::post(
+            "/admin/import",
+            json!({
+                "version": 2,
+                "exportedAt": Utc::now(),
+                "watchlist": [{ "tokenAddress": TOKEN, "pinned": true }],
+            }),
+        ),
+        Check::put("/admin/presets/selfTest", json!({ "interval": "1D" })),
+        Check::delete("/admin/presets/selfTest"),
+        Check {
+            expected: StatusCode::CREATED,
+            ..Check::post("/admin/tokens", json!({ "tokenAddress": OTHER_TOKEN }))
+        },
+        Check::delete(format!("/admin/tokens/{}", OTHER_TOKEN)),
+        Check::get(format!("/admin/verify/{}", TOKEN)),
+    ]);
+    checks
+}
+
+/// Serves `app` on a random local port, returning its base URL.
+async fn serve(app: Router) -> std::io::Result<String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    Ok(format!("http://{}", address))
+}
+
+//
+// ----------- Mock provider -----------
+//
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    address: String,
+    #[serde(rename = "type")]
+    interval: Interval,
+    time_from: i64,
+    time_to: i64,
+}
+
+#[derive(Deserialize)]
+struct AddressQuery {
+    address: String,
+}
+
+/// Birdeye stand-in answering the history, OHLCV, price, search and metadata endpoints.
+fn mock_birdeye() -> Router {
+    Router::new()
+        .route("/history", get(mock_history))
+        .route("/price", get(mock_price))
+        .route("/search", get(mock_search))
+        .route("/metadata", get(mock_metadata))
+}
+
+/// Candles between `time_from` and `time_to`, carrying both the history (`value`) and the
+/// OHLCV (`o`, `h`, `l`, `c`, `v`) fields so one handler serves both endpoints.
+async fn mock_history(Query(query): Query<HistoryQuery>) -> Json<Value> {
+    let step = query.interval.seconds();
+    let first = query.time_from.div_euclid(step) * step;
+    let items: Vec<Value> = (0..)
+        .map(|i| first + i * step)
+        .skip_while(|time| *time < query.time_from)
+        .take_while(|time| *time <= query.time_to)
+        .map(|time| {
+            let open = synthetic_price(&query.address, time);
+            let close = synthetic_price(&query.address, time + step);
+            json!({
+                "unixTime": time,
+                "value": open,
+                "o": open,
+                "h": open.max(close) * 1.01,
+                "l": open.min(close) * 0.99,
+                "c": close,
+                "v": 1000.0,
+            })
+        })
+        .collect();
+    Json(json!({ "success": true, "data": { "items": items } }))
+}
+
+async fn mock_price(Query(query): Query<AddressQuery>) -> Json<Value> {
+    let value = synthetic_price(&query.address, Utc::now().timestamp());
+    Json(json!({ "success": true, "data": { "value": value } }))
+}
+
+async fn mock_search() -> Json<Value> {
+    Json(json!({
+        "success": true,
+        "data": { "items": [{ "type": "token", "result": [{
+            "address": TOKEN,
+            "name": "Wrapped SOL",
+            "symbol": "SOL",
+            "logo_uri": null,
+        }] }] }
+    }))
+}
+
+async fn mock_metadata(Query(query): Query<AddressQuery>) -> Json<Value> {
+    Json(json!({
+        "success": true,
+        "data": { "name": "Self-test token", "symbol": "TEST", "decimals": 9, "logo_uri": null, "address": query.address }
+    }))
+}
+
+/// Deterministic price of `address` at `time`: two overlapping waves whose phase depends
+/// on the address, giving every token its own non-trivial, reproducible series.
+fn synthetic_price(address: &str, time: i64) -> f64 {
+    let seed = (address.bytes().map(u64::from).sum::<u64>() % 97) as f64;
+    let day = time as f64 / 86_400.0;
+    (1.0 + seed) * (0.15 * (day * 0.21 + seed).sin() + 0.05 * (day * 1.7 + seed * 0.5).sin()).exp()
+}
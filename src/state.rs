@@ -1,15 +1,37 @@
 use crate::config::AppConfig;
+use crate::background::health_monitor::HealthMonitor;
+use crate::background::metadata_cache::MetadataCache;
+use crate::background::price_cache::PriceCache;
 use crate::background::volatility_cache::VolatilityCache;
+use crate::deprecations::DeprecationRegistry;
+use crate::presets::PresetStore;
+use crate::provider::PriceProvider;
+use crate::symbols::SymbolResolver;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct AppState {
     pub config: AppConfig,
     pub volatility_cache: VolatilityCache,
+    pub price_cache: PriceCache,
+    pub metadata_cache: MetadataCache,
+    pub monitor: HealthMonitor,
+    pub provider: Arc<dyn PriceProvider>,
+    pub presets: PresetStore,
+    pub symbols: SymbolResolver,
+    pub deprecations: DeprecationRegistry,
 }
 
 impl AppState {
     pub fn new(config: AppConfig, volatility_cache: VolatilityCache) -> Self {
         Self {
+            price_cache: volatility_cache.price_cache().clone(),
+            metadata_cache: MetadataCache::new(),
+            monitor: volatility_cache.monitor().clone(),
+            provider: volatility_cache.provider().clone(),
+            presets: PresetStore::new(config.methodology()),
+            symbols: SymbolResolver::new(),
+            deprecations: DeprecationRegistry::default(),
             config,
             volatility_cache,
         }
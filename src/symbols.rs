@@ -0,0 +1,339 @@
+//! Resolution of token symbols ("SOL", "JUP") to addresses, so callers can pass a symbol
+//! wherever an endpoint takes a token address.
+//!
+//! Symbols are looked up in the bundled token list first, which pins well-known tokens to
+//! their canonical address, and then through Birdeye token search.
+
+use crate::errors::api_error::ApiError;
+use crate::routes::token_search::make_birdeye_search_request;
+use crate::state::AppState;
+use crate::utils::chain::{self, Chain};
+use crate::utils::deadline;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header::CONTENT_TYPE, uri::PathAndQuery, HeaderMap, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+/// Longest value treated as a symbol; token addresses are always longer.
+pub const MAX_SYMBOL_LEN: usize = 16;
+
+/// Query parameter naming a token by symbol instead of `tokenAddress`.
+pub const SYMBOL_PARAM: &str = "symbol";
+
+/// Query parameters and JSON body fields holding token addresses (or lists of them).
+const ADDRESS_FIELDS: [&str; 3] = ["tokenAddress", "tokenAddresses", "benchmark"];
+
+/// Largest JSON body inspected for symbols, matching axum's default body limit.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Birdeye search results considered when resolving a symbol.
+const SEARCH_LIMIT: u32 = 20;
+
+/// Token list compiled into the binary.
+const TOKEN_LIST: &str = include_str!("../assets/token_list.json");
+
+/// A token of the bundled token list.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenListEntry {
+    pub chain: Chain,
+    pub symbol: String,
+    pub name: String,
+    pub address: String,
+}
+
+/// Whether a token address parameter holds a symbol: 1 to [`MAX_SYMBOL_LEN`] uppercase ASCII
+/// letters or digits, at least one of them a letter.
+pub fn is_symbol(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_SYMBOL_LEN
+        && value.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && value.chars().any(|c| c.is_ascii_uppercase())
+}
+
+/// Resolves symbols against the bundled token list, then Birdeye search, remembering
+/// search results so each symbol is searched once per chain.
+#[derive(Clone)]
+pub struct SymbolResolver {
+    bundled: Arc<Vec<TokenListEntry>>,
+    /// Addresses found through search, by chain and uppercased symbol.
+    searched: Arc<RwLock<HashMap<(Chain, String), String>>>,
+}
+
+impl Default for SymbolResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolResolver {
+    /// Create a resolver over the bundled token list.
+    pub fn new() -> Self {
+        Self {
+            bundled: Arc::new(serde_json::from_str(TOKEN_LIST).expect("bundled token list is valid JSON")),
+            searched: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Tokens of the bundled list on `chain` whose symbol is `symbol`, ignoring case.
+    pub fn bundled(&self, chain: Chain, symbol: &str) -> Vec<&TokenListEntry> {
+        self.bundled
+            .iter()
+            .filter(|entry| entry.chain == chain && entry.symbol.eq_ignore_ascii_case(symbol))
+            .collect()
+    }
+
+    /// Address of the token `symbol` names on the current request's chain.
+    ///
+    /// # Errors
+    /// - `ApiError::InvalidQuery` when no token or several tokens carry the symbol, listing
+    ///   the candidates in the latter case.
+    /// - `ApiError::InternalServerError` or `ApiError::DeadlineExceeded` when the search fails.
+    pub async fn resolve(&self, state: &AppState, symbol: &str) -> Result<String, ApiError> {
+        let chain = chain::current();
+        let bundled = self.bundled(chain, symbol);
+        let candidates: Vec<(String, Option<String>)> = if !bundled.is_empty() {
+            bundled
+                .into_iter()
+                .map(|entry| (entry.address.clone(), Some(entry.name.clone())))
+                .collect()
+        } else {
+            let key = (chain, symbol.to_ascii_uppercase());
+            if let Some(address) = self.searched.read().await.get(&key) {
+                return Ok(address.clone());
+            }
+            let candidates = search(state, symbol).await?;
+            if let [(address, _)] = &candidates[..] {
+                self.searched.write().await.insert(key, address.clone());
+            }
+            candidates
+        };
+
+        match &candidates[..] {
+            [] => Err(ApiError::InvalidQuery(format!(
+                "Unknown symbol '{}' on {}.",
+                symbol, chain
+            ))),
+            [(address, _)] => {
+                info!(symbol = %symbol, address = %address, "Resolved token symbol");
+                Ok(address.clone())
+            }
+            _ => {
+                let listed: Vec<String> = candidates
+                    .iter()
+                    .map(|(address, name)| match name {
+                        Some(name) => format!("{} ({})", address, name),
+                        None => address.clone(),
+                    })
+                    .collect();
+                Err(ApiError::InvalidQuery(format!(
+                    "Symbol '{}' matches several tokens on {}: {}. Pass tokenAddress instead.",
+                    symbol,
+                    chain,
+                    listed.join(", ")
+                )))
+            }
+        }
+    }
+
+    /// Resolves `value` if it is a symbol, leaving addresses unchanged.
+    async fn resolve_value(&self, state: &AppState, value: &str) -> Result<Option<String>, ApiError> {
+        if !is_symbol(value) {
+            return Ok(None);
+        }
+        self.resolve(state, value).await.map(Some)
+    }
+}
+
+/// Distinct addresses of Birdeye search results whose symbol is exactly `symbol`.
+async fn search(state: &AppState, symbol: &str) -> Result<Vec<(String, Option<String>)>, ApiError> {
+    let result = make_birdeye_search_request(&state.config, symbol, SEARCH_LIMIT).await;
+    state
+        .monitor
+        .record_upstream(result.as_ref().map(|_| ()).map_err(|e| e.to_string()))
+        .await;
+    let tokens = result.map_err(|e| {
+        if deadline::expired() {
+            return ApiError::DeadlineExceeded;
+        }
+        error!(symbol = %symbol, error = %e, "Birdeye symbol search failed");
+        ApiError::InternalServerError
+    })?;
+
+    let mut candidates: Vec<(String, Option<String>)> = Vec::new();
+    for token in tokens {
+        let matches = token
+            .symbol
+            .as_deref()
+            .is_some_and(|token_symbol| token_symbol.eq_ignore_ascii_case(symbol));
+        if matches && !candidates.iter().any(|(address, _)| *address == token.address) {
+            candidates.push((token.address, token.name));
+        }
+    }
+    Ok(candidates)
+}
+
+/// Middleware replacing symbols with token addresses before handlers see the request:
+/// `symbol=JUP` becomes `tokenAddress=<mint>`, and symbol values of the address query
+/// parameters and JSON body fields are swapped for the addresses they name.
+pub async fn resolve_symbols(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let (mut parts, body) = request.into_parts();
+
+    if let Some(query) = parts.uri.query() {
+        match resolve_query(&state, query).await {
+            Ok(Some(query)) => {
+                let path_and_query = format!("{}?{}", parts.uri.path(), query);
+                let mut uri_parts = parts.uri.clone().into_parts();
+                uri_parts.path_and_query = PathAndQuery::try_from(path_and_query).ok();
+                if let Ok(uri) = Uri::from_parts(uri_parts) {
+                    parts.uri = uri;
+                }
+            }
+            Ok(None) => {}
+            Err(err) => return err.into_response(),
+        }
+    }
+
+    let body = if is_json(&parts.headers) {
+        match resolve_body(&state, body).await {
+            Ok(body) => body,
+            Err(err) => return err.into_response(),
+        }
+    } else {
+        body
+    };
+
+    next.run(Request::from_parts(parts, body)).await
+}
+
+/// The query string with symbols resolved, or `None` if it holds none.
+async fn resolve_query(state: &AppState, query: &str) -> Result<Option<String>, ApiError> {
+    let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(query) else {
+        // Left for the handler's extractor to reject
+        return Ok(None);
+    };
+
+    let has_address = pairs.iter().any(|(name, _)| name == ADDRESS_FIELDS[0]);
+    let mut changed = false;
+    let mut resolved = Vec::with_capacity(pairs.len());
+    for (name, value) in pairs {
+        if name == SYMBOL_PARAM {
+            if has_address {
+                return Err(ApiError::InvalidQuery(
+                    "Pass either tokenAddress or symbol, not both.".to_string(),
+                ));
+            }
+            let symbol = value.trim();
+            if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LEN {
+                return Err(ApiError::InvalidQuery(format!(
+                    "symbol must be 1 to {} characters.",
+                    MAX_SYMBOL_LEN
+                )));
+            }
+            let address = state.symbols.resolve(state, symbol).await?;
+            resolved.push((ADDRESS_FIELDS[0].to_string(), address));
+            changed = true;
+        } else if ADDRESS_FIELDS.contains(&name.as_str()) {
+            // `tokenAddresses` lists are comma-separated
+            let mut addresses = Vec::new();
+            for item in value.split(',') {
+                match state.symbols.resolve_value(state, item.trim()).await? {
+                    Some(address) => {
+                        addresses.push(address);
+                        changed = true;
+                    }
+                    None => addresses.push(item.to_string()),
+                }
+            }
+            resolved.push((name, addresses.join(",")));
+        } else {
+            resolved.push((name, value));
+        }
+    }
+
+    if !changed {
+        return Ok(None);
+    }
+    Ok(serde_urlencoded::to_string(&resolved).ok())
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+/// The JSON body with symbols resolved; bodies that are not JSON objects pass unchanged.
+async fn resolve_body(state: &AppState, body: Body) -> Result<Body, ApiError> {
+    let bytes = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|err| ApiError::InvalidBody(err.to_string()))?;
+    let Ok(Value::Object(mut fields)) = serde_json::from_slice::<Value>(&bytes) else {
+        return Ok(Body::from(bytes));
+    };
+
+    let mut changed = false;
+    for field in ADDRESS_FIELDS {
+        let values: Vec<&mut Value> = match fields.get_mut(field) {
+            Some(Value::Array(items)) => items.iter_mut().collect(),
+            Some(value) => vec![value],
+            None => continue,
+        };
+        for value in values {
+            let Value::String(text) = value else { continue };
+            let address = state
+                .symbols
+                .resolve_value(state, text)
+                .await
+                .map_err(|err| match err {
+                    ApiError::InvalidQuery(msg) => ApiError::InvalidBody(msg),
+                    err => err,
+                })?;
+            if let Some(address) = address {
+                *text = address;
+                changed = true;
+            }
+        }
+    }
+
+    if !changed {
+        return Ok(Body::from(bytes));
+    }
+    Ok(Body::from(Value::Object(fields).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_are_told_apart_from_addresses() {
+        assert!(is_symbol("SOL"));
+        assert!(is_symbol("1INCH"));
+        assert!(!is_symbol("sol"));
+        assert!(!is_symbol("2024"));
+        assert!(!is_symbol("So11111111111111111111111111111111111111112"));
+        assert!(!is_symbol("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"));
+    }
+
+    #[test]
+    fn test_bundled_list_is_per_chain() {
+        let resolver = SymbolResolver::new();
+        let solana = resolver.bundled(Chain::Solana, "usdc");
+        let ethereum = resolver.bundled(Chain::Ethereum, "USDC");
+        assert_eq!(solana.len(), 1);
+        assert_eq!(ethereum.len(), 1);
+        assert_ne!(solana[0].address, ethereum[0].address);
+        assert!(resolver.bundled(Chain::Base, "USDC").is_empty());
+    }
+}
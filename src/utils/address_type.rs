@@ -0,0 +1,73 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    extract::{Query, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+
+/// What a request's `tokenAddress` points at, chosen with the `addressType` query parameter.
+///
+/// Serialized with Birdeye's `address_type` values.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressType {
+    /// A token mint or contract; prices are the token's.
+    #[default]
+    Token,
+    /// A liquidity pool pair; prices are the pool's base-in-quote price.
+    Pair,
+}
+
+impl AddressType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AddressType::Token => "token",
+            AddressType::Pair => "pair",
+        }
+    }
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+tokio::task_local! {
+    /// Address type of the request being handled on this task.
+    static ADDRESS_TYPE: AddressType;
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddressTypeQuery {
+    #[serde(default)]
+    address_type: AddressType,
+}
+
+/// Middleware reading the `addressType` query parameter and putting it in scope for the
+/// handler, so providers and cache keys treat the address alike.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a value other than `token` or `pair`.
+pub async fn scope_address_type(request: Request, next: Next) -> Response {
+    let address_type = match Query::<AddressTypeQuery>::try_from_uri(request.uri()) {
+        Ok(Query(query)) => query.address_type,
+        Err(err) => return ApiError::InvalidQuery(err.body_text()).into_response(),
+    };
+
+    ADDRESS_TYPE.scope(address_type, next.run(request)).await
+}
+
+/// Address type of the current request; `token` outside a request.
+pub fn current() -> AddressType {
+    ADDRESS_TYPE.try_with(|address_type| *address_type).unwrap_or_default()
+}
+
+/// Runs `future` with `address_type` in scope, as for a request with that address type.
+pub async fn scope<F: Future>(address_type: AddressType, future: F) -> F::Output {
+    ADDRESS_TYPE.scope(address_type, future).await
+}
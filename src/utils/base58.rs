@@ -0,0 +1,73 @@
+/// Bitcoin base58 alphabet, used by Solana: no `0`, `O`, `I` or `l`.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Length in bytes of a Solana public key.
+pub const PUBKEY_LEN: usize = 32;
+
+/// Decodes a base58 string, `None` if it contains a character outside the alphabet.
+pub fn decode(input: &str) -> Option<Vec<u8>> {
+    // Little-endian digits of the decoded number
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for c in input.bytes() {
+        let mut carry = ALPHABET.iter().position(|&digit| digit == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    // Each leading '1' encodes a leading zero byte
+    let leading_zeros = input.bytes().take_while(|&c| c == b'1').count();
+    bytes.extend(std::iter::repeat_n(0, leading_zeros));
+    bytes.reverse();
+    Some(bytes)
+}
+
+/// Checks that `address` is a well-formed Solana public key: base58 decoding to exactly
+/// [`PUBKEY_LEN`] bytes. The message says which of the two it fails.
+pub fn validate_pubkey(address: &str) -> Result<(), String> {
+    let bytes = decode(address).ok_or_else(|| {
+        format!(
+            "'{}' is not a valid Solana address: it contains characters outside the base58 alphabet (0, O, I and l are never used).",
+            address
+        )
+    })?;
+    if bytes.len() != PUBKEY_LEN {
+        return Err(format!(
+            "'{}' is not a valid Solana address: it decodes to {} bytes instead of {}.",
+            address,
+            bytes.len(),
+            PUBKEY_LEN
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_known_values() {
+        assert_eq!(decode(""), Some(vec![]));
+        assert_eq!(decode("1"), Some(vec![0]));
+        assert_eq!(decode("2g"), Some(vec![0x61]));
+        assert_eq!(decode("11StV1DL6CwTryKyV"), Some(b"\0\0hello world".to_vec()));
+        assert_eq!(decode("0OIl"), None);
+    }
+
+    #[test]
+    fn test_validate_pubkey() {
+        assert!(validate_pubkey("So11111111111111111111111111111111111111112").is_ok());
+        assert!(validate_pubkey("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN").is_ok());
+        assert!(validate_pubkey("11111111111111111111111111111111").is_ok());
+        assert!(validate_pubkey("So1111111111111111111111111111111111111111O").is_err());
+        assert!(validate_pubkey("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN123").is_err());
+        assert!(validate_pubkey("tokenA").is_err());
+    }
+}
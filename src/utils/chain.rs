@@ -0,0 +1,137 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    extract::{Query, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+
+/// Blockchain a request's tokens live on, chosen with the `chain` query parameter.
+///
+/// Serialized with Birdeye's chain names.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Chain {
+    #[default]
+    Solana,
+    Ethereum,
+    Bsc,
+    Base,
+    Arbitrum,
+    Optimism,
+    Polygon,
+    Avalanche,
+}
+
+impl Chain {
+    /// Birdeye `x-chain` value for this chain.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Chain::Solana => "solana",
+            Chain::Ethereum => "ethereum",
+            Chain::Bsc => "bsc",
+            Chain::Base => "base",
+            Chain::Arbitrum => "arbitrum",
+            Chain::Optimism => "optimism",
+            Chain::Polygon => "polygon",
+            Chain::Avalanche => "avalanche",
+        }
+    }
+
+    /// CoinGecko asset platform id of this chain.
+    pub fn coingecko_platform(self) -> &'static str {
+        match self {
+            Chain::Solana => "solana",
+            Chain::Ethereum => "ethereum",
+            Chain::Bsc => "binance-smart-chain",
+            Chain::Base => "base",
+            Chain::Arbitrum => "arbitrum-one",
+            Chain::Optimism => "optimistic-ethereum",
+            Chain::Polygon => "polygon-pos",
+            Chain::Avalanche => "avalanche",
+        }
+    }
+
+    /// Checks that `address` is shaped like a token address on this chain: `0x` followed by
+    /// 40 hex digits on EVM chains, any non-empty string on Solana.
+    pub fn validate_address(self, address: &str) -> Result<(), String> {
+        let valid = match self {
+            Chain::Solana => !address.trim().is_empty(),
+            _ => address
+                .strip_prefix("0x")
+                .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit())),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("'{}' is not a valid {} token address.", address, self))
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+tokio::task_local! {
+    /// Chain of the request being handled on this task.
+    static CHAIN: Chain;
+}
+
+#[derive(Deserialize)]
+struct ChainQuery {
+    #[serde(default)]
+    chain: Chain,
+}
+
+/// Middleware reading the `chain` query parameter and putting it in scope for the handler,
+/// so providers, cache keys and address checks all see the same chain.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for an unsupported chain.
+pub async fn scope_chain(request: Request, next: Next) -> Response {
+    let chain = match Query::<ChainQuery>::try_from_uri(request.uri()) {
+        Ok(Query(query)) => query.chain,
+        Err(err) => return ApiError::InvalidQuery(err.body_text()).into_response(),
+    };
+
+    CHAIN.scope(chain, next.run(request)).await
+}
+
+/// Chain of the current request; Solana outside a request, e.g. in the background refresh.
+pub fn current() -> Chain {
+    CHAIN.try_with(|chain| *chain).unwrap_or_default()
+}
+
+/// Runs `future` with `chain` in scope, as for a request on `chain`.
+pub async fn scope<F: Future>(chain: Chain, future: F) -> F::Output {
+    CHAIN.scope(chain, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_validation_follows_the_chain() {
+        let evm = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        assert!(Chain::Ethereum.validate_address(evm).is_ok());
+        assert!(Chain::Base.validate_address("0x1234").is_err());
+        assert!(Chain::Ethereum
+            .validate_address("So11111111111111111111111111111111111111112")
+            .is_err());
+        assert!(Chain::Solana
+            .validate_address("So11111111111111111111111111111111111111112")
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_current_defaults_to_solana_outside_a_request() {
+        assert_eq!(current(), Chain::Solana);
+        assert_eq!(CHAIN.scope(Chain::Bsc, async { current() }).await, Chain::Bsc);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Query, Request},
+    http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, VARY},
+        HeaderMap, HeaderValue,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tracing::error;
+
+const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+#[derive(Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ResponseFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Deserialize)]
+struct CsvFormatQuery {
+    #[serde(default)]
+    format: ResponseFormat,
+}
+
+/// Middleware rendering a successful JSON response as CSV when the request sets `format=csv`
+/// or accepts `text/csv`, so the series can be loaded straight into a spreadsheet or pandas.
+///
+/// The first array of objects in the response becomes the rows, each one prefixed with the
+/// response's scalar fields (token address, interval, ...); a response without one becomes a
+/// single row. Nested objects are flattened into dotted column names such as
+/// `extendedStats.meanReturn`. Error responses stay JSON. Every response carries
+/// `Vary: Accept`, so shared caches keep the JSON and CSV versions apart.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a `format` other than `json` or `csv`.
+pub async fn render_csv(request: Request, next: Next) -> Response {
+    let format = match Query::<CsvFormatQuery>::try_from_uri(request.uri()) {
+        Ok(Query(query)) => query.format,
+        Err(err) => return ApiError::InvalidQuery(err.body_text()).into_response(),
+    };
+    let accepts_csv = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|media| media.trim().starts_with("text/csv")));
+
+    let mut response = next.run(request).await;
+    vary_on_accept(response.headers_mut());
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    let wants_csv = format == ResponseFormat::Csv || accepts_csv;
+    if !wants_csv || !is_json || !response.status().is_success() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            error!(error = %err, "Failed to read response body");
+            return ApiError::InternalServerError.into_response();
+        }
+    };
+    let Ok(value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(CSV_CONTENT_TYPE));
+    Response::from_parts(parts, Body::from(to_csv(&value)))
+}
+
+/// Renders `value` as CSV text with a header line, one line per row.
+fn to_csv(value: &Value) -> String {
+    let rows = rows(value);
+    let mut columns: Vec<&String> = Vec::new();
+    for row in &rows {
+        for (column, _) in row {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+    }
+
+    let mut csv = String::new();
+    let header: Vec<String> = columns.iter().map(|column| escape(column)).collect();
+    csv.push_str(&header.join(","));
+    csv.push('\n');
+    for row in &rows {
+        let line: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                row.iter()
+                    .find(|(name, _)| name == *column)
+                    .map(|(_, value)| cell(value))
+                    .unwrap_or_default()
+            })
+            .collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Splits `value` into rows of column name and scalar value.
+fn rows(value: &Value) -> Vec<Vec<(String, Value)>> {
+    match value {
+        Value::Array(items) => items.iter().map(flatten).collect(),
+        Value::Object(fields) => {
+            let mut shared = Vec::new();
+            flatten_into(fields, "", &mut shared);
+            let items = fields.values().find_map(|field| match field {
+                Value::Array(items) if items.iter().any(Value::is_object) => Some(items),
+                _ => None,
+            });
+            match items {
+                Some(items) => items
+                    .iter()
+                    .map(|item| shared.iter().cloned().chain(flatten(item)).collect())
+                    .collect(),
+                None => vec![shared],
+            }
+        }
+        scalar => vec![vec![("value".to_string(), scalar.clone())]],
+    }
+}
+
+fn flatten(value: &Value) -> Vec<(String, Value)> {
+    match value {
+        Value::Object(fields) => {
+            let mut row = Vec::new();
+            flatten_into(fields, "", &mut row);
+            row
+        }
+        scalar => vec![("value".to_string(), scalar.clone())],
+    }
+}
+
+/// Appends the scalar fields of `fields` to `row`, recursing into nested objects. Arrays are
+/// left out, as they don't fit in a single cell.
+fn flatten_into(fields: &Map<String, Value>, prefix: &str, row: &mut Vec<(String, Value)>) {
+    for (name, value) in fields {
+        let column = format!("{}{}", prefix, name);
+        match value {
+            Value::Object(nested) => flatten_into(nested, &format!("{}.", column), row),
+            Value::Array(_) => {}
+            scalar => row.push((column, scalar.clone())),
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => escape(text),
+        other => other.to_string(),
+    }
+}
+
+/// Quotes `text` when it holds a separator, quote or line break, doubling its quotes.
+fn escape(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Adds `Accept` to the response's `Vary` header, unless it is already listed.
+pub fn vary_on_accept(headers: &mut HeaderMap) {
+    let listed = headers
+        .get_all(VARY)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|name| name.trim().eq_ignore_ascii_case("accept") || name.trim() == "*");
+    if !listed {
+        headers.append(VARY, HeaderValue::from_static("Accept"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_becomes_rows_prefixed_with_scalar_fields() {
+        let value = serde_json::json!({
+            "tokenAddress": "So11111111111111111111111111111111111111112",
+            "interval": "1D",
+            "prices": [
+                { "unixTime": 1700000000, "value": 100.5 },
+                { "unixTime": 1700086400, "value": 101.0 }
+            ]
+        });
+
+        assert_eq!(
+            to_csv(&value),
+            "tokenAddress,interval,unixTime,value\n\
+             So11111111111111111111111111111111111111112,1D,1700000000,100.5\n\
+             So11111111111111111111111111111111111111112,1D,1700086400,101.0\n"
+        );
+    }
+
+    #[test]
+    fn test_single_value_becomes_one_row_with_flattened_fields() {
+        let value = serde_json::json!({
+            "historicalVolatility": 0.42,
+            "hvRank": null,
+            "note": "a \"quoted\", value",
+            "extendedStats": { "meanReturn": 0.01, "skewness": -0.2 }
+        });
+
+        assert_eq!(
+            to_csv(&value),
+            "historicalVolatility,hvRank,note,extendedStats.meanReturn,extendedStats.skewness\n\
+             0.42,,\"a \"\"quoted\"\", value\",0.01,-0.2\n"
+        );
+    }
+
+    #[test]
+    fn test_accept_is_added_to_vary_once() {
+        let mut headers = HeaderMap::new();
+        headers.insert(VARY, HeaderValue::from_static("Origin"));
+        vary_on_accept(&mut headers);
+        vary_on_accept(&mut headers);
+
+        let vary: Vec<_> = headers.get_all(VARY).iter().collect();
+        assert_eq!(vary, ["Origin", "Accept"]);
+    }
+}
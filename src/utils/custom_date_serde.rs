@@ -1,32 +1,90 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
 use serde::{self, Deserialize, Deserializer, Serializer};
 
-const FORMAT: &'static str = "%Y-%m-%d";
+const FORMAT: &str = "%Y-%m-%d";
 
-/// Serialize `DateTime<Utc>` into "YYYY-MM-DD" format.
+/// Serialize `DateTime<Utc>` into "YYYY-MM-DD" format at midnight UTC, and as an RFC 3339
+/// datetime otherwise so the time survives a round trip.
 pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
-    let s = date.format(FORMAT).to_string();
+    let s = if date.time() == NaiveTime::MIN {
+        date.format(FORMAT).to_string()
+    } else {
+        date.to_rfc3339_opts(SecondsFormat::Secs, true)
+    };
     serializer.serialize_str(&s)
 }
 
-/// Deserialize a "YYYY-MM-DD" string into `DateTime<Utc>`, assuming 00:00:00 time.
+/// [`serialize`] for optional dates; `None` is left out of query strings.
+pub fn serialize_option<S>(date: &Option<DateTime<Utc>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match date {
+        Some(date) => serialize(date, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Length of accepted Unix timestamps: 10 digits covers seconds from 2001-09-09 to 2286,
+/// while compact `YYYYMMDD` dates (8 digits) and millisecond timestamps (13 digits) are
+/// rejected rather than misread.
+const UNIX_SECONDS_DIGITS: usize = 10;
+
+/// Deserialize a "YYYY-MM-DD" string into `DateTime<Utc>`, assuming 00:00:00 time, or an
+/// ISO-8601 datetime with offset or a Unix timestamp in seconds into that exact time.
 pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    let date = NaiveDate::parse_from_str(&s, FORMAT).map_err(serde::de::Error::custom)?;
+    parse(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parses a "YYYY-MM-DD" date, an ISO-8601 datetime with offset (RFC 3339, e.g.
+/// `2025-03-31T14:00:00Z`) or a Unix timestamp in seconds.
+fn parse(s: &str) -> Result<DateTime<Utc>, String> {
+    if s.len() == UNIX_SECONDS_DIGITS && s.bytes().all(|b| b.is_ascii_digit()) {
+        let unix_time: i64 = s.parse().map_err(|e: std::num::ParseIntError| e.to_string())?;
+        return DateTime::<Utc>::from_timestamp(unix_time, 0)
+            .ok_or_else(|| format!("Unix timestamp {} is out of range", unix_time));
+    }
+
+    if s.contains('T') {
+        // An unencoded `+` offset arrives as a space once the query string is decoded
+        return DateTime::parse_from_rfc3339(&s.replace(' ', "+"))
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .map_err(|_| {
+                format!(
+                    "invalid datetime '{}': expected ISO-8601 with an offset, e.g. 2025-03-31T14:00:00Z",
+                    s
+                )
+            });
+    }
 
+    let date = NaiveDate::parse_from_str(s, FORMAT).map_err(|_| {
+        format!(
+            "invalid date '{}': expected YYYY-MM-DD, an ISO-8601 datetime or a Unix timestamp in seconds",
+            s
+        )
+    })?;
     let datetime = date
         .and_hms_opt(0, 0, 0)
-        .ok_or_else(|| serde::de::Error::custom("Invalid hour/minute/second"))?;
+        .ok_or_else(|| "Invalid hour/minute/second".to_string())?;
 
     Ok(DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
 }
 
+/// [`deserialize`] for optional dates; pair with `#[serde(default)]` so a missing field is `None`.
+pub fn deserialize_option<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize(deserializer).map(Some)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,6 +104,13 @@ mod tests {
         assert_eq!(serialized, "2024-04-05");
     }
 
+    #[test]
+    fn test_serialize_keeps_time_of_day() {
+        let datetime = Utc.with_ymd_and_hms(2024, 4, 5, 14, 0, 0).unwrap();
+        let serialized = serialize(&datetime, serde_json::value::Serializer).unwrap();
+        assert_eq!(serialized, "2024-04-05T14:00:00Z");
+    }
+
     #[test]
     fn test_deserialize_valid_date() {
         let json = r#"{ "date": "2024-04-05" }"#;
@@ -56,6 +121,42 @@ mod tests {
         assert_eq!(result.date, expected);
     }
 
+    #[test]
+    fn test_deserialize_unix_timestamp() {
+        let json = r#"{ "date": "1712319000" }"#;
+        let result: TestStruct =
+            serde_json::from_str(json).expect("deserialization should have succeeded");
+
+        let expected = Utc.with_ymd_and_hms(2024, 4, 5, 12, 10, 0).unwrap();
+        assert_eq!(result.date, expected);
+    }
+
+    #[test]
+    fn test_deserialize_iso_datetime() {
+        let expected = Utc.with_ymd_and_hms(2025, 3, 31, 14, 0, 0).unwrap();
+        for input in [
+            "2025-03-31T14:00:00Z",
+            "2025-03-31T16:00:00+02:00",
+            "2025-03-31T16:00:00 02:00",
+        ] {
+            let json = format!(r#"{{ "date": "{}" }}"#, input);
+            let result: TestStruct =
+                serde_json::from_str(&json).expect("deserialization should have succeeded");
+            assert_eq!(result.date, expected, "{}", input);
+        }
+
+        let result: Result<TestStruct, _> = serde_json::from_str(r#"{ "date": "2025-03-31T14:00:00" }"#);
+        assert!(result.is_err(), "Expected error for datetime without offset");
+    }
+
+    #[test]
+    fn test_deserialize_millisecond_timestamp_is_rejected() {
+        let json = r#"{ "date": "1712319000000" }"#;
+        let result: Result<TestStruct, _> = serde_json::from_str(json);
+
+        assert!(result.is_err(), "Expected error for millisecond timestamp");
+    }
+
     #[test]
     fn test_deserialize_invalid_date_format() {
         let json = r#"{ "date": "20240405" }"#; // wrong format
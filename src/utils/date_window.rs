@@ -0,0 +1,111 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use crate::errors::api_error::ApiError;
+
+/// Default look-back for endpoints that take a `days` parameter.
+pub const DEFAULT_WINDOW_DAYS: u32 = 90;
+
+/// Returns the `[now - days, now]` window used by the `days`-based endpoints.
+///
+/// # Errors
+/// - `ApiError::InvalidQuery` if `days` is 0 or reaches further back than representable.
+/// - `ApiError::DateRangeTooLong` if `days` exceeds `max_days`.
+pub fn trailing_window(days: u32, max_days: u32) -> Result<(DateTime<Utc>, DateTime<Utc>), ApiError> {
+    if days == 0 {
+        return Err(ApiError::InvalidQuery(
+            "days must be greater than 0.".to_string(),
+        ));
+    }
+    if days > max_days {
+        return Err(ApiError::DateRangeTooLong(max_days));
+    }
+
+    let to_date = Utc::now();
+    let from_date = to_date
+        .checked_sub_signed(Duration::days(days as i64))
+        .ok_or_else(|| ApiError::InvalidQuery(format!("days {} is out of range.", days)))?;
+    Ok((from_date, to_date))
+}
+
+/// Returns the `DEFAULT_WINDOW_DAYS` ending yesterday, as whole dates, used when a request
+/// gives neither `fromDate` nor `toDate`. Ending yesterday keeps today's still-forming
+/// candle out of the result.
+pub fn default_range() -> (DateTime<Utc>, DateTime<Utc>) {
+    let today = Utc::now().date_naive().and_time(NaiveTime::MIN).and_utc();
+    let to_date = today - Duration::days(1);
+    (to_date - Duration::days(DEFAULT_WINDOW_DAYS as i64), to_date)
+}
+
+/// Checks an explicit `[from_date, to_date]` range: in order, not ending in the future, and
+/// spanning at most `max_days`.
+///
+/// # Errors
+/// - `ApiError::FromDateAfterToDate`, `ApiError::ToDateInFuture` or
+///   `ApiError::DateRangeTooLong`, whichever check fails first.
+pub fn validate_range(
+    from_date: DateTime<Utc>,
+    to_date: DateTime<Utc>,
+    max_days: u32,
+) -> Result<(), ApiError> {
+    if from_date > to_date {
+        return Err(ApiError::FromDateAfterToDate);
+    }
+    if to_date > Utc::now() {
+        return Err(ApiError::ToDateInFuture);
+    }
+    if to_date - from_date > Duration::days(max_days as i64) {
+        return Err(ApiError::DateRangeTooLong(max_days));
+    }
+    Ok(())
+}
+
+pub fn default_window_days() -> u32 {
+    DEFAULT_WINDOW_DAYS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_window_rejects_spans_above_the_maximum() {
+        let (from_date, to_date) = trailing_window(30, 730).unwrap();
+        assert_eq!(to_date - from_date, Duration::days(30));
+        assert!(matches!(trailing_window(0, 730), Err(ApiError::InvalidQuery(_))));
+        assert!(matches!(
+            trailing_window(731, 730),
+            Err(ApiError::DateRangeTooLong(730))
+        ));
+        assert!(matches!(
+            trailing_window(u32::MAX, u32::MAX),
+            Err(ApiError::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_range() {
+        let now = Utc::now();
+        let day = Duration::days(1);
+        assert!(validate_range(now - day * 730, now, 730).is_ok());
+        assert!(matches!(
+            validate_range(now - day, now - day * 2, 730),
+            Err(ApiError::FromDateAfterToDate)
+        ));
+        assert!(matches!(
+            validate_range(now, now + day, 730),
+            Err(ApiError::ToDateInFuture)
+        ));
+        assert!(matches!(
+            validate_range(now - day * 731, now, 730),
+            Err(ApiError::DateRangeTooLong(730))
+        ));
+    }
+
+    #[test]
+    fn test_default_range_ends_yesterday() {
+        let (from_date, to_date) = default_range();
+        let yesterday = Utc::now().date_naive() - Duration::days(1);
+        assert_eq!(to_date.date_naive(), yesterday);
+        assert_eq!(to_date.time(), NaiveTime::MIN);
+        assert_eq!(to_date - from_date, Duration::days(DEFAULT_WINDOW_DAYS as i64));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::errors::api_error::ApiError;
+use axum::{extract::Request, http::HeaderMap, middleware::Next, response::Response};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+/// Header carrying a relative time budget in milliseconds.
+pub const TIMEOUT_HEADER: &str = "x-request-timeout";
+/// Header carrying an absolute RFC 3339 deadline.
+pub const DEADLINE_HEADER: &str = "x-request-deadline";
+
+tokio::task_local! {
+    /// Deadline of the request being handled on this task, if the client sent one.
+    static DEADLINE: Instant;
+}
+
+/// Time left before the current request's deadline, or `None` without one.
+///
+/// Provider calls use this to cap their HTTP timeout so they never outlive the caller.
+pub fn remaining() -> Option<Duration> {
+    DEADLINE
+        .try_with(|deadline| deadline.saturating_duration_since(Instant::now()))
+        .ok()
+}
+
+/// Whether the current request's deadline has already passed.
+///
+/// Lets handlers tell a provider call cut short by [`with_deadline`] apart from a real failure.
+pub fn expired() -> bool {
+    remaining().is_some_and(|remaining| remaining.is_zero())
+}
+
+/// Caps a provider request's timeout at the time left before the request deadline, if any.
+pub fn with_deadline(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    match remaining() {
+        Some(remaining) => request.timeout(remaining),
+        None => request,
+    }
+}
+
+/// Middleware enforcing client-supplied deadlines.
+///
+/// When `X-Request-Timeout` or `X-Request-Deadline` is present (the earlier wins), the
+/// handler runs with the deadline in scope and is cancelled when it passes, dropping any
+/// in-flight upstream calls and answering `504 Gateway Timeout`. Malformed headers are
+/// ignored.
+pub async fn enforce_deadline(request: Request, next: Next) -> Response {
+    let Some(deadline) = parse_deadline(request.headers()) else {
+        return next.run(request).await;
+    };
+
+    match DEADLINE
+        .scope(deadline, tokio::time::timeout_at(deadline, next.run(request)))
+        .await
+    {
+        Ok(response) => response,
+        Err(_) => axum::response::IntoResponse::into_response(ApiError::DeadlineExceeded),
+    }
+}
+
+fn parse_deadline(headers: &HeaderMap) -> Option<Instant> {
+    let now = Instant::now();
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let from_timeout = header(TIMEOUT_HEADER).and_then(|value| match value.trim().parse::<u64>() {
+        Ok(millis) => Some(now + Duration::from_millis(millis)),
+        Err(_) => {
+            warn!(value = %value, "Ignoring malformed X-Request-Timeout header");
+            None
+        }
+    });
+
+    let from_deadline = header(DEADLINE_HEADER).and_then(|value| {
+        match DateTime::parse_from_rfc3339(value.trim()) {
+            Ok(deadline) => {
+                let budget = (deadline.with_timezone(&Utc) - Utc::now())
+                    .to_std()
+                    .unwrap_or(Duration::ZERO);
+                Some(now + budget)
+            }
+            Err(_) => {
+                warn!(value = %value, "Ignoring malformed X-Request-Deadline header");
+                None
+            }
+        }
+    });
+
+    match (from_timeout, from_deadline) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[tokio::test]
+    async fn test_earliest_deadline_wins() {
+        let mut headers = HeaderMap::new();
+        headers.insert(TIMEOUT_HEADER, HeaderValue::from_static("60000"));
+        let deadline = (Utc::now() + chrono::Duration::seconds(5)).to_rfc3339();
+        headers.insert(DEADLINE_HEADER, HeaderValue::from_str(&deadline).unwrap());
+
+        let budget = parse_deadline(&headers).unwrap() - Instant::now();
+        assert!(budget <= Duration::from_secs(5));
+
+        headers.insert(TIMEOUT_HEADER, HeaderValue::from_static("soon"));
+        headers.remove(DEADLINE_HEADER);
+        assert!(parse_deadline(&headers).is_none());
+    }
+}
@@ -1 +1,12 @@
-pub mod custom_date_serde;
\ No newline at end of file
+pub mod address_type;
+pub mod base58;
+pub mod chain;
+pub mod csv_format;
+pub mod custom_date_serde;
+pub mod date_window;
+pub mod deadline;
+pub mod msgpack_format;
+pub mod ndjson;
+pub mod number_format;
+pub mod request_scope;
+pub mod timestamp_format;
@@ -0,0 +1,75 @@
+use crate::errors::api_error::ApiError;
+use crate::utils::csv_format::vary_on_accept;
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{
+        header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE},
+        HeaderValue,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use tracing::error;
+
+const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Middleware encoding every JSON response, errors included, as MessagePack when the request
+/// accepts `application/msgpack` (or `application/x-msgpack`), for bots that want smaller
+/// payloads and cheaper decoding than JSON.
+///
+/// Field names and values are the same as in the JSON response; objects become maps with
+/// string keys, integers stay integers and other numbers become 64-bit floats. JSON
+/// responses carry `Vary: Accept` whether or not they were encoded, since either form can
+/// be served for the same URL.
+pub async fn render_msgpack(request: Request, next: Next) -> Response {
+    let accepts_msgpack = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.split(',').any(|media| {
+                let media = media.trim();
+                media.starts_with(MSGPACK_CONTENT_TYPE) || media.starts_with("application/x-msgpack")
+            })
+        });
+
+    let mut response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if is_json {
+        vary_on_accept(response.headers_mut());
+    }
+    if !accepts_msgpack || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            error!(error = %err, "Failed to read response body");
+            return ApiError::InternalServerError.into_response();
+        }
+    };
+    let Ok(value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let encoded = match rmp_serde::to_vec_named(&value) {
+        Ok(encoded) => encoded,
+        Err(err) => {
+            error!(error = %err, "Failed to encode response as MessagePack");
+            return ApiError::InternalServerError.into_response();
+        }
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static(MSGPACK_CONTENT_TYPE));
+    Response::from_parts(parts, Body::from(encoded))
+}
@@ -0,0 +1,81 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{
+        header::{ACCEPT, CONTENT_TYPE},
+        HeaderMap,
+    },
+    response::{IntoResponse, Response},
+};
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use std::convert::Infallible;
+use tracing::error;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Whether the request accepts newline-delimited JSON.
+pub fn accepts_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|media| media.trim().starts_with(NDJSON_CONTENT_TYPE))
+        })
+}
+
+/// `items` as JSON lines, each ending with a newline.
+pub fn lines<T: Serialize>(items: impl IntoIterator<Item = T>) -> Bytes {
+    let mut bytes = Vec::new();
+    for item in items {
+        match serde_json::to_writer(&mut bytes, &item) {
+            Ok(()) => bytes.push(b'\n'),
+            Err(e) => error!(error = %e, "Failed to serialize NDJSON line"),
+        }
+    }
+    Bytes::from(bytes)
+}
+
+/// Line ending a stream that failed after its status was sent, holding the error body a
+/// non-streamed response would have had.
+pub async fn error_line(err: ApiError) -> Bytes {
+    let body = to_bytes(err.into_response().into_body(), usize::MAX)
+        .await
+        .unwrap_or_default();
+    let mut line = body.to_vec();
+    line.push(b'\n');
+    Bytes::from(line)
+}
+
+/// Streamed `200 OK` response sending each chunk of lines as soon as it's produced.
+pub fn response(chunks: impl Stream<Item = Bytes> + Send + 'static) -> Response {
+    (
+        [(CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(chunks.map(Ok::<_, Infallible>)),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_accept_header_is_matched_among_media_types() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_ndjson(&headers));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json, application/x-ndjson;q=0.9"));
+        assert!(accepts_ndjson(&headers));
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        assert!(!accepts_ndjson(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_error_line_holds_the_error_body() {
+        let line = error_line(ApiError::InvalidQuery("bad".to_string())).await;
+        assert_eq!(&line[..], b"{\"error\":\"Bad Request\",\"message\":\"bad\"}\n");
+    }
+}
@@ -0,0 +1,93 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Query, Request},
+    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::error;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NumberFormatQuery {
+    #[serde(default)]
+    numbers_as_strings: bool,
+}
+
+/// Middleware rendering every floating-point number of a JSON response as a string when the
+/// request sets `numbersAsStrings=true`, for JS and fixed-point consumers that would
+/// otherwise round them through a double. Integers (counts, Unix timestamps) stay numbers.
+///
+/// Numbers are written in the shortest form that parses back to the same `f64`.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a value other than `true` or `false`.
+pub async fn render_numbers_as_strings(request: Request, next: Next) -> Response {
+    let numbers_as_strings = match Query::<NumberFormatQuery>::try_from_uri(request.uri()) {
+        Ok(Query(query)) => query.numbers_as_strings,
+        Err(err) => return ApiError::InvalidQuery(err.body_text()).into_response(),
+    };
+
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !numbers_as_strings || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            error!(error = %err, "Failed to read response body");
+            return ApiError::InternalServerError.into_response();
+        }
+    };
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    stringify_floats(&mut value);
+
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(value.to_string()))
+}
+
+/// Replaces every non-integer number in `value` with its string form, recursively.
+fn stringify_floats(value: &mut Value) {
+    match value {
+        Value::Number(number) if number.is_f64() => *value = Value::String(number.to_string()),
+        Value::Array(items) => items.iter_mut().for_each(stringify_floats),
+        Value::Object(fields) => fields.values_mut().for_each(stringify_floats),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_floats_become_strings() {
+        let mut value = serde_json::json!({
+            "historicalVolatility": 0.1 + 0.2,
+            "annualizationDays": 365,
+            "prices": [{ "unixTime": 1700000000, "value": 100.0 }],
+            "hvRank": null,
+            "token": "SOL"
+        });
+        stringify_floats(&mut value);
+
+        assert_eq!(value["historicalVolatility"], "0.30000000000000004");
+        assert_eq!(value["annualizationDays"], 365);
+        assert_eq!(value["prices"][0]["unixTime"], 1700000000);
+        assert_eq!(value["prices"][0]["value"], "100.0");
+        assert_eq!(value["hvRank"], Value::Null);
+        assert_eq!(value["token"], "SOL");
+    }
+}
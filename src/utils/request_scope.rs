@@ -0,0 +1,46 @@
+use crate::utils::address_type::{self, AddressType};
+use crate::utils::chain::{self, Chain};
+use crate::utils::timestamp_format::{self, TimestampFormat};
+use std::future::Future;
+
+/// Settings the scoping middlewares put in task-locals for a request (chain, address type,
+/// timestamp format), captured by a handler so work done after it returned, such as
+/// producing a streamed body, sees the same ones.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestScope {
+    chain: Chain,
+    address_type: AddressType,
+    timestamp_format: Option<TimestampFormat>,
+}
+
+impl RequestScope {
+    /// Settings of the request being handled on this task.
+    pub fn capture() -> Self {
+        Self {
+            chain: chain::current(),
+            address_type: address_type::current(),
+            timestamp_format: timestamp_format::requested(),
+        }
+    }
+
+    /// Runs `future` with the captured settings in scope.
+    pub async fn run<F: Future>(self, future: F) -> F::Output {
+        let future = timestamp_format::scope(self.timestamp_format, future);
+        let future = address_type::scope(self.address_type, future);
+        chain::scope(self.chain, future).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_captured_settings_apply_outside_the_request() {
+        let scope = chain::scope(Chain::Base, async { RequestScope::capture() }).await;
+        assert_eq!(chain::current(), Chain::Solana);
+
+        let seen = scope.run(async { (chain::current(), address_type::current()) }).await;
+        assert_eq!(seen, (Chain::Base, AddressType::default()));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::errors::api_error::ApiError;
+use axum::{
+    extract::{Query, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::future::Future;
+
+/// How timestamps in response payloads are rendered, chosen with `timestampFormat`.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampFormat {
+    /// Seconds since the Unix epoch, e.g. `1700000000`.
+    Unix,
+    /// RFC 3339 in UTC, e.g. `2023-11-14T22:13:20Z`.
+    Rfc3339,
+    /// UTC calendar date, e.g. `2023-11-14`.
+    Date,
+}
+
+tokio::task_local! {
+    /// Format requested for the response being built on this task, if the client chose one.
+    static FORMAT: Option<TimestampFormat>;
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimestampFormatQuery {
+    timestamp_format: Option<TimestampFormat>,
+}
+
+/// Middleware reading the `timestampFormat` query parameter and putting it in scope for
+/// the handler, so the serializers below render every timestamp of the response alike.
+///
+/// # Errors
+/// - Returns `400 Bad Request` for a value other than `unix`, `rfc3339` or `date`.
+pub async fn scope_timestamp_format(request: Request, next: Next) -> Response {
+    let format = match Query::<TimestampFormatQuery>::try_from_uri(request.uri()) {
+        Ok(Query(query)) => query.timestamp_format,
+        Err(err) => return ApiError::InvalidQuery(err.body_text()).into_response(),
+    };
+
+    FORMAT.scope(format, next.run(request)).await
+}
+
+/// Format the current request chose, if any; `None` outside a request.
+pub fn requested() -> Option<TimestampFormat> {
+    FORMAT.try_with(|format| *format).ok().flatten()
+}
+
+/// Runs `future` with `format` in scope, as for a request choosing that format.
+pub async fn scope<F: Future>(format: Option<TimestampFormat>, future: F) -> F::Output {
+    FORMAT.scope(format, future).await
+}
+
+fn serialize_in<S>(
+    unix_time: i64,
+    format: TimestampFormat,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let datetime = || {
+        DateTime::<Utc>::from_timestamp(unix_time, 0)
+            .ok_or_else(|| serde::ser::Error::custom("timestamp out of range"))
+    };
+
+    match format {
+        TimestampFormat::Unix => serializer.serialize_i64(unix_time),
+        TimestampFormat::Rfc3339 => serializer
+            .serialize_str(&datetime()?.to_rfc3339_opts(SecondsFormat::Secs, true)),
+        TimestampFormat::Date => {
+            serializer.serialize_str(&datetime()?.format("%Y-%m-%d").to_string())
+        }
+    }
+}
+
+/// Serialize a Unix timestamp in the requested format, as seconds when none was requested.
+pub fn serialize<S>(unix_time: &i64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serialize_in(
+        *unix_time,
+        requested().unwrap_or(TimestampFormat::Unix),
+        serializer,
+    )
+}
+
+/// [`serialize`] for optional timestamps; `None` stays `null`.
+pub fn serialize_option<S>(unix_time: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match unix_time {
+        Some(unix_time) => serialize(unix_time, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serialize a `DateTime<Utc>` in the requested format, as chrono's RFC 3339 when none was
+/// requested.
+pub fn serialize_datetime<S>(datetime: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match requested() {
+        Some(format) => serialize_in(datetime.timestamp(), format, serializer),
+        None => datetime.serialize(serializer),
+    }
+}
+
+/// [`serialize_datetime`] for optional datetimes; `None` stays `null`.
+pub fn serialize_datetime_option<S>(
+    datetime: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match datetime {
+        Some(datetime) => serialize_datetime(datetime, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize a timestamp given in any of the formats responses can render: Unix seconds,
+/// RFC 3339, or a `YYYY-MM-DD` date (its midnight UTC).
+pub fn deserialize_datetime<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_datetime(&s).ok_or_else(|| {
+        serde::de::Error::custom(format!(
+            "invalid timestamp '{}': expected Unix seconds, RFC 3339 or YYYY-MM-DD",
+            s
+        ))
+    })
+}
+
+fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(unix_time) = s.parse::<i64>() {
+        return DateTime::<Utc>::from_timestamp(unix_time, 0);
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[derive(Serialize)]
+    struct TestStruct {
+        #[serde(serialize_with = "serialize")]
+        unix_time: i64,
+        #[serde(serialize_with = "serialize_datetime")]
+        at: DateTime<Utc>,
+    }
+
+    fn render() -> serde_json::Value {
+        serde_json::to_value(TestStruct {
+            unix_time: 1700000000,
+            at: Utc.with_ymd_and_hms(2024, 4, 5, 12, 30, 0).unwrap(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_timestamps_follow_the_scoped_format() {
+        let default = render();
+        assert_eq!(default["unix_time"], 1700000000);
+        assert_eq!(default["at"], "2024-04-05T12:30:00Z");
+
+        let rfc3339 = FORMAT.scope(Some(TimestampFormat::Rfc3339), async { render() }).await;
+        assert_eq!(rfc3339["unix_time"], "2023-11-14T22:13:20Z");
+
+        let date = FORMAT.scope(Some(TimestampFormat::Date), async { render() }).await;
+        assert_eq!(date["unix_time"], "2023-11-14");
+        assert_eq!(date["at"], "2024-04-05");
+
+        let unix = FORMAT.scope(Some(TimestampFormat::Unix), async { render() }).await;
+        assert_eq!(unix["at"], 1712320200);
+    }
+
+    #[test]
+    fn test_parses_every_rendered_format() {
+        let expected = Utc.with_ymd_and_hms(2023, 11, 14, 0, 0, 0).unwrap();
+        assert_eq!(parse_datetime("1699920000"), Some(expected));
+        assert_eq!(parse_datetime("2023-11-14T00:00:00Z"), Some(expected));
+        assert_eq!(parse_datetime("2023-11-14T01:00:00+01:00"), Some(expected));
+        assert_eq!(parse_datetime("2023-11-14"), Some(expected));
+        assert_eq!(parse_datetime("yesterday"), None);
+    }
+}
@@ -0,0 +1,363 @@
+#![cfg(feature = "admin")]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::{CacheStats, VolatilityCache};
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::math::{EstimatorKind, Interval};
+use historical_volatility_api::routes::admin::{
+    ImportResponse, StateSnapshot, TrackTokenResponse, VerifyResponse, WatchedToken,
+};
+use historical_volatility_api::routes::presets::PresetsResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+const ADMIN_KEY: &str = "admin-secret";
+const SOL: &str = "So11111111111111111111111111111111111111112";
+const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+async fn app_state(server: &MockServer) -> AppState {
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 }
+                ]
+            }
+        })))
+        .mount(server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        admin_api_key: Some(ADMIN_KEY.to_string()),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    AppState::new(config, volatility_cache)
+}
+
+#[tokio::test]
+async fn admin_endpoints_reject_missing_key() {
+    let server = MockServer::start().await;
+    let app = register_routes(app_state(&server).await);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/admin/export")
+                .header("x-admin-key", "wrong")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn verify_compares_cached_volatility_with_fresh_computation() {
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+    state
+        .volatility_cache
+        .add_token("tokenA".to_string())
+        .await
+        .expect("token should be cached");
+
+    let verify = |token: &'static str| {
+        register_routes(state.clone()).oneshot(
+            Request::builder()
+                .uri(format!("/admin/verify/{}", token))
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    let response = verify("tokenA").await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let verified: VerifyResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(verified.token_address, "tokenA");
+    assert!(verified.cached.volatility > 0.0);
+    assert_eq!(verified.fresh.volatility, verified.cached.volatility);
+    assert_eq!(verified.delta, 0.0);
+    assert_eq!(verified.relative_delta, Some(0.0));
+    assert_eq!(verified.data_points, 3);
+
+    let response = verify("tokenB").await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn tracked_tokens_can_be_added_and_removed() {
+    const TOKEN: &str = "LABSh5DTebUcUbEoLzXKCiXFJLecDFiDWiBGUU1GpxR";
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+
+    let send = |method: &str, uri: String, body: Body| {
+        register_routes(state.clone()).oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(body)
+                .unwrap(),
+        )
+    };
+    let track = || {
+        send(
+            "POST",
+            "/admin/tokens".to_string(),
+            Body::from(format!(r#"{{ "tokenAddress": "{}" }}"#, TOKEN)),
+        )
+    };
+
+    let response = track().await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let tracked: TrackTokenResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(tracked.token_address, TOKEN);
+    assert!(tracked.volatility > 0.0);
+    assert_eq!(state.volatility_cache.tracked_tokens().await, vec![TOKEN.to_string()]);
+
+    let response = track().await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = send(
+        "POST",
+        "/admin/tokens".to_string(),
+        Body::from(r#"{ "tokenAddress": "not-a-token" }"#),
+    )
+    .await
+    .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let untrack = || send("DELETE", format!("/admin/tokens/{}", TOKEN), Body::empty());
+    let response = untrack().await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(state.volatility_cache.tracked_tokens().await.is_empty());
+
+    let response = untrack().await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn cache_stats_count_volatility_requests() {
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+    let send = |uri: &'static str| {
+        register_routes(state.clone()).oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+
+    // The first request misses and adds the token, the second hits
+    for _ in 0..2 {
+        let response = send("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112")
+            .await
+            .expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let response = send("/admin/cacheStats").await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let stats: CacheStats = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(stats.entries, 1);
+    assert_eq!((stats.hits, stats.misses), (1, 1));
+    assert_eq!(stats.refreshes, 0);
+    assert!(stats.last_refresh_at.is_none());
+}
+
+#[tokio::test]
+async fn import_then_export_round_trips_watchlist() {
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+
+    let import = register_routes(state.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/import")
+                .header("content-type", "application/json")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::from(
+                    serde_json::json!({
+                        "version": 2,
+                        "exportedAt": "2025-01-01T00:00:00Z",
+                        "watchlist": [
+                            { "tokenAddress": SOL, "pinned": true },
+                            { "tokenAddress": SOL, "pinned": true },
+                            { "tokenAddress": USDC, "pinned": false }
+                        ]
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(import.status(), StatusCode::OK);
+    let imported: ImportResponse = serde_json::from_slice(
+        &to_bytes(import.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(imported.imported, vec![SOL.to_string(), USDC.to_string()]);
+    assert_eq!(imported.skipped, vec![SOL.to_string()]);
+
+    let export = register_routes(state)
+        .oneshot(
+            Request::builder()
+                .uri("/admin/export")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(export.status(), StatusCode::OK);
+    let snapshot: StateSnapshot = serde_json::from_slice(
+        &to_bytes(export.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(snapshot.version, 2);
+    assert_eq!(
+        snapshot.watchlist,
+        vec![
+            WatchedToken { token_address: USDC.to_string(), pinned: false },
+            WatchedToken { token_address: SOL.to_string(), pinned: true },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn import_rejects_invalid_addresses_before_importing_anything() {
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+
+    let import = register_routes(state.clone())
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/import")
+                .header("content-type", "application/json")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::from(
+                    serde_json::json!({
+                        "version": 2,
+                        "exportedAt": "2025-01-01T00:00:00Z",
+                        "watchlist": [
+                            { "tokenAddress": SOL, "pinned": true },
+                            { "tokenAddress": "not-an-address", "pinned": false }
+                        ],
+                        "presets": { "optionsDesk": { "interval": "1H" } }
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(import.status(), StatusCode::BAD_REQUEST);
+    assert!(state.volatility_cache.tracked_tokens().await.is_empty());
+    assert!(state.presets.all().await.is_empty());
+}
+
+#[tokio::test]
+async fn defined_preset_is_listed_and_exported() {
+    let server = MockServer::start().await;
+    let state = app_state(&server).await;
+
+    let put = register_routes(state.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/admin/presets/optionsDesk")
+                .header("content-type", "application/json")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::from(
+                    r#"{ "estimator": "yang_zhang", "interval": "1H", "annualizationDays": 365 }"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(put.status(), StatusCode::CREATED);
+
+    let list = register_routes(state.clone())
+        .oneshot(Request::builder().uri("/presets").body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+    let listed: PresetsResponse = serde_json::from_slice(
+        &to_bytes(list.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    let preset = &listed.presets["optionsDesk"];
+    assert_eq!(preset.estimator, Some(EstimatorKind::YangZhang));
+    assert_eq!(preset.interval, Some(Interval::OneHour));
+
+    let export = register_routes(state.clone())
+        .oneshot(
+            Request::builder()
+                .uri("/admin/export")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    let snapshot: StateSnapshot = serde_json::from_slice(
+        &to_bytes(export.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(snapshot.presets.get("optionsDesk"), Some(preset));
+
+    let delete = register_routes(state)
+        .oneshot(
+            Request::builder()
+                .method("DELETE")
+                .uri("/admin/presets/optionsDesk")
+                .header("x-admin-key", ADMIN_KEY)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(delete.status(), StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "client")]
+
+use chrono::{TimeZone, Utc};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::client::{Client, ClientError};
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::extractors::query_extractor::HistoricalVolatilityParams;
+use historical_volatility_api::math::{EstimatorKind, Interval};
+use historical_volatility_api::routes::prices::PricesQuery;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use wiremock::{
+    matchers::{method, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+/// Serves the API on a random local port, backed by `provider`, returning its base URL.
+async fn serve(provider: &MockServer) -> String {
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: provider.uri(),
+        birdeye_ohlcv_url: provider.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("should bind a local port");
+    let address = listener.local_addr().expect("should have a local address");
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    format!("http://{}", address)
+}
+
+#[tokio::test]
+async fn client_round_trips_shared_request_and_response_types() {
+    let provider = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("time_from", "1699920000"))
+        .and(query_param("time_to", "1699970400"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "o": 100.0, "h": 110.0, "l": 100.0, "c": 105.0, "v": 1.0, "value": 100.0 },
+                    { "unixTime": 1699963200, "o": 105.0, "h": 115.5, "l": 105.0, "c": 110.0, "v": 1.0, "value": 101.0 }
+                ]
+            }
+        })))
+        .mount(&provider)
+        .await;
+    let client = Client::new(serve(&provider).await);
+
+    let health = client.health_check().await.expect("health check should succeed");
+    assert_eq!(health.message, "Server is running.");
+
+    // Dates with a time of day are sent as datetimes, not truncated to the day
+    let prices = client
+        .prices(&PricesQuery {
+            from_date: Utc.with_ymd_and_hms(2023, 11, 14, 0, 0, 0).unwrap(),
+            to_date: Utc.with_ymd_and_hms(2023, 11, 14, 14, 0, 0).unwrap(),
+            token_address: "So11111111111111111111111111111111111111112".to_string(),
+            interval: Interval::OneHour,
+        })
+        .await
+        .expect("prices should succeed");
+    assert_eq!(prices.prices.len(), 2);
+
+    let volatility = client
+        .historical_volatility(&HistoricalVolatilityParams {
+            from_date: Some(Utc.with_ymd_and_hms(2023, 11, 14, 0, 0, 0).unwrap()),
+            to_date: Some(Utc.with_ymd_and_hms(2023, 11, 14, 14, 0, 0).unwrap()),
+            token_address: "So11111111111111111111111111111111111111112".to_string(),
+            estimator: Some(EstimatorKind::Parkinson),
+            ..Default::default()
+        })
+        .await
+        .expect("volatility should succeed");
+    assert!(volatility.historical_volatility > 0.0);
+}
+
+#[tokio::test]
+async fn client_surfaces_api_errors() {
+    let provider = MockServer::start().await;
+    let client = Client::new(serve(&provider).await);
+
+    let err = client
+        .historical_volatility(&HistoricalVolatilityParams {
+            token_address: "So11111111111111111111111111111111111111112".to_string(),
+            days: Some(0),
+            ..Default::default()
+        })
+        .await
+        .expect_err("days=0 should be rejected");
+    match err {
+        ClientError::Api { status, message, .. } => {
+            assert_eq!(status, 400);
+            assert_eq!(message, "days must be greater than 0.");
+        }
+        err => panic!("expected an API error, got {}", err),
+    }
+}
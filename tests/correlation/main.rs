@@ -0,0 +1,207 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::correlation::CorrelationResponse;
+use historical_volatility_api::routes::correlation_matrix::CorrelationMatrixResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn mount_series(server: &MockServer, address: &str, prices: &[(i64, f64)]) {
+    let items: Vec<_> = prices
+        .iter()
+        .map(|(unix_time, value)| serde_json::json!({ "unixTime": unix_time, "value": value }))
+        .collect();
+    Mock::given(method("GET"))
+        .and(query_param("address", address))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": items }
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn get_correlation_aligns_series_with_missing_candles() {
+    let server = MockServer::start().await;
+    const DAY: i64 = 86_400;
+    // tokenB moves exactly opposite to tokenA but is missing the day-2 candle
+    mount_series(
+        &server,
+        "tokenA",
+        &[(0, 100.0), (DAY, 110.0), (2 * DAY, 99.0), (3 * DAY, 120.0), (4 * DAY, 100.0)],
+    )
+    .await;
+    mount_series(
+        &server,
+        "tokenB",
+        &[(0, 100.0), (DAY, 100.0 / 1.1), (3 * DAY, 100.0 / 1.2), (4 * DAY, 100.0)],
+    )
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/correlation?tokenA=tokenA&tokenB=tokenB&days=30")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let correlation_response: CorrelationResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(correlation_response.data_points, 3);
+    assert!((correlation_response.correlation + 1.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn get_correlation_rejects_days_beyond_the_representable_range() {
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/correlation?tokenA=tokenA&tokenB=tokenB&days=4000000000")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn post_correlation_matrix_returns_symmetric_matrices() {
+    let server = MockServer::start().await;
+    const DAY: i64 = 86_400;
+    let prices = [(0, 100.0), (DAY, 110.0), (2 * DAY, 99.0), (3 * DAY, 120.0)];
+    mount_series(&server, "tokenA", &prices).await;
+    mount_series(&server, "tokenB", &prices).await;
+    mount_series(
+        &server,
+        "tokenC",
+        &[(0, 50.0), (DAY, 51.0), (2 * DAY, 49.0), (3 * DAY, 52.0)],
+    )
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/correlationMatrix")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{ "tokenAddresses": ["tokenA", "tokenB", "tokenC"], "days": 30 }"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let matrix: CorrelationMatrixResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(matrix.data_points, 3);
+    assert!((matrix.correlation[0][1].unwrap() - 1.0).abs() < 1e-9);
+    assert_eq!(matrix.correlation[0][2], matrix.correlation[2][0]);
+    assert_eq!(matrix.covariance[1][2], matrix.covariance[2][1]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/correlationMatrix")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{ "tokenAddresses": ["tokenA"] }"#))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn post_correlation_matrix_resolves_symbols_in_body() {
+    let server = MockServer::start().await;
+    const DAY: i64 = 86_400;
+    let sol = "So11111111111111111111111111111111111111112";
+    let jup = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+    mount_series(&server, sol, &[(0, 100.0), (DAY, 110.0), (2 * DAY, 99.0), (3 * DAY, 120.0)]).await;
+    mount_series(&server, jup, &[(0, 1.0), (DAY, 1.2), (2 * DAY, 1.0), (3 * DAY, 1.1)]).await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/correlationMatrix")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{ "tokenAddresses": ["SOL", "JUP"], "days": 30 }"#))
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let matrix: CorrelationMatrixResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(matrix.token_addresses, vec![sol.to_string(), jup.to_string()]);
+}
@@ -0,0 +1,95 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+
+const ORIGIN: &str = "https://app.example.com";
+
+fn app(config: AppConfig) -> Router {
+    let volatility_cache = VolatilityCache::new(config.clone());
+    register_routes(AppState::new(config, volatility_cache))
+}
+
+/// Browser preflight for a cross-origin request to `uri` with `method`.
+fn preflight(uri: &str, method: &str) -> Request<Body> {
+    Request::builder()
+        .method("OPTIONS")
+        .uri(uri)
+        .header("origin", ORIGIN)
+        .header("access-control-request-method", method)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn public_routes_allow_any_origin_by_default() {
+    let response = app(AppConfig::default())
+        .oneshot(
+            Request::builder()
+                .uri("/healthCheck")
+                .header("origin", ORIGIN)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+}
+
+#[tokio::test]
+async fn public_routes_allow_only_configured_origins() {
+    let app = app(AppConfig {
+        cors_public_origins: vec![ORIGIN.to_string()],
+        ..AppConfig::default()
+    });
+
+    let response = app
+        .clone()
+        .oneshot(preflight("/correlationMatrix", "POST"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.headers()["access-control-allow-origin"], ORIGIN);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/healthCheck")
+                .header("origin", "https://elsewhere.example.com")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert!(response.headers().get("access-control-allow-origin").is_none());
+}
+
+#[cfg(feature = "admin")]
+#[tokio::test]
+async fn admin_routes_allow_no_origin_unless_configured() {
+    let response = app(AppConfig::default())
+        .oneshot(preflight("/admin/presets/desk", "PUT"))
+        .await
+        .expect("Should receive a response");
+    assert!(response.headers().get("access-control-allow-origin").is_none());
+
+    let response = app(AppConfig {
+        cors_admin_origins: vec![ORIGIN.to_string()],
+        ..AppConfig::default()
+    })
+    .oneshot(preflight("/admin/presets/desk", "PUT"))
+    .await
+    .expect("Should receive a response");
+    assert_eq!(response.headers()["access-control-allow-origin"], ORIGIN);
+    let allowed_headers = response.headers()["access-control-allow-headers"]
+        .to_str()
+        .unwrap();
+    assert!(allowed_headers.contains("x-admin-key"));
+}
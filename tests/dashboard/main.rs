@@ -0,0 +1,62 @@
+#![cfg(feature = "dashboard")]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn dashboard_renders_tracked_tokens() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    volatility_cache
+        .add_token("So11111111111111111111111111111111111111112".to_string())
+        .await
+        .expect("Should track token");
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(Request::builder().uri("/dashboard").body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    let body = String::from_utf8(
+        to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body")
+            .to_vec(),
+    )
+    .expect("should be UTF-8");
+    assert!(body.contains(r#""tokenAddress":"So11111111111111111111111111111111111111112""#));
+    assert!(!body.contains("__TRACKED_TOKENS__"));
+}
@@ -0,0 +1,47 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn request_timeout_header_cuts_slow_upstream_short() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_delay(Duration::from_secs(5))
+                .set_body_json(serde_json::json!({ "success": true, "data": { "items": [] } })),
+        )
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let started = Instant::now();
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112")
+                .header("x-request-timeout", "100")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
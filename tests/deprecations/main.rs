@@ -0,0 +1,64 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::{TimeZone, Utc};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::deprecations::{Deprecation, DeprecationRegistry};
+use historical_volatility_api::routes::deprecations::DeprecationsResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn deprecated_routes_carry_headers_and_are_counted() {
+    let config = AppConfig::default();
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState {
+        deprecations: DeprecationRegistry::new(vec![Deprecation {
+            route: "/healthCheck".to_string(),
+            parameter: None,
+            deprecated_at: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            sunset_at: Some(Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap()),
+            link: Some("https://example.com/docs/status".to_string()),
+        }]),
+        ..AppState::new(config, volatility_cache)
+    };
+    let app = register_routes(state);
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(request("/healthCheck"))
+            .await
+            .expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers["deprecation"], "@1735689600");
+        assert_eq!(headers["sunset"], "Thu, 31 Dec 2026 00:00:00 GMT");
+        assert_eq!(
+            headers["link"],
+            "<https://example.com/docs/status>; rel=\"deprecation\""
+        );
+    }
+
+    let response = app
+        .clone()
+        .oneshot(request("/deprecations"))
+        .await
+        .expect("Should receive a response");
+    assert!(response.headers().get("deprecation").is_none());
+    let report: DeprecationsResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(report.deprecations.len(), 1);
+    assert_eq!(report.deprecations[0].feature, "/healthCheck");
+    assert_eq!(report.deprecations[0].uses, 2);
+    assert!(report.deprecations[0].last_used_at.is_some());
+}
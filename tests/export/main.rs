@@ -0,0 +1,124 @@
+#![cfg(feature = "parquet")]
+
+use arrow_array::{Float64Array, RecordBatch, StringArray, TimestampMillisecondArray};
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+const TOKEN: &str = "So11111111111111111111111111111111111111112";
+const DAY: i64 = 86_400;
+/// 2023-11-10T00:00:00Z
+const FIRST_DAY: i64 = 1_699_574_400;
+
+async fn app_with_daily_prices(prices: &[f64]) -> (MockServer, Router) {
+    let server = MockServer::start().await;
+    let items: Vec<_> = prices
+        .iter()
+        .enumerate()
+        .map(|(day, value)| serde_json::json!({ "unixTime": FIRST_DAY + day as i64 * DAY, "value": value }))
+        .collect();
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": items }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    (server, register_routes(AppState::new(config, volatility_cache)))
+}
+
+async fn download(app: Router, uri: &str) -> (axum::http::HeaderMap, RecordBatch) {
+    let response = app
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers().clone();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    let mut batches = ParquetRecordBatchReaderBuilder::try_new(body)
+        .expect("body should be a Parquet file")
+        .build()
+        .unwrap();
+    let batch = batches.next().expect("file should hold a batch").unwrap();
+    (headers, batch)
+}
+
+#[tokio::test]
+async fn export_prices_returns_a_parquet_file() {
+    let (_server, app) = app_with_daily_prices(&[100.0, 101.0, 99.5]).await;
+
+    let (headers, batch) = download(
+        app,
+        &format!("/export/prices.parquet?fromDate=2023-11-10&toDate=2023-11-12&tokenAddress={}", TOKEN),
+    )
+    .await;
+
+    assert_eq!(headers["content-type"], "application/vnd.apache.parquet");
+    assert_eq!(
+        headers["content-disposition"],
+        format!("attachment; filename=\"{}-prices-1D.parquet\"", TOKEN)
+    );
+    assert_eq!(batch.num_rows(), 3);
+    let tokens = batch.column_by_name("token_address").unwrap().as_any().downcast_ref::<StringArray>().unwrap();
+    assert_eq!(tokens.value(0), TOKEN);
+    let times = batch.column_by_name("time").unwrap().as_any().downcast_ref::<TimestampMillisecondArray>().unwrap();
+    assert_eq!(times.value(1), (FIRST_DAY + DAY) * 1000);
+    let prices = batch.column_by_name("price").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+    assert_eq!(prices.values(), &[100.0, 101.0, 99.5]);
+}
+
+#[tokio::test]
+async fn export_volatility_series_covers_the_requested_range() {
+    let (_server, app) = app_with_daily_prices(&[100.0, 102.0, 99.0, 101.0, 103.0, 100.0]).await;
+
+    // Windows of 2 returns ending on 2023-11-12 through 2023-11-15
+    let (_, batch) = download(
+        app.clone(),
+        &format!(
+            "/export/volatilitySeries.parquet?fromDate=2023-11-12&toDate=2023-11-15&tokenAddress={}&windowDays=2",
+            TOKEN
+        ),
+    )
+    .await;
+
+    assert_eq!(batch.num_rows(), 4);
+    let times = batch.column_by_name("time").unwrap().as_any().downcast_ref::<TimestampMillisecondArray>().unwrap();
+    assert_eq!(times.value(0), (FIRST_DAY + 2 * DAY) * 1000);
+    let volatilities = batch.column_by_name("volatility").unwrap().as_any().downcast_ref::<Float64Array>().unwrap();
+    assert!(volatilities.values().iter().all(|volatility| *volatility > 0.0));
+
+    for window_days in ["1", "4000000000"] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/export/volatilitySeries.parquet?fromDate=2023-11-12&toDate=2023-11-15&tokenAddress={}&windowDays={}",
+                        TOKEN, window_days
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -14,7 +14,8 @@ async fn health_check_returns_200_ok() {
     let config = AppConfig {
         birdeye_api_key: "DUMMY_KEY".to_string(),
         birdeye_base_url: "https://dummy.birdeye.api".to_string(),
-        app_server_port: 8080
+        app_server_port: 8080,
+        ..AppConfig::default()
     };
     
     let volatility_cache = VolatilityCache::new(config.clone());
@@ -0,0 +1,107 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::heatmap::HeatmapResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn mount_series(server: &MockServer, address: &str, days: i64) {
+    let items: Vec<_> = (0..days)
+        .map(|day| {
+            let value = if day % 2 == 0 { 100.0 } else { 104.0 + day as f64 };
+            serde_json::json!({ "unixTime": 1_700_000_000 + day * 86_400, "value": value })
+        })
+        .collect();
+    Mock::given(method("GET"))
+        .and(query_param("address", address))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": items }
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn get_heatmap_reads_tracked_tokens_from_cache() {
+    let server = MockServer::start().await;
+    mount_series(&server, "tracked", 12).await;
+    mount_series(&server, "untracked", 12).await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    volatility_cache
+        .add_token("tracked".to_string())
+        .await
+        .expect("should cache the tracked token");
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/heatmap?tokenAddresses=tracked,untracked&windows=90,7,30")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let heatmap: HeatmapResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(heatmap.windows, vec![7, 30, 90]);
+    let [tracked, untracked] = &heatmap.rows[..] else {
+        panic!("expected two rows, got {:?}", heatmap.rows);
+    };
+    assert!(tracked.cached);
+    assert!(tracked.last_updated.is_some());
+    assert!(!untracked.cached);
+    // Both series are identical, so cached and on-demand values agree
+    assert_eq!(tracked.volatility[0], untracked.volatility[0]);
+    assert!(tracked.volatility[0].is_some());
+    // Twelve daily candles cannot cover a 30-day window
+    assert_eq!(tracked.volatility[1], None);
+    assert_eq!(untracked.volatility[1], None);
+    // The 90-day column is the headline cached value, computed over the whole fetched series
+    assert!(tracked.volatility[2].is_some());
+}
+
+#[tokio::test]
+async fn get_heatmap_rejects_uncached_windows() {
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/heatmap?windows=7,14")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -4,6 +4,8 @@ use axum::{
     Router,
 };
 use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::math::Interval;
+use historical_volatility_api::presets::Preset;
 use historical_volatility_api::routes::historical_volatility::HistoricalVolatilityResponse;
 use historical_volatility_api::routes::register_routes;
 use historical_volatility_api::state::AppState;
@@ -11,7 +13,11 @@ use historical_volatility_api::background::volatility_cache::VolatilityCache;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use tower::ServiceExt;
-use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+use chrono::{Duration, NaiveTime, Utc};
+use wiremock::{
+    matchers::{method, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
 
 //
 // ----------- Global Setup -----------
@@ -59,7 +65,7 @@ async fn setup_mock_server(json_response: serde_json::Value) -> MockServer {
 
 #[tokio::test]
 async fn get_historical_volatility_returns_positive_value_with_mock() {
-    let _ = *INIT;
+    Lazy::force(&INIT);
 
     let fake_response = serde_json::json!({
         "success": true,
@@ -77,7 +83,8 @@ async fn get_historical_volatility_returns_positive_value_with_mock() {
     let config = AppConfig {
         birdeye_api_key: "dummy-key".to_string(),
         birdeye_base_url: mock_server.uri(),
-        app_server_port: 8080
+        app_server_port: 8080,
+        ..AppConfig::default()
     };
     
     let volatility_cache = VolatilityCache::new(config.clone());
@@ -99,6 +106,11 @@ async fn get_historical_volatility_returns_positive_value_with_mock() {
         historical_volatility_response.historical_volatility > 0.0,
         "Volatility should be > 0"
     );
+    assert_eq!(historical_volatility_response.annualization_days, 365);
+    assert_eq!(historical_volatility_response.data_points, 3);
+    assert_eq!(historical_volatility_response.window_days, 90);
+    assert_eq!(historical_volatility_response.interval, Interval::OneDay);
+    assert_eq!(historical_volatility_response.provider, "birdeye");
     assert!(
         headers
             .get("content-type")
@@ -110,13 +122,299 @@ async fn get_historical_volatility_returns_positive_value_with_mock() {
     );
 }
 
+#[tokio::test]
+async fn get_historical_volatility_includes_extended_stats_when_requested() {
+    Lazy::force(&INIT);
+
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1735689600, "value": 100.0 },
+                { "unixTime": 1735776000, "value": 101.0 },
+                { "unixTime": 1735862400, "value": 102.0 },
+                { "unixTime": 1735948800, "value": 80.0 }
+            ]
+        }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress=So11111111111111111111111111111111111111112&extendedStats=true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    let stats = historical_volatility_response
+        .extended_stats
+        .expect("Should include extended stats");
+    assert!(stats.skewness < 0.0, "A crash should skew returns negatively");
+    assert!((stats.min_return - (80.0f64 / 102.0).ln()).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn get_historical_volatility_includes_prices_when_requested() {
+    Lazy::force(&INIT);
+
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1700000000, "value": 100.0 },
+                { "unixTime": 1700086400, "value": 101.0 },
+                { "unixTime": 1700172800, "value": 98.0 }
+            ]
+        }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    for (query, expects_prices) in [("&includePrices=true", true), ("", false)] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112{}", query))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("Should receive a response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse JSON");
+
+        match historical_volatility_response.prices {
+            Some(prices) => {
+                assert!(expects_prices, "Prices should only be included on request");
+                let values: Vec<f64> = prices.iter().map(|point| point.value).collect();
+                assert_eq!(values, vec![100.0, 101.0, 98.0]);
+                assert_eq!(prices[0].unix_time, 1700000000);
+            }
+            None => assert!(!expects_prices, "Should include prices"),
+        }
+    }
+}
+
+/// Sends `uri` with an optional admin key, returning the response status.
+async fn send_status(app: &Router, uri: &str, admin_key: Option<&str>) -> StatusCode {
+    let mut request = Request::builder().uri(uri);
+    if let Some(key) = admin_key {
+        request = request.header("x-admin-key", key);
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response")
+        .status()
+}
+
+#[tokio::test]
+async fn get_historical_volatility_force_refresh_bypasses_caches() {
+    Lazy::force(&INIT);
+
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1735689600, "o": 100.0, "h": 110.0, "l": 100.0, "c": 105.0, "v": 1.0, "value": 100.0 },
+                { "unixTime": 1735776000, "o": 105.0, "h": 115.5, "l": 105.0, "c": 110.0, "v": 1.0, "value": 105.0 },
+                { "unixTime": 1735862400, "o": 110.0, "h": 112.0, "l": 95.0, "c": 95.0, "v": 1.0, "value": 95.0 }
+            ]
+        }
+    }))
+    .await;
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        birdeye_ohlcv_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+    let provider_calls = || async { mock_server.received_requests().await.unwrap_or_default().len() };
+
+    for (uri, expected_calls) in [
+        // Cached path: the first request fills the volatility cache, the second reads it
+        ("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112", 1),
+        ("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112", 1),
+        ("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&forceRefresh=true", 2),
+        // On-demand path: the price cache answers repeats unless forced
+        ("/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson", 3),
+        ("/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson", 3),
+        ("/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson&forceRefresh=true", 4),
+    ] {
+        assert_eq!(send_status(&app, uri, None).await, StatusCode::OK, "{}", uri);
+        assert_eq!(provider_calls().await, expected_calls, "{}", uri);
+    }
+}
+
+#[cfg(feature = "admin")]
+#[tokio::test]
+async fn get_historical_volatility_force_refresh_can_require_admin_key() {
+    Lazy::force(&INIT);
+
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1700000000, "value": 100.0 },
+                { "unixTime": 1700086400, "value": 105.0 }
+            ]
+        }
+    }))
+    .await;
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        admin_api_key: Some("admin-secret".to_string()),
+        force_refresh_requires_admin: true,
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let uri = "/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&forceRefresh=true";
+    assert_eq!(send_status(&app, uri, None).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(send_status(&app, uri, Some("wrong")).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(send_status(&app, uri, Some("admin-secret")).await, StatusCode::OK);
+    // Requests not forcing a refresh need no key
+    let uri = "/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112";
+    assert_eq!(send_status(&app, uri, None).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn get_historical_volatility_applies_preset_with_explicit_overrides() {
+    Lazy::force(&INIT);
+
+    // Inside the preset's 30-day window
+    let day = |days_ago: i64| (Utc::now() - Duration::days(days_ago)).timestamp();
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": day(3), "value": 100.0 },
+                { "unixTime": day(2), "value": 104.0 },
+                { "unixTime": day(1), "value": 99.0 }
+            ]
+        }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState::new(config, volatility_cache);
+    state
+        .presets
+        .upsert(
+            "riskDaily".to_string(),
+            Preset {
+                annualization_days: Some(252),
+                days: Some(30),
+                extended_stats: Some(true),
+                ..Preset::default()
+            },
+        )
+        .await;
+
+    let response = register_routes(state)
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&preset=riskDaily&extendedStats=false")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(historical_volatility_response.annualization_days, 252);
+    assert!(
+        historical_volatility_response.extended_stats.is_none(),
+        "An explicit parameter should override the preset"
+    );
+}
+
 //
 // ----------- Sad Path Tests -----------
 //
 
+#[tokio::test]
+async fn get_historical_volatility_unknown_preset_returns_400() {
+    Lazy::force(&INIT);
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&preset=optionsDesk")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("should have gotten a response");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error_response: ErrorResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse error response JSON");
+    assert_eq!(error_response.message, "Unknown preset 'optionsDesk'.");
+}
+
 #[tokio::test]
 async fn get_historical_volatility_missing_api_key_returns_500() {
-    let _ = *INIT;
+    Lazy::force(&INIT);
 
     let fake_response = serde_json::json!({
         "success": false,
@@ -128,7 +426,8 @@ async fn get_historical_volatility_missing_api_key_returns_500() {
     let config = AppConfig {
         birdeye_api_key: "".to_string(),
         birdeye_base_url: mock_server.uri(),
-        app_server_port: 8080
+        app_server_port: 8080,
+        ..AppConfig::default()
     };
     
     let volatility_cache = VolatilityCache::new(config.clone());
@@ -152,12 +451,13 @@ async fn get_historical_volatility_missing_api_key_returns_500() {
 
 #[tokio::test]
 async fn get_historical_volatility_invalid_query_returns_400() {
-    let _ = *INIT;
+    Lazy::force(&INIT);
 
     let config = AppConfig {
         birdeye_api_key: "dummy".to_string(),
         birdeye_base_url: "https://public-api.birdeye.so/token_price/history".to_string(),
-        app_server_port: 8080
+        app_server_port: 8080,
+        ..AppConfig::default()
     };
     
     let volatility_cache = VolatilityCache::new(config.clone());
@@ -188,7 +488,534 @@ async fn get_historical_volatility_invalid_query_returns_400() {
 
     assert_eq!(
         error_response.message,
-        "Failed to deserialize query string: missing field `fromDate`"
+        "Failed to deserialize query string: missing field `tokenAddress`"
     );
 }
 
+#[tokio::test]
+async fn get_historical_volatility_rejects_invalid_date_ranges() {
+    Lazy::force(&INIT);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        max_date_range_days: 365,
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    for (from_date, to_date, expected) in [
+        ("2025-03-31", "2024-12-31", "fromDate cannot be after toDate."),
+        ("2024-12-31", "2099-01-01", "toDate cannot be in the future."),
+        ("2023-12-31", "2025-03-31", "Date range cannot exceed 365 days."),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/historicalVolatility?fromDate={}&toDate={}&tokenAddress=So11111111111111111111111111111111111111112",
+                        from_date, to_date
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("should have gotten a response");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_response: ErrorResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse error response JSON");
+        assert_eq!(error_response.message, expected);
+    }
+}
+
+#[tokio::test]
+async fn get_historical_volatility_accepts_days_instead_of_dates() {
+    Lazy::force(&INIT);
+
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1700000000, "o": 100.0, "h": 110.0, "l": 100.0, "c": 105.0, "v": 1.0 },
+                { "unixTime": 1700086400, "o": 105.0, "h": 115.5, "l": 105.0, "c": 110.0, "v": 1.0 }
+            ]
+        }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_ohlcv_url: mock_server.uri(),
+        max_date_range_days: 365,
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |params: &str| {
+        Request::builder()
+            .uri(format!(
+                "/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson&{}",
+                params
+            ))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(request("days=30"))
+        .await
+        .expect("should have gotten a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for (params, expected) in [
+        ("days=30&fromDate=2024-12-31", "Pass either days or fromDate and toDate, not both."),
+        ("days=0", "days must be greater than 0."),
+        ("days=400", "Date range cannot exceed 365 days."),
+        ("days=4000000000", "Date range cannot exceed 365 days."),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(request(params))
+            .await
+            .expect("should have gotten a response");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_response: ErrorResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse error response JSON");
+        assert_eq!(error_response.message, expected);
+    }
+}
+
+#[tokio::test]
+async fn get_historical_volatility_serves_cached_window_matching_days() {
+    Lazy::force(&INIT);
+
+    // 90 daily closes, calm except for a swinging last week
+    let now = Utc::now();
+    let items: Vec<serde_json::Value> = (0..90)
+        .map(|i| {
+            let swing = if i >= 82 { 10.0 } else { 1.0 };
+            serde_json::json!({
+                "unixTime": (now - Duration::days(89 - i) - Duration::hours(1)).timestamp(),
+                "value": if i % 2 == 0 { 100.0 } else { 100.0 + swing },
+            })
+        })
+        .collect();
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": { "items": items }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let mut responses = Vec::new();
+    for days in [7, 90] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&days={}",
+                        days
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse JSON");
+        responses.push(historical_volatility_response);
+    }
+
+    assert_eq!(responses[0].window_days, 7);
+    assert_eq!(responses[0].data_points, 8);
+    assert_eq!(responses[1].window_days, 90);
+    assert_eq!(responses[1].data_points, 90);
+    assert!(
+        responses[0].historical_volatility > responses[1].historical_volatility,
+        "The 7-day window only covers the swinging week"
+    );
+    // Both windows come from the one cached series
+    assert_eq!(mock_server.received_requests().await.unwrap_or_default().len(), 1);
+}
+
+#[tokio::test]
+async fn get_historical_volatility_malformed_address_returns_400_without_calling_birdeye() {
+    Lazy::force(&INIT);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    for (token_address, expected) in [
+        (
+            "So1111111111111111111111111111111111111111O",
+            "'So1111111111111111111111111111111111111111O' is not a valid Solana address: it contains characters outside the base58 alphabet (0, O, I and l are never used).",
+        ),
+        (
+            "So111111111111111111111111111111",
+            "'So111111111111111111111111111111' is not a valid Solana address: it decodes to 24 bytes instead of 32.",
+        ),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!(
+                        "/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress={}",
+                        token_address
+                    ))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .expect("should have gotten a response");
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let error_response: ErrorResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse error response JSON");
+        assert_eq!(error_response.message, expected);
+    }
+}
+
+#[tokio::test]
+async fn get_historical_volatility_with_parkinson_estimator_uses_ohlcv() {
+    Lazy::force(&INIT);
+
+    let fake_response = serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1700000000, "o": 100.0, "h": 110.0, "l": 100.0, "c": 105.0, "v": 1.0 },
+                { "unixTime": 1700086400, "o": 105.0, "h": 115.5, "l": 105.0, "c": 110.0, "v": 1.0 }
+            ]
+        }
+    });
+
+    let mock_server = setup_mock_server(fake_response).await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_ohlcv_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState::new(config, volatility_cache);
+
+    let app = register_routes(state);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?fromDate=2024-12-31&toDate=2025-03-31&tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert!((historical_volatility_response.historical_volatility - 109.4).abs() < 0.5);
+    // Computed on demand over the requested range rather than the cached window
+    assert_eq!(historical_volatility_response.data_points, 2);
+    assert_eq!(historical_volatility_response.window_days, 90);
+    assert_eq!(historical_volatility_response.provider, "birdeye");
+    assert!(historical_volatility_response.last_updated <= Utc::now());
+}
+
+#[tokio::test]
+async fn get_historical_volatility_defaults_to_trailing_90_days_ending_yesterday() {
+    Lazy::force(&INIT);
+
+    let yesterday = (Utc::now().date_naive() - Duration::days(1))
+        .and_time(NaiveTime::MIN)
+        .and_utc();
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("time_to", yesterday.timestamp().to_string()))
+        .and(query_param(
+            "time_from",
+            (yesterday - Duration::days(90)).timestamp().to_string(),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "o": 100.0, "h": 110.0, "l": 100.0, "c": 105.0, "v": 1.0 },
+                    { "unixTime": 1700086400, "o": 105.0, "h": 115.5, "l": 105.0, "c": 110.0, "v": 1.0 }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_ohlcv_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState::new(config, volatility_cache);
+
+    let app = register_routes(state);
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&estimator=parkinson")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn get_historical_volatility_computes_explicit_range_instead_of_cached_window() {
+    Lazy::force(&INIT);
+
+    let from_date = chrono::DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap();
+    let to_date = chrono::DateTime::parse_from_rfc3339("2025-01-15T00:00:00Z").unwrap();
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("time_from", from_date.timestamp().to_string()))
+        .and(query_param("time_to", to_date.timestamp().to_string()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1735689600, "value": 100.0 },
+                    { "unixTime": 1735776000, "value": 104.0 },
+                    { "unixTime": 1735862400, "value": 99.0 },
+                    { "unixTime": 1735948800, "value": 101.0 }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    // The default estimator is the cache's, but the cached 90-day window isn't the range asked for
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility?fromDate=2025-01-01&toDate=2025-01-15&tokenAddress=So11111111111111111111111111111111111111112")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(historical_volatility_response.data_points, 4);
+    assert_eq!(historical_volatility_response.window_days, 14);
+}
+
+#[tokio::test]
+async fn get_historical_volatility_serves_iso_range_from_cache_only_when_it_is_the_cached_window() {
+    Lazy::force(&INIT);
+
+    let now = Utc::now();
+    let mock_server = setup_mock_server(serde_json::json!({
+        "success": true,
+        "data": {
+            "items": [
+                { "unixTime": 1736683200, "value": 100.0 },
+                { "unixTime": 1736769600, "value": 104.0 },
+                { "unixTime": 1736856000, "value": 99.0 },
+                { "unixTime": (now - Duration::days(2)).timestamp(), "value": 100.0 },
+                { "unixTime": (now - Duration::days(1)).timestamp(), "value": 102.0 }
+            ]
+        }
+    }))
+    .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+    let send = |params: String| {
+        app.clone().oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/historicalVolatility?tokenAddress=So11111111111111111111111111111111111111112&{}",
+                    params
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+    };
+    let provider_calls = || async { mock_server.received_requests().await.unwrap_or_default().len() };
+
+    // Fill the cache
+    let response = send(String::new()).await.expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(provider_calls().await, 1);
+
+    // The cached window, to the second, is served from the cache
+    let to_date = now - Duration::minutes(1);
+    let from_date = to_date - Duration::days(90);
+    let response = send(format!(
+        "fromDate={}&toDate={}",
+        from_date.format("%Y-%m-%dT%H:%M:%SZ"),
+        to_date.format("%Y-%m-%dT%H:%M:%SZ")
+    ))
+    .await
+    .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(provider_calls().await, 1);
+
+    // Any other ISO range is computed over the times given
+    let response = send("fromDate=2025-01-12T00:00:00Z&toDate=2025-01-15T12:00:00Z".to_string())
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(provider_calls().await, 2);
+    let historical_volatility_response: HistoricalVolatilityResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(historical_volatility_response.window_days, 4);
+    let requests = mock_server.received_requests().await.unwrap_or_default();
+    let query = requests[1].url.query().unwrap_or_default().to_string();
+    assert!(query.contains("time_from=1736640000"), "{}", query);
+    assert!(query.contains("time_to=1736942400"), "{}", query);
+}
+
+#[tokio::test]
+async fn explain_reports_cache_miss_without_calling_birdeye() {
+    Lazy::force(&INIT);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock_server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: mock_server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility/explain?tokenAddress=So11111111111111111111111111111111111111112")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    let explain: serde_json::Value = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(explain["validation"]["valid"], true);
+    assert_eq!(explain["cache"]["cache"], "volatilityCache");
+    assert_eq!(explain["cache"]["hit"], false);
+    assert_eq!(explain["provider"]["wouldCall"], true);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/historicalVolatility/explain?fromDate=2024-12-31")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let explain: serde_json::Value = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(explain["validation"]["valid"], false);
+}
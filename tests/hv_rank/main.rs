@@ -0,0 +1,63 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::hv_rank::HvRankResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn get_hv_rank_ranks_latest_window_with_mock() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 101.0 },
+                    { "unixTime": 1700172800, "value": 100.0 },
+                    { "unixTime": 1700259200, "value": 110.0 },
+                    { "unixTime": 1700345600, "value": 100.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/hvRank?tokenAddress=So11111111111111111111111111111111111111112&windowDays=2&lookbackDays=3")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let hv_rank: HvRankResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(hv_rank.data_points, 3);
+    assert_eq!(hv_rank.current_volatility, hv_rank.max_volatility);
+    assert_eq!(hv_rank.hv_rank, Some(100.0));
+    assert_eq!(hv_rank.hv_percentile, 100.0);
+}
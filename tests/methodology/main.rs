@@ -0,0 +1,78 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::presets::{ChangeKind, Preset};
+use historical_volatility_api::routes::methodology::ChangeLogResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+
+fn app_state() -> AppState {
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    AppState::new(config, volatility_cache)
+}
+
+async fn methodology_version(state: &AppState, uri: &str) -> String {
+    let response = register_routes(state.clone())
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+
+    response
+        .headers()
+        .get("x-methodology-version")
+        .expect("Every response should carry a methodology version")
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn change_log_tracks_preset_versions() {
+    let state = app_state();
+    let base = methodology_version(&state, "/presets").await;
+
+    state
+        .presets
+        .upsert(
+            "riskDaily".to_string(),
+            Preset {
+                annualization_days: Some(252),
+                ..Preset::default()
+            },
+        )
+        .await;
+    let risk_daily = methodology_version(&state, "/presets?preset=riskDaily").await;
+    assert_ne!(risk_daily, base);
+    assert_eq!(methodology_version(&state, "/presets").await, base);
+
+    let response = register_routes(state)
+        .oneshot(
+            Request::builder()
+                .uri("/methodology/changelog?preset=riskDaily")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let change_log: ChangeLogResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(change_log.methodology_version, base);
+    assert_eq!(change_log.presets["riskDaily"], risk_daily);
+    assert_eq!(change_log.changes.len(), 1);
+    assert_eq!(change_log.changes[0].change, ChangeKind::Defined);
+    assert_eq!(change_log.changes[0].methodology_version, Some(risk_daily));
+}
@@ -0,0 +1,90 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::option_price::OptionPriceResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+async fn app_with_mock(server: &MockServer) -> Router {
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 },
+                    { "unixTime": 1700259200, "value": 100.0 }
+                ]
+            }
+        })))
+        .mount(server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    register_routes(AppState::new(config, volatility_cache))
+}
+
+#[tokio::test]
+async fn get_option_price_prices_call_with_historical_volatility() {
+    let server = MockServer::start().await;
+    let app = app_with_mock(&server).await;
+    let expiry = (Utc::now() + Duration::days(30)).format("%Y-%m-%d");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!(
+                    "/optionPrice?tokenAddress=So11111111111111111111111111111111111111112&strike=100&expiry={}&optionType=call",
+                    expiry
+                ))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let option_price: OptionPriceResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(option_price.spot, 100.0);
+    assert!(option_price.historical_volatility > 0.0);
+    assert!(option_price.quote.price > 0.0);
+    assert!((0.0..=1.0).contains(&option_price.quote.delta));
+}
+
+#[tokio::test]
+async fn get_option_price_rejects_past_expiry() {
+    let server = MockServer::start().await;
+    let app = app_with_mock(&server).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/optionPrice?tokenAddress=So11111111111111111111111111111111111111112&strike=100&expiry=2020-01-01&optionType=put")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,598 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::prices::PricesResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn send_prices_request(app: Router) -> axum::response::Response {
+    app.oneshot(
+        Request::builder()
+            .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .expect("Should receive a response")
+}
+
+#[tokio::test]
+async fn get_prices_serves_repeat_requests_from_cache() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 100.0 },
+                    { "unixTime": 1700006400, "value": 101.0 }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    for _ in 0..2 {
+        let response = send_prices_request(app.clone()).await;
+        let status = response.status();
+        let prices_response: PricesResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse JSON");
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(prices_response.prices.len(), 2);
+        assert_eq!(prices_response.prices[1].value, 101.0);
+    }
+}
+
+#[tokio::test]
+async fn get_prices_renders_timestamps_in_requested_format() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": [{ "unixTime": 1699920000, "value": 100.0 }] }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112&timestampFormat=rfc3339")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(body["prices"][0]["unixTime"], "2023-11-14T00:00:00Z");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112&timestampFormat=iso")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_prices_queries_the_requested_chain() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(header("x-chain", "ethereum"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 1.0 },
+                    { "unixTime": 1700006400, "value": 1.01 }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&chain=ethereum&tokenAddress=0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A Solana mint is not an Ethereum address, and unknown chains are rejected
+    for uri in [
+        "/prices?fromDate=2023-11-14&toDate=2023-11-16&chain=ethereum&tokenAddress=So11111111111111111111111111111111111111112",
+        "/prices?fromDate=2023-11-14&toDate=2023-11-16&chain=dogechain&tokenAddress=So11111111111111111111111111111111111111112",
+    ] {
+        let response = app.clone().oneshot(request(uri)).await.expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn get_prices_passes_pair_address_type_to_birdeye() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("address_type", "pair"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 0.5 },
+                    { "unixTime": 1700006400, "value": 0.52 }
+                ]
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&addressType=pair&tokenAddress=Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&addressType=pool&tokenAddress=Czfq3xZZDmsdGdUyrNLtRhGc47cXcZtLG4crryfu44zE"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_prices_accepts_unix_timestamps_as_dates() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("time_from", "1699950000"))
+        .and(query_param("time_to", "1700100000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": [{ "unixTime": 1700006400, "value": 101.0 }] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=1699950000&toDate=1700100000&tokenAddress=So11111111111111111111111111111111111111112"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Millisecond timestamps are rejected rather than read as dates far in the future
+    let response = app
+        .oneshot(request("/prices?fromDate=1699950000000&toDate=1700100000000&tokenAddress=So11111111111111111111111111111111111111112"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_prices_accepts_iso_datetimes_as_dates() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(query_param("type", "1H"))
+        .and(query_param("time_from", "1699970400"))
+        .and(query_param("time_to", "1699992000"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": [{ "unixTime": 1699970400, "value": 101.0 }] }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14T14:00:00Z&toDate=2023-11-14T22:00:00%2B02:00&interval=1H&tokenAddress=So11111111111111111111111111111111111111112")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn get_prices_resolves_token_symbols() {
+    let server = MockServer::start().await;
+    for address in [
+        "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN",
+        "MEWmint1111111111111111111111111111111111111",
+    ] {
+        Mock::given(method("GET"))
+            .and(path("/history"))
+            .and(query_param("address", address))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": {
+                    "items": [
+                        { "unixTime": 1699920000, "value": 1.0 },
+                        { "unixTime": 1700006400, "value": 1.1 }
+                    ]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    let search_result = |tokens: serde_json::Value| {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": { "items": [{ "type": "token", "result": tokens }] }
+        }))
+    };
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("keyword", "MEW"))
+        .respond_with(search_result(serde_json::json!([
+            { "address": "MEWmint1111111111111111111111111111111111111", "name": "cat in a dogs world", "symbol": "MEW" },
+            { "address": "MEWtoo11111111111111111111111111111111111111", "name": "MEW wrapped", "symbol": "wMEW" }
+        ])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("keyword", "DUP"))
+        .respond_with(search_result(serde_json::json!([
+            { "address": "DUPone11111111111111111111111111111111111111", "name": "Dup One", "symbol": "DUP" },
+            { "address": "DUPtwo11111111111111111111111111111111111111", "name": "Dup Two", "symbol": "DUP" }
+        ])))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: format!("{}/history", server.uri()),
+        birdeye_search_url: format!("{}/search", server.uri()),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    // JUP comes from the bundled list; MEW is searched once, then remembered
+    for uri in [
+        "/prices?fromDate=2023-11-14&toDate=2023-11-16&symbol=jup",
+        "/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=MEW",
+        "/prices?fromDate=2023-11-14&toDate=2023-11-16&symbol=MEW",
+    ] {
+        let response = app.clone().oneshot(request(uri)).await.expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+    }
+
+    let response = app
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=DUP"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    let message = String::from_utf8_lossy(&body);
+    assert!(message.contains("DUPone11111111111111111111111111111111111111 (Dup One)"));
+    assert!(message.contains("DUPtwo11111111111111111111111111111111111111 (Dup Two)"));
+}
+
+#[tokio::test]
+async fn get_prices_renders_floats_as_strings_when_requested() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 0.000012345678901234567 },
+                    { "unixTime": 1700006400, "value": 101.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112&numbersAsStrings=true"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    let small = body["prices"][0]["value"].as_str().expect("value should be a string");
+    assert_eq!(small.parse::<f64>(), Ok(0.000012345678901234567));
+    assert_eq!(body["prices"][1]["value"], "101.0");
+    assert_eq!(body["prices"][0]["unixTime"], 1699920000);
+
+    let response = app
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112&numbersAsStrings=yes"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_prices_renders_csv_when_requested() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 100.0 },
+                    { "unixTime": 1700006400, "value": 101.5 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+    let uri = "/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112";
+    let expected = "tokenAddress,interval,unixTime,value\n\
+        So11111111111111111111111111111111111111112,1D,1699920000,100.0\n\
+        So11111111111111111111111111111111111111112,1D,1700006400,101.5\n";
+
+    for request in [
+        Request::builder().uri(format!("{}&format=csv", uri)).body(Body::empty()).unwrap(),
+        Request::builder()
+            .uri(uri)
+            .header("Accept", "text/csv")
+            .body(Body::empty())
+            .unwrap(),
+    ] {
+        let response = app.clone().oneshot(request).await.expect("Should receive a response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/csv; charset=utf-8");
+        assert_eq!(response.headers()["vary"], "Accept");
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body");
+        assert_eq!(std::str::from_utf8(&body).unwrap(), expected);
+    }
+
+    let response = app
+        .oneshot(Request::builder().uri(format!("{}&format=xml", uri)).body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn get_prices_streams_ndjson_in_chunks() {
+    let server = MockServer::start().await;
+    // Daily prices for whatever range is asked for
+    Mock::given(method("GET"))
+        .respond_with(|request: &wiremock::Request| {
+            let param = |name: &str| {
+                request
+                    .url
+                    .query_pairs()
+                    .find(|(key, _)| key == name)
+                    .and_then(|(_, value)| value.parse::<i64>().ok())
+                    .unwrap_or_default()
+            };
+            let from = param("time_from").div_euclid(86_400) * 86_400;
+            let items: Vec<_> = (from..=param("time_to"))
+                .step_by(86_400)
+                .map(|unix_time| serde_json::json!({ "unixTime": unix_time, "value": 100.0 }))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "data": { "items": items }
+            }))
+        })
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        max_date_range_days: 2000,
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let price_cache = volatility_cache.price_cache().clone();
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2021-01-01&toDate=2023-12-31&tokenAddress=So11111111111111111111111111111111111111112&timestampFormat=date")
+                .header("Accept", "application/x-ndjson")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    let lines: Vec<serde_json::Value> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line should be JSON"))
+        .collect();
+
+    // 2021 through 2023, each day once, in order
+    assert_eq!(lines.len(), 1095);
+    assert_eq!(lines[0]["unixTime"], "2021-01-01");
+    assert_eq!(lines[1094]["unixTime"], "2023-12-31");
+    assert!(lines.windows(2).all(|pair| pair[0]["unixTime"].as_str() < pair[1]["unixTime"].as_str()));
+    let requests = server.received_requests().await.expect("requests should be recorded");
+    assert!(requests.len() >= 3, "expected a request per chunk, got {}", requests.len());
+    // Streamed chunks don't pile up in the price cache
+    assert_eq!(price_cache.entry_count().await, 0);
+}
+
+#[tokio::test]
+async fn get_prices_encodes_msgpack_when_accepted() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 100.0 },
+                    { "unixTime": 1700006400, "value": 101.5 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+    let request = |uri: &str| {
+        Request::builder()
+            .uri(uri)
+            .header("Accept", "application/msgpack")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/msgpack");
+    assert_eq!(response.headers()["vary"], "Accept");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    let prices: PricesResponse = rmp_serde::from_slice(&body).expect("body should be MessagePack");
+    assert_eq!(prices.prices.len(), 2);
+    assert_eq!(prices.prices[1].value, 101.5);
+
+    // Errors are encoded alike
+    let response = app
+        .clone()
+        .oneshot(request("/prices?fromDate=2023-11-16&toDate=2023-11-14&tokenAddress=So11111111111111111111111111111111111111112"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-type"], "application/msgpack");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    let error: serde_json::Value = rmp_serde::from_slice(&body).expect("body should be MessagePack");
+    assert_eq!(error["error"], "Bad Request");
+
+    // JSON answers vary on Accept too, since the same URL can be served as MessagePack
+    let response = app
+        .oneshot(Request::builder().uri("/healthCheck").body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert!(response.headers().get_all("vary").iter().any(|value| value == "Accept"));
+}
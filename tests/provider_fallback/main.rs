@@ -0,0 +1,187 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::provider::ProviderKind;
+use historical_volatility_api::routes::prices::PricesResponse;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::status::StatusResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path, path_regex, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn get_prices_falls_back_to_coingecko_when_birdeye_fails() {
+    let birdeye = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": false,
+            "message": "Too many requests"
+        })))
+        .mount(&birdeye)
+        .await;
+
+    let coingecko = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path_regex(r"^/coins/solana/contract/.+/market_chart/range$"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "prices": [
+                [1699920000000.0, 100.0],
+                [1700006400000.0, 101.0]
+            ]
+        })))
+        .expect(1)
+        .mount(&coingecko)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: birdeye.uri(),
+        fallback_provider: Some(ProviderKind::CoinGecko),
+        coingecko_base_url: coingecko.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-price-provider"], "coingecko");
+    let prices: PricesResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(prices.prices.len(), 2);
+    assert_eq!(prices.prices[1].value, 101.0);
+}
+
+#[tokio::test]
+async fn get_prices_falls_back_to_pyth_benchmarks() {
+    let birdeye = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&birdeye)
+        .await;
+
+    let pyth = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/shims/tradingview/history"))
+        .and(query_param("symbol", "Crypto.SOL/USD"))
+        .and(query_param("resolution", "1D"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "s": "ok",
+            "t": [1699920000, 1700006400],
+            "o": [99.0, 100.0],
+            "h": [101.0, 102.0],
+            "l": [98.0, 99.5],
+            "c": [100.0, 101.0],
+            "v": [0.0, 0.0]
+        })))
+        .expect(1)
+        .mount(&pyth)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: birdeye.uri(),
+        fallback_provider: Some(ProviderKind::Pyth),
+        pyth_benchmarks_url: pyth.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-16&tokenAddress=So11111111111111111111111111111111111111112")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-price-provider"], "pyth");
+    let prices: PricesResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+    assert_eq!(prices.prices.len(), 2);
+    assert_eq!(prices.prices[1].value, 101.0);
+}
+
+#[tokio::test]
+async fn provider_chain_counters_are_reported_on_status() {
+    let birdeye = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1699920000, "value": 100.0 },
+                    { "unixTime": 1700006400, "value": 101.0 }
+                ]
+            }
+        })))
+        .mount(&birdeye)
+        .await;
+
+    // No Pyth feed is registered for this mint, so Pyth fails before making a request
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: birdeye.uri(),
+        providers: Some(vec![ProviderKind::Pyth, ProviderKind::Birdeye]),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState::new(config, volatility_cache);
+
+    let response = register_routes(state.clone())
+        .oneshot(
+            Request::builder()
+                .uri("/prices?fromDate=2023-11-14&toDate=2023-11-15&tokenAddress=unlistedMint")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-price-provider"], "birdeye");
+
+    let status = register_routes(state)
+        .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+    let status: StatusResponse = serde_json::from_slice(
+        &to_bytes(status.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    let providers: Vec<(&str, u64, u64)> = status
+        .providers
+        .iter()
+        .map(|health| (health.provider.as_str(), health.successes, health.errors))
+        .collect();
+    assert_eq!(providers, vec![("pyth", 0, 1), ("birdeye", 1, 0)]);
+}
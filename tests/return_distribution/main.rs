@@ -0,0 +1,67 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::return_distribution::ReturnDistributionResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn get_return_distribution_buckets_log_returns_with_mock() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 },
+                    { "unixTime": 1700259200, "value": 102.0 },
+                    { "unixTime": 1700345600, "value": 95.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/returnDistribution?tokenAddress=So11111111111111111111111111111111111111112&bins=2")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let distribution: ReturnDistributionResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(distribution.data_points, 4);
+    assert_eq!(distribution.histogram.len(), 2);
+    assert_eq!(
+        distribution.histogram.iter().map(|bin| bin.count).sum::<usize>(),
+        4
+    );
+    assert!((distribution.normal.mean - (95.0_f64 / 100.0).ln() / 4.0).abs() < 1e-12);
+    assert!(distribution.normal.std_dev > 0.0);
+}
@@ -0,0 +1,65 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::risk_adjusted_returns::RiskAdjustedReturnsResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn get_risk_adjusted_returns_uses_requested_risk_free_rate_with_mock() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 },
+                    { "unixTime": 1700259200, "value": 102.0 },
+                    { "unixTime": 1700345600, "value": 95.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/riskAdjustedReturns?tokenAddress=So11111111111111111111111111111111111111112&riskFreeRate=0.05")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let ratios: RiskAdjustedReturnsResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ratios.data_points, 4);
+    assert_eq!(ratios.risk_free_rate, 0.05);
+    assert!(ratios.annualized_volatility > 0.0);
+    let sharpe = ratios.sharpe.expect("Should have a Sharpe ratio");
+    let sortino = ratios.sortino.expect("Should have a Sortino ratio");
+    assert_eq!(sharpe.signum(), sortino.signum());
+}
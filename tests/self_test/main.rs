@@ -0,0 +1,7 @@
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::self_test;
+
+#[tokio::test]
+async fn self_test_passes_on_every_route() {
+    assert_eq!(self_test::run(AppConfig::default()).await, 0);
+}
@@ -0,0 +1,111 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use historical_volatility_api::background::clock_skew::check_clock_skew;
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::provider::ProviderEnvironment;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::status::{OverallStatus, StatusResponse};
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn status_reports_operational_on_fresh_start() {
+    let config = AppConfig {
+        birdeye_api_key: "DUMMY_KEY".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+        .await
+        .expect("Failed to get a response");
+
+    let status = response.status();
+    let status_response: StatusResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes"),
+    )
+    .expect("Failed to deserialize JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(status_response.status, OverallStatus::Operational);
+    assert_eq!(status_response.environment, ProviderEnvironment::Production);
+    assert_eq!(status_response.health.upstream_requests, 0);
+    assert!(status_response.health.last_incidents.is_empty());
+}
+
+#[tokio::test]
+async fn status_reports_degraded_when_host_clock_is_skewed() {
+    let server = MockServer::start().await;
+    let server_time = Utc::now() - Duration::hours(1);
+    Mock::given(method("HEAD"))
+        .respond_with(
+            ResponseTemplate::new(401).insert_header("date", server_time.to_rfc2822().as_str()),
+        )
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "DUMMY_KEY".to_string(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let state = AppState::new(config, volatility_cache);
+    check_clock_skew(&reqwest::Client::new(), &server.uri(), &state.monitor, 30).await;
+
+    let response = register_routes(state)
+        .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+        .await
+        .expect("Failed to get a response");
+
+    let skew_header: i64 = response.headers()["x-clock-skew-seconds"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((skew_header - 3600).abs() <= 2);
+
+    let status_response: StatusResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes"),
+    )
+    .expect("Failed to deserialize JSON");
+    assert_eq!(status_response.status, OverallStatus::Degraded);
+    assert!(status_response.health.clock_skewed);
+    assert_eq!(status_response.health.last_incidents[0].component, "clock");
+}
+
+#[tokio::test]
+async fn sandbox_responses_are_labeled() {
+    let mut config = AppConfig {
+        birdeye_api_key: "DUMMY_KEY".to_string(),
+        provider_environment: ProviderEnvironment::Sandbox,
+        ..AppConfig::default()
+    };
+    config.apply_provider_environment();
+    assert_eq!(config.pyth_hermes_url, "https://hermes-beta.pyth.network");
+
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let response = register_routes(AppState::new(config, volatility_cache))
+        .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+        .await
+        .expect("Failed to get a response");
+
+    assert_eq!(response.headers()["x-provider-environment"], "sandbox");
+    let status_response: StatusResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes"),
+    )
+    .expect("Failed to deserialize JSON");
+    assert_eq!(status_response.environment, ProviderEnvironment::Sandbox);
+}
@@ -0,0 +1,81 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::token_metadata::TokenMetadataResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn get_token_metadata_caches_birdeye_metadata() {
+    let jup = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/metadata"))
+        .and(query_param("address", jup))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "address": jup,
+                "name": "Jupiter",
+                "symbol": "JUP",
+                "decimals": 6,
+                "logo_uri": "https://static.jup.ag/jup/icon.png"
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/metadata"))
+        .and(query_param("address", "unknownMint1111111111111111111111111111111"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": null
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_token_metadata_url: format!("{}/metadata", server.uri()),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let request = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+    // The second lookup, by symbol, is served from the cache
+    for uri in [format!("/token/{}", jup), "/token/JUP".to_string()] {
+        let response = app.clone().oneshot(request(&uri)).await.expect("Should receive a response");
+        let status = response.status();
+        let metadata: TokenMetadataResponse = serde_json::from_slice(
+            &to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("should read body"),
+        )
+        .expect("should parse JSON");
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(metadata.address, jup);
+        assert_eq!(metadata.metadata.symbol.as_deref(), Some("JUP"));
+        assert_eq!(metadata.metadata.decimals, Some(6));
+        assert_eq!(
+            metadata.metadata.logo_uri.as_deref(),
+            Some("https://static.jup.ag/jup/icon.png")
+        );
+    }
+
+    let response = app
+        .oneshot(request("/token/unknownMint1111111111111111111111111111111"))
+        .await
+        .expect("Should receive a response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,91 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::token_search::TokenSearchResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn search_tokens_annotates_tracked_tokens_with_mock() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/search"))
+        .and(query_param("keyword", "jup"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    {
+                        "type": "token",
+                        "result": [
+                            { "address": "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN", "name": "Jupiter", "symbol": "JUP" },
+                            { "address": "jupSoLaHXQiZZTSfEWMTRRgpnyFm8f6sZdosWBjx93v", "name": "Jupiter Staked SOL", "symbol": "JupSOL" }
+                        ]
+                    },
+                    { "type": "market", "result": [] }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 1.0 },
+                    { "unixTime": 1700086400, "value": 1.1 },
+                    { "unixTime": 1700172800, "value": 0.9 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: format!("{}/history", server.uri()),
+        birdeye_search_url: format!("{}/search", server.uri()),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    volatility_cache
+        .add_token("JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN".to_string())
+        .await
+        .expect("Should track token");
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/tokens/search?q=jup")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+
+    let status = response.status();
+    let search: TokenSearchResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(search.results.len(), 2);
+    assert!(search.results[0].tracked);
+    assert!(search.results[0].volatility.is_some());
+    assert!(!search.results[1].tracked);
+    assert_eq!(search.results[1].symbol.as_deref(), Some("JupSOL"));
+}
@@ -0,0 +1,68 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::tracked_tokens::TrackedTokensResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn tracked_tokens_lists_cached_values_with_mock() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 104.0 },
+                    { "unixTime": 1700172800, "value": 99.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    for token_address in ["tokenB", "tokenA"] {
+        volatility_cache
+            .add_token(token_address.to_string())
+            .await
+            .expect("token should be cached");
+    }
+    let app = register_routes(AppState::new(config, volatility_cache));
+
+    let response = app
+        .oneshot(Request::builder().uri("/tokens").body(Body::empty()).unwrap())
+        .await
+        .expect("Should receive a response");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let tracked: TrackedTokensResponse = serde_json::from_slice(
+        &to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("should read body"),
+    )
+    .expect("should parse JSON");
+
+    let addresses: Vec<&str> = tracked
+        .tokens
+        .iter()
+        .map(|token| token.token_address.as_str())
+        .collect();
+    assert_eq!(addresses, vec!["tokenA", "tokenB"]);
+    let token = &tracked.tokens[0];
+    assert!(token.volatility > 0.0);
+    assert_eq!(token.window_days, 90);
+    assert_eq!(token.data_points, 3);
+    assert_eq!(token.provider, "birdeye");
+}
@@ -0,0 +1,85 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::updates::UpdatesResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+async fn send_updates_request(app: Router, since: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/updates?since={}", since))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    (status, serde_json::from_slice(&body).expect("should parse JSON"))
+}
+
+#[tokio::test]
+async fn get_updates_returns_only_tokens_changed_since() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 101.0 },
+                    { "unixTime": 1700172800, "value": 99.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    volatility_cache
+        .add_token("tokenA".to_string())
+        .await
+        .expect("should cache tokenA");
+    let app = register_routes(AppState::new(config, volatility_cache.clone()));
+
+    let (status, body) = send_updates_request(app.clone(), "0").await;
+    assert_eq!(status, StatusCode::OK);
+    let updates: UpdatesResponse = serde_json::from_value(body.clone()).expect("should parse updates");
+    let tokens: Vec<&str> = updates.updates.iter().map(|update| update.token_address.as_str()).collect();
+    assert_eq!(tokens, vec!["tokenA"]);
+
+    // Refreshing tokenA to the same values is not a change; tracking tokenB is
+    let as_of = body["asOf"].as_str().expect("asOf should be a timestamp").to_string();
+    volatility_cache
+        .add_token("tokenA".to_string())
+        .await
+        .expect("should refresh tokenA");
+    volatility_cache
+        .add_token("tokenB".to_string())
+        .await
+        .expect("should cache tokenB");
+
+    let (status, body) = send_updates_request(app.clone(), &as_of).await;
+    assert_eq!(status, StatusCode::OK);
+    let updates: UpdatesResponse = serde_json::from_value(body).expect("should parse updates");
+    let tokens: Vec<&str> = updates.updates.iter().map(|update| update.token_address.as_str()).collect();
+    assert_eq!(tokens, vec!["tokenB"]);
+
+    let (status, _) = send_updates_request(app, "last-tuesday").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,75 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use historical_volatility_api::background::volatility_cache::VolatilityCache;
+use historical_volatility_api::config::AppConfig;
+use historical_volatility_api::routes::register_routes;
+use historical_volatility_api::routes::volatility_history::VolatilityHistoryResponse;
+use historical_volatility_api::state::AppState;
+use tower::ServiceExt;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+async fn send_history_request(app: Router, query: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/volatilityHistory?{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("Should receive a response");
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("should read body");
+    (status, serde_json::from_slice(&body).expect("should parse JSON"))
+}
+
+#[tokio::test]
+async fn get_volatility_history_returns_computed_values() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "success": true,
+            "data": {
+                "items": [
+                    { "unixTime": 1700000000, "value": 100.0 },
+                    { "unixTime": 1700086400, "value": 101.0 },
+                    { "unixTime": 1700172800, "value": 99.0 }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let config = AppConfig {
+        birdeye_api_key: "dummy-key".to_string(),
+        birdeye_base_url: server.uri(),
+        ..AppConfig::default()
+    };
+    let volatility_cache = VolatilityCache::new(config.clone());
+    volatility_cache
+        .add_token("tokenA".to_string())
+        .await
+        .expect("should cache tokenA");
+    let app = register_routes(AppState::new(config, volatility_cache.clone()));
+
+    let (status, body) = send_history_request(app.clone(), "tokenAddress=tokenA&days=7").await;
+    assert_eq!(status, StatusCode::OK);
+    let history: VolatilityHistoryResponse = serde_json::from_value(body).expect("should parse history");
+    assert_eq!((history.window_days, history.days), (90, 7));
+    let volatilities: Vec<f64> = history.points.iter().map(|point| point.volatility).collect();
+    assert_eq!(volatilities, vec![volatility_cache.get_volatility("tokenA").await.unwrap()]);
+
+    let (status, body) = send_history_request(app.clone(), "tokenAddress=untracked").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["points"], serde_json::json!([]));
+
+    let (status, _) = send_history_request(app.clone(), "tokenAddress=tokenA&windowDays=14").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send_history_request(app, "tokenAddress=tokenA&days=0").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}